@@ -0,0 +1,218 @@
+use crate::error::ParseError;
+use crate::store::{self, Line};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub const ANSWERS_DIR: &str = "./answers";
+
+/// How the sections of a [`PartAnswers`] file are named, e.g. `[day12]`.
+pub trait Sections {
+    const PREFIX: &'static str;
+    /// What a section header should look like, for parse errors.
+    const EXPECTED: &'static str;
+    type Key: Ord + Copy + FromStr + Display;
+}
+
+/// Answers to part 1 and part 2 of a set of puzzles, one section per puzzle:
+///
+/// ```toml
+/// [day1]
+/// part_1 = "142"
+/// part_2 = "281"
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PartAnswers<S: Sections> {
+    answers: BTreeMap<(S::Key, u8), String>,
+}
+
+/// Sections of the [`AnswerBook`], one per day.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Days;
+
+impl Sections for Days {
+    const PREFIX: &'static str = "day";
+    const EXPECTED: &'static str = "a [dayN] section";
+    type Key = u8;
+}
+
+/// Accepted answers for a single year, stored as `answers/{year}.toml`.
+pub type AnswerBook = PartAnswers<Days>;
+
+impl<S: Sections> Default for PartAnswers<S> {
+    fn default() -> Self {
+        PartAnswers {
+            answers: BTreeMap::new(),
+        }
+    }
+}
+
+impl<S: Sections> PartAnswers<S> {
+    pub fn get(&self, key: S::Key, part: u8) -> Option<&str> {
+        self.answers.get(&(key, part)).map(String::as_str)
+    }
+
+    pub fn set(&mut self, key: S::Key, part: u8, answer: &str) {
+        self.answers.insert((key, part), answer.to_string());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+impl AnswerBook {
+    pub fn path(dir: &Path, year: u16) -> PathBuf {
+        dir.join(format!("{year}.toml"))
+    }
+
+    /// Loads the book for `year`, or an empty one if nothing has been recorded yet.
+    pub fn load(dir: &Path, year: u16) -> io::Result<AnswerBook> {
+        store::load(&Self::path(dir, year))
+    }
+
+    pub fn save(&self, dir: &Path, year: u16) -> io::Result<()> {
+        store::save(&Self::path(dir, year), self)
+    }
+}
+
+impl<S: Sections> std::fmt::Display for PartAnswers<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        store::write_sections(
+            f,
+            self.answers.iter().map(|((key, part), answer)| {
+                (
+                    format!("{}{key}", S::PREFIX),
+                    format!("part_{part}"),
+                    store::quote(answer),
+                )
+            }),
+        )
+    }
+}
+
+impl<S: Sections> FromStr for PartAnswers<S> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = PartAnswers::default();
+        let mut section = None;
+
+        for line in store::lines(s) {
+            match line? {
+                Line::Section(name) => section = Some(name.number_after(S::PREFIX, S::EXPECTED)?),
+                Line::Pair(key, value) => {
+                    let part = match key.text() {
+                        "part_1" => 1,
+                        "part_2" => 2,
+                        _ => return Err(key.error("part_1 or part_2")),
+                    };
+                    let answer = value.string()?;
+                    let section = section.ok_or_else(|| key.error(S::EXPECTED))?;
+                    answers.set(section, part, &answer);
+                }
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing,
+}
+
+impl Verdict {
+    pub fn check(expected: Option<&str>, actual: &str) -> Verdict {
+        match expected {
+            Some(expected) if expected == actual => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            },
+            None => Verdict::Missing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_answer_book() {
+        let input = "# 2023
+        [day1]
+        part_1 = \"142\"
+        part_2 = \"281\"
+
+        [day13]
+        part_1 = \"say \\\"hi\\\"\"";
+
+        let book = AnswerBook::from_str(input).unwrap();
+
+        assert_eq!(book.get(1, 1), Some("142"));
+        assert_eq!(book.get(1, 2), Some("281"));
+        assert_eq!(book.get(13, 1), Some("say \"hi\""));
+        assert_eq!(book.get(13, 2), None);
+    }
+
+    #[test]
+    fn answer_book_round_trip() {
+        let mut book = AnswerBook::default();
+        book.set(10, 2, "5");
+        book.set(2, 1, "a\\b");
+        book.set(2, 2, "8");
+
+        assert_eq!(
+            book.to_string(),
+            "[day2]\npart_1 = \"a\\\\b\"\npart_2 = \"8\"\n\n[day10]\npart_2 = \"5\"\n"
+        );
+        assert_eq!(AnswerBook::from_str(&book.to_string()).unwrap(), book);
+
+        book.set(3, 1, "#..\n.##\n");
+        assert_eq!(AnswerBook::from_str(&book.to_string()).unwrap(), book);
+    }
+
+    #[test]
+    fn parse_answer_book_error() {
+        assert_eq!(
+            AnswerBook::from_str("part_1 = \"1\""),
+            Err(ParseError::new("a [dayN] section", "part_1"))
+        );
+        assert_eq!(
+            AnswerBook::from_str("[day1]\npart_x = \"1\""),
+            Err(ParseError::new("part_1 or part_2", "part_x").on_line(2))
+        );
+        assert_eq!(
+            AnswerBook::from_str("[day3]\npart_1 = \"1\"\npart_7 = \"x\""),
+            Err(ParseError::new("part_1 or part_2", "part_7").on_line(3))
+        );
+        assert_eq!(
+            AnswerBook::from_str("[day3]\npart_1 = 1"),
+            Err(ParseError::new("a quoted string", "1").at(2, 10))
+        );
+        assert_eq!(
+            AnswerBook::from_str("[dayx]"),
+            Err(ParseError::new("a [dayN] section", "dayx").at(1, 2))
+        );
+    }
+
+    #[test]
+    fn verdict_check() {
+        assert_eq!(Verdict::check(Some("1"), "1"), Verdict::Pass);
+        assert_eq!(
+            Verdict::check(Some("1"), "2"),
+            Verdict::Fail {
+                expected: String::from("1"),
+                actual: String::from("2")
+            }
+        );
+        assert_eq!(Verdict::check(None, "2"), Verdict::Missing);
+    }
+}
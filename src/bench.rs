@@ -0,0 +1,355 @@
+use crate::error::{Error, ParseError};
+use crate::runner::{format_duration, render_row, render_rule};
+use crate::solution::{Entry, Timings};
+use crate::store::{self, Line};
+use crate::utils::read_file_to_string;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+pub const BENCH_DIR: &str = "./bench";
+
+/// The timed steps of a solution, in the order they run.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Step {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Step {
+    pub const ALL: [Step; 3] = [Step::Parse, Step::Part1, Step::Part2];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Step::Parse => "parse",
+            Step::Part1 => "part_1",
+            Step::Part2 => "part_2",
+        }
+    }
+
+    fn of(&self, timings: &Timings) -> Duration {
+        match self {
+            Step::Parse => timings.parse,
+            Step::Part1 => timings.part_1,
+            Step::Part2 => timings.part_2,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Summarises at least one sample. The standard deviation is the sample one,
+    /// zero for a single sample.
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+        let mean = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / n as f64;
+        let variance = sorted
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / (n.max(2) - 1) as f64;
+
+        Stats {
+            mean: Duration::from_secs_f64(mean),
+            median,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BenchOptions {
+    /// Runs thrown away before measuring, to warm caches and the allocator.
+    pub warmup: usize,
+    pub samples: usize,
+}
+
+#[derive(Debug)]
+pub struct DayBench {
+    pub year: u16,
+    pub day: u8,
+    /// Statistics for each of [`Step::ALL`].
+    pub result: Result<[Stats; 3], Error>,
+}
+
+/// Solves `entry` `warmup + samples` times, reading its input once.
+pub fn bench_entry(dir: &Path, entry: &Entry, options: BenchOptions) -> DayBench {
+    let path = entry.input_path(dir);
+    let result = match read_file_to_string(&path) {
+        Ok(input) => measure(entry, &input, options).map_err(Error::from),
+        Err(source) => Err(Error::Io { path, source }),
+    };
+
+    DayBench {
+        year: entry.year,
+        day: entry.day,
+        result,
+    }
+}
+
+fn measure(entry: &Entry, input: &str, options: BenchOptions) -> Result<[Stats; 3], ParseError> {
+    for _ in 0..options.warmup {
+        (entry.solve)(input)?;
+    }
+
+    let mut timings = vec![];
+    for _ in 0..options.samples.max(1) {
+        timings.push((entry.solve)(input)?.timings);
+    }
+
+    Ok(Step::ALL.map(|step| {
+        let samples: Vec<Duration> = timings.iter().map(|t| step.of(t)).collect();
+        Stats::from_samples(&samples)
+    }))
+}
+
+/// Median timings saved by an earlier `aoc bench --save`, stored as `bench/{year}.toml`
+/// in nanoseconds:
+///
+/// ```toml
+/// [day12]
+/// parse = 41250
+/// part_1 = 1830000
+/// part_2 = 95200000
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Baseline {
+    medians: BTreeMap<(u8, Step), Duration>,
+}
+
+impl Baseline {
+    pub fn path(dir: &Path, year: u16) -> PathBuf {
+        dir.join(format!("{year}.toml"))
+    }
+
+    /// Loads the baseline for `year`, or an empty one if none has been saved yet.
+    pub fn load(dir: &Path, year: u16) -> io::Result<Baseline> {
+        store::load(&Self::path(dir, year))
+    }
+
+    pub fn save(&self, dir: &Path, year: u16) -> io::Result<()> {
+        store::save(&Self::path(dir, year), self)
+    }
+
+    pub fn get(&self, day: u8, step: Step) -> Option<Duration> {
+        self.medians.get(&(day, step)).copied()
+    }
+
+    pub fn set(&mut self, day: u8, step: Step, median: Duration) {
+        self.medians.insert((day, step), median);
+    }
+
+    /// Records the medians of every day that could be measured.
+    pub fn update<'a>(&mut self, benches: impl IntoIterator<Item = &'a DayBench>) {
+        for bench in benches {
+            if let Ok(stats) = &bench.result {
+                for (step, stats) in Step::ALL.iter().zip(stats) {
+                    self.set(bench.day, *step, stats.median);
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        store::write_sections(
+            f,
+            self.medians
+                .iter()
+                .map(|((day, step), median)| (format!("day{day}"), step.key(), median.as_nanos())),
+        )
+    }
+}
+
+impl FromStr for Baseline {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut baseline = Baseline::default();
+        let mut day: Option<u8> = None;
+
+        for line in store::lines(s) {
+            match line? {
+                Line::Section(name) => day = Some(name.number_after("day", "a [dayN] section")?),
+                Line::Pair(key, value) => {
+                    let step = Step::ALL
+                        .into_iter()
+                        .find(|step| step.key() == key.text())
+                        .ok_or_else(|| key.error("parse, part_1 or part_2"))?;
+                    let nanos: u64 = value.parse("a number of nanoseconds")?;
+
+                    let day = day.ok_or_else(|| key.error("a [dayN] section first"))?;
+                    baseline.set(day, step, Duration::from_nanos(nanos));
+                }
+            }
+        }
+
+        Ok(baseline)
+    }
+}
+
+/// The relative change from `baseline` to `median`, e.g. `+12.5%` for a slowdown.
+pub fn format_change(median: Duration, baseline: Duration) -> String {
+    if baseline.is_zero() {
+        return String::from("-");
+    }
+    let change = (median.as_secs_f64() / baseline.as_secs_f64() - 1.0) * 100.0;
+    format!("{:+.1}%", change)
+}
+
+const HEADERS: [&str; 8] = [
+    "Year", "Day", "Step", "Mean", "Median", "Std dev", "Baseline", "Change",
+];
+const RIGHT_ALIGNED: [bool; 8] = [true, true, false, true, true, true, true, true];
+
+/// Renders a row per step of every day, comparing medians against `baselines` by year.
+pub fn render_table(benches: &[DayBench], baselines: &BTreeMap<u16, Baseline>) -> String {
+    let mut rows: Vec<[String; 8]> = vec![];
+
+    for bench in benches {
+        let stats = match &bench.result {
+            Ok(stats) => stats,
+            Err(error) => {
+                let mut row = [(); 8].map(|_| String::from("-"));
+                row[0] = bench.year.to_string();
+                row[1] = bench.day.to_string();
+                row[2] = String::from(if error.is_missing_input() {
+                    "no input"
+                } else {
+                    "error"
+                });
+                rows.push(row);
+                continue;
+            }
+        };
+
+        for (step, stats) in Step::ALL.iter().zip(stats) {
+            let baseline = baselines
+                .get(&bench.year)
+                .and_then(|b| b.get(bench.day, *step));
+            rows.push([
+                bench.year.to_string(),
+                bench.day.to_string(),
+                step.key().to_string(),
+                format_duration(stats.mean),
+                format_duration(stats.median),
+                format_duration(stats.stddev),
+                baseline.map_or(String::from("-"), format_duration),
+                baseline.map_or(String::from("-"), |b| format_change(stats.median, b)),
+            ]);
+        }
+    }
+
+    let mut widths: Vec<usize> = HEADERS.iter().map(|h| h.chars().count()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let header: Vec<String> = HEADERS.iter().map(|h| h.to_string()).collect();
+    let mut out = String::new();
+    out.push_str(&render_row(&header, &widths, &RIGHT_ALIGNED));
+    out.push_str(&render_rule(&widths));
+    for row in &rows {
+        out.push_str(&render_row(row, &widths, &RIGHT_ALIGNED));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_test() {
+        let samples: Vec<Duration> = [4, 2, 9, 5]
+            .into_iter()
+            .map(Duration::from_micros)
+            .collect();
+        let stats = Stats::from_samples(&samples);
+
+        assert_eq!(stats.mean, Duration::from_micros(5));
+        assert_eq!(
+            stats.median,
+            Duration::from_micros(4) + Duration::from_nanos(500)
+        );
+        assert_eq!(stats.stddev.as_nanos(), 2944);
+        assert_eq!(
+            Stats::from_samples(&[Duration::from_millis(3)]).stddev,
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.set(12, Step::Part2, Duration::from_millis(95));
+        baseline.set(3, Step::Parse, Duration::from_nanos(1_250));
+        baseline.set(3, Step::Part1, Duration::from_micros(40));
+
+        assert_eq!(
+            baseline.to_string(),
+            "[day3]\nparse = 1250\npart_1 = 40000\n\n[day12]\npart_2 = 95000000\n"
+        );
+        assert_eq!(Baseline::from_str(&baseline.to_string()), Ok(baseline));
+        assert_eq!(
+            Baseline::from_str("[day1]\npart_3 = 5"),
+            Err(ParseError::new("parse, part_1 or part_2", "part_3").on_line(2))
+        );
+    }
+
+    #[test]
+    fn render_table_test() {
+        let stats = |micros| Stats {
+            mean: Duration::from_micros(micros),
+            median: Duration::from_micros(micros),
+            stddev: Duration::from_micros(1),
+        };
+        let benches = vec![
+            DayBench {
+                year: 2023,
+                day: 13,
+                result: Ok([stats(10), stats(200), stats(40)]),
+            },
+            DayBench {
+                year: 2023,
+                day: 14,
+                result: Err(Error::from(ParseError::new("a row", ""))),
+            },
+        ];
+        let mut baseline = Baseline::default();
+        baseline.set(13, Step::Part1, Duration::from_micros(250));
+        let baselines = BTreeMap::from([(2023, baseline)]);
+
+        assert_eq!(
+            render_table(&benches, &baselines),
+            "Year  Day  Step       Mean   Median  Std dev  Baseline  Change
+--------------------------------------------------------------
+2023   13  parse    10.0µs   10.0µs    1.0µs         -       -
+2023   13  part_1  200.0µs  200.0µs    1.0µs   250.0µs  -20.0%
+2023   13  part_2   40.0µs   40.0µs    1.0µs         -       -
+2023   14  error         -        -        -         -       -
+"
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use crate::config::Config;
+use crate::session::Session;
+use reqwest::blocking::{Client, Response};
+use std::time::Duration;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_USER_AGENT: &str =
+    concat!("github.com/jessebond2/aoc v", env!("CARGO_PKG_VERSION"));
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ClientConfig {
+    pub base_url: String,
+    pub user_agent: String,
+    pub timeout: Duration,
+}
+
+impl ClientConfig {
+    /// Flag or environment values win over the config file, which wins over the defaults.
+    pub fn resolve(
+        base_url: Option<String>,
+        user_agent: Option<String>,
+        timeout: Option<u64>,
+        config: &Config,
+    ) -> ClientConfig {
+        ClientConfig {
+            base_url: base_url
+                .or_else(|| config.base_url.clone())
+                .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            user_agent: user_agent
+                .or_else(|| config.user_agent.clone())
+                .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string()),
+            timeout: Duration::from_secs(
+                timeout.or(config.timeout).unwrap_or(DEFAULT_TIMEOUT_SECS),
+            ),
+        }
+    }
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig::resolve(None, None, None, &Config::default())
+    }
+}
+
+pub struct AocClient {
+    client: Client,
+    config: ClientConfig,
+}
+
+impl AocClient {
+    pub fn new(config: ClientConfig) -> reqwest::Result<AocClient> {
+        let client = Client::builder()
+            .user_agent(config.user_agent.as_str())
+            .timeout(config.timeout)
+            .build()?;
+
+        Ok(AocClient { client, config })
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.config.base_url.trim_end_matches('/'), path)
+    }
+
+    /// GETs `path` as the user behind `session`.
+    pub fn page(&self, path: &str, session: &Session) -> reqwest::Result<Response> {
+        self.client
+            .get(self.url(path))
+            .header(reqwest::header::COOKIE, session.cookie())
+            .send()
+    }
+
+    pub fn input(&self, year: u16, day: u8, session: &Session) -> reqwest::Result<Response> {
+        self.page(&format!("/{year}/day/{day}/input"), session)
+    }
+
+    pub fn answer(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+        session: &Session,
+    ) -> reqwest::Result<Response> {
+        self.client
+            .post(self.url(&format!("/{year}/day/{day}/answer")))
+            .header(reqwest::header::COOKIE, session.cookie())
+            .form(&[("level", part.to_string().as_str()), ("answer", answer)])
+            .send()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::TestServer;
+
+    #[test]
+    fn resolve_precedence() {
+        let config = Config {
+            base_url: Some(String::from("http://from-file")),
+            user_agent: None,
+            timeout: Some(5),
+        };
+
+        assert_eq!(
+            ClientConfig::resolve(Some(String::from("http://from-flag")), None, None, &config),
+            ClientConfig {
+                base_url: String::from("http://from-flag"),
+                user_agent: String::from(DEFAULT_USER_AGENT),
+                timeout: Duration::from_secs(5),
+            }
+        );
+        assert_eq!(
+            ClientConfig::resolve(None, None, Some(1), &config).base_url,
+            "http://from-file"
+        );
+        assert_eq!(ClientConfig::default().base_url, "https://adventofcode.com");
+    }
+
+    #[test]
+    fn url_test() {
+        let client = AocClient::new(ClientConfig {
+            base_url: String::from("http://localhost:8080/"),
+            ..ClientConfig::default()
+        })
+        .unwrap();
+
+        assert_eq!(
+            client.url("/2023/day/1/input"),
+            "http://localhost:8080/2023/day/1/input"
+        );
+    }
+
+    #[test]
+    fn input_request() {
+        let server = TestServer::new(vec![(200, "1abc2\n")]);
+        let client = AocClient::new(ClientConfig {
+            base_url: server.url.clone(),
+            user_agent: String::from("aoc-test"),
+            timeout: Duration::from_secs(5),
+        })
+        .unwrap();
+
+        let session = Session::new("abc").unwrap();
+        let response = client.input(2023, 1, &session).unwrap();
+        let request = server.request();
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.text().unwrap(), "1abc2\n");
+        assert!(request.starts_with("GET /2023/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("user-agent: aoc-test\r\n"));
+        assert!(request.contains("cookie: session=abc\r\n"));
+    }
+}
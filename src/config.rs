@@ -0,0 +1,109 @@
+use crate::error::ParseError;
+use crate::store::{self, Line};
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Settings read from `$XDG_CONFIG_HOME/aoc/config.toml`, falling back to
+/// `~/.config/aoc/config.toml`:
+///
+/// ```toml
+/// base_url = "https://adventofcode.com"
+/// user_agent = "github.com/you/aoc by you@example.com"
+/// timeout = 30
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Config {
+    pub base_url: Option<String>,
+    pub user_agent: Option<String>,
+    pub timeout: Option<u64>,
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir).join("aoc")),
+        _ => env::var_os("HOME").map(|home| PathBuf::from(home).join(".config").join("aoc")),
+    }
+}
+
+impl Config {
+    pub fn load() -> io::Result<Config> {
+        match config_dir() {
+            Some(dir) => Config::load_from(&dir.join("config.toml")),
+            None => Ok(Config::default()),
+        }
+    }
+
+    /// Loads `path`, or the default config if it doesn't exist.
+    pub fn load_from(path: &Path) -> io::Result<Config> {
+        store::load(path)
+            .map_err(|error| io::Error::new(error.kind(), format!("{}: {error}", path.display())))
+    }
+}
+
+impl FromStr for Config {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut config = Config::default();
+
+        for line in store::lines(s) {
+            let (key, value) = match line? {
+                Line::Section(name) => return Err(name.error("key = value")),
+                Line::Pair(key, value) => (key, value),
+            };
+
+            match key.text() {
+                "base_url" => config.base_url = Some(value.string()?),
+                "user_agent" => config.user_agent = Some(value.string()?),
+                "timeout" => config.timeout = Some(value.parse("a number of seconds")?),
+                _ => {}
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_config() {
+        let input = "# local stand-in
+        base_url = \"http://127.0.0.1:8080\"
+        timeout = 5
+        unknown = \"ignored\"";
+
+        assert_eq!(
+            Config::from_str(input).unwrap(),
+            Config {
+                base_url: Some(String::from("http://127.0.0.1:8080")),
+                user_agent: None,
+                timeout: Some(5),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_config_error() {
+        assert_eq!(
+            Config::from_str("timeout = soon"),
+            Err(ParseError::new("a number of seconds", "soon").at(1, 11))
+        );
+        assert_eq!(
+            Config::from_str("\nbase_url = http://localhost"),
+            Err(ParseError::new("a quoted string", "http://localhost").at(2, 12))
+        );
+    }
+
+    #[test]
+    fn missing_config_file() {
+        assert_eq!(
+            Config::load_from(Path::new("./does/not/exist.toml")).unwrap(),
+            Config::default()
+        );
+    }
+}
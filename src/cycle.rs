@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where the sequence `x, step(x), step(step(x)), ...` starts repeating: the states at
+/// `prefix..prefix + period` recur forever after the first `prefix` states.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Cycle {
+    pub prefix: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The earliest index holding the same state as index `n`.
+    pub fn index(&self, n: usize) -> usize {
+        if n < self.prefix {
+            n
+        } else {
+            self.prefix + (n - self.prefix) % self.period
+        }
+    }
+}
+
+/// Brent's algorithm, comparing states without storing them. Never returns if the
+/// sequence does not cycle.
+pub fn brent<S: Clone + Eq>(start: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let (mut power, mut period) = (1, 1);
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+
+    Cycle { prefix, period }
+}
+
+/// Floyd's tortoise and hare, comparing states without storing them. Never returns if
+/// the sequence does not cycle.
+pub fn floyd<S: Clone + Eq>(start: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    let mut prefix = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+
+    let mut period = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+
+    Cycle { prefix, period }
+}
+
+/// Steps until a state repeats, or until state `limit` is reached first. Returns the
+/// states seen in order and the cycle, if one was found.
+fn walk<S: Clone + Eq + Hash>(
+    start: S,
+    mut step: impl FnMut(&S) -> S,
+    limit: usize,
+) -> (Vec<S>, Option<Cycle>) {
+    let mut seen = HashMap::from([(start.clone(), 0)]);
+    let mut states = vec![start];
+    while states.len() <= limit {
+        let next = step(states.last().unwrap());
+        if let Some(&prefix) = seen.get(&next) {
+            let period = states.len() - prefix;
+            return (states, Some(Cycle { prefix, period }));
+        }
+        seen.insert(next.clone(), states.len());
+        states.push(next);
+    }
+
+    (states, None)
+}
+
+/// Finds the cycle by remembering every state, which takes the fewest steps. Never
+/// returns if the sequence does not cycle.
+pub fn find_cycle<S: Clone + Eq + Hash>(start: S, step: impl FnMut(&S) -> S) -> Cycle {
+    walk(start, step, usize::MAX).1.unwrap()
+}
+
+/// The state after `n` steps from `start`, skipping ahead once the sequence repeats.
+pub fn nth_state<S: Clone + Eq + Hash>(start: S, step: impl FnMut(&S) -> S, n: usize) -> S {
+    let (mut states, cycle) = walk(start, step, n);
+    match cycle {
+        Some(cycle) => states.swap_remove(cycle.index(n)),
+        None => states.swap_remove(n),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(x: &u64) -> u64 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn detection_test() {
+        let expected = find_cycle(3, step);
+        let mut sequence = vec![3];
+        for _ in 0..expected.prefix + expected.period {
+            sequence.push(step(sequence.last().unwrap()));
+        }
+
+        assert_eq!(
+            sequence[expected.prefix],
+            sequence[expected.prefix + expected.period]
+        );
+        assert_eq!(brent(3, step), expected);
+        assert_eq!(floyd(3, step), expected);
+        assert_eq!(
+            find_cycle(0, |x| (x + 1) % 7),
+            Cycle {
+                prefix: 0,
+                period: 7
+            }
+        );
+    }
+
+    #[test]
+    fn nth_state_test() {
+        let mut x = 3;
+        for n in 0..50 {
+            assert_eq!(nth_state(3, step, n), x);
+            x = step(&x);
+        }
+
+        let cycle = Cycle {
+            prefix: 2,
+            period: 3,
+        };
+        assert_eq!(cycle.index(1), 1);
+        assert_eq!(cycle.index(5), 2);
+        assert_eq!(cycle.index(1_000_000_000), 2 + (1_000_000_000 - 2) % 3);
+    }
+}
@@ -0,0 +1,232 @@
+use crate::client::AocClient;
+use crate::session::Session;
+use reqwest::StatusCode;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const INPUT_DIR: &str = "./input";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DownloadOutcome {
+    Saved,
+    Cached,
+    /// 404, the puzzle hasn't unlocked yet.
+    Locked,
+    /// 400 or 500, usually a missing or expired session cookie.
+    BadSession,
+    Failed(String),
+}
+
+impl DownloadOutcome {
+    /// Whether the input couldn't be fetched for a reason other than the day being
+    /// locked.
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            DownloadOutcome::BadSession | DownloadOutcome::Failed(_)
+        )
+    }
+}
+
+impl std::fmt::Display for DownloadOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DownloadOutcome::Saved => write!(f, "saved"),
+            DownloadOutcome::Cached => write!(f, "already cached"),
+            DownloadOutcome::Locked => write!(f, "not unlocked yet"),
+            DownloadOutcome::BadSession => write!(f, "rejected, check the session cookie"),
+            DownloadOutcome::Failed(reason) => write!(f, "failed: {reason}"),
+        }
+    }
+}
+
+pub fn input_path(dir: &Path, year: u16, day: u8) -> PathBuf {
+    dir.join(year.to_string()).join(format!("day{day}.txt"))
+}
+
+/// Downloads one day's input into `dir/{year}/day{day}.txt`, skipping days that are
+/// already cached unless `force` is set. Error pages are never written to disk.
+pub fn download_day(
+    client: &AocClient,
+    dir: &Path,
+    year: u16,
+    day: u8,
+    session: &Session,
+    force: bool,
+) -> DownloadOutcome {
+    let path = input_path(dir, year, day);
+    if !force && path.exists() {
+        return DownloadOutcome::Cached;
+    }
+
+    let response = match client.input(year, day, session) {
+        Ok(response) => response,
+        Err(error) => return DownloadOutcome::Failed(error.to_string()),
+    };
+
+    match response.status() {
+        StatusCode::OK => {}
+        StatusCode::NOT_FOUND => return DownloadOutcome::Locked,
+        StatusCode::BAD_REQUEST | StatusCode::INTERNAL_SERVER_ERROR => {
+            return DownloadOutcome::BadSession
+        }
+        status => return DownloadOutcome::Failed(format!("unexpected status {status}")),
+    }
+
+    let body = match response.bytes() {
+        Ok(body) => body,
+        Err(error) => return DownloadOutcome::Failed(error.to_string()),
+    };
+    if body.is_empty() {
+        return DownloadOutcome::Failed(String::from("empty response"));
+    }
+
+    if let Some(parent) = path.parent() {
+        if let Err(error) = fs::create_dir_all(parent) {
+            return DownloadOutcome::Failed(error.to_string());
+        }
+    }
+    match fs::write(&path, &body) {
+        Ok(_) => DownloadOutcome::Saved,
+        Err(error) => DownloadOutcome::Failed(error.to_string()),
+    }
+}
+
+/// Downloads days `1..=day`, printing each outcome followed by a summary. Returns
+/// false if any day failed to download.
+pub fn download(
+    client: &AocClient,
+    dir: &Path,
+    year: u16,
+    day: u8,
+    session: &Session,
+    force: bool,
+) -> bool {
+    let mut outcomes = vec![];
+    for day in 1..=day {
+        let outcome = download_day(client, dir, year, day, session, force);
+        println!(
+            "Day {day:>2}: {outcome} ({})",
+            input_path(dir, year, day).display()
+        );
+        let stop = outcome == DownloadOutcome::BadSession;
+        outcomes.push(outcome);
+        if stop {
+            break;
+        }
+    }
+
+    let count = |f: fn(&DownloadOutcome) -> bool| outcomes.iter().filter(|o| f(o)).count();
+    println!(
+        "{} saved, {} cached, {} locked, {} failed",
+        count(|o| *o == DownloadOutcome::Saved),
+        count(|o| *o == DownloadOutcome::Cached),
+        count(|o| *o == DownloadOutcome::Locked),
+        count(DownloadOutcome::is_failure),
+    );
+
+    !outcomes.iter().any(DownloadOutcome::is_failure)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::ClientConfig;
+    use crate::test_server::TestServer;
+    use std::env;
+
+    fn setup(name: &str, responses: Vec<(u16, &str)>) -> (TestServer, AocClient, PathBuf) {
+        let server = TestServer::new(responses);
+        let client = AocClient::new(ClientConfig {
+            base_url: server.url.clone(),
+            ..ClientConfig::default()
+        })
+        .unwrap();
+        let dir = env::temp_dir().join(format!("aoc-download-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        (server, client, dir)
+    }
+
+    fn session() -> Session {
+        Session::new("abc").unwrap()
+    }
+
+    #[test]
+    fn saves_and_caches() {
+        let (server, client, dir) = setup("cache", vec![(200, "1abc2\n"), (200, "new\n")]);
+        let session = session();
+
+        assert_eq!(
+            download_day(&client, &dir, 2023, 1, &session, false),
+            DownloadOutcome::Saved
+        );
+        assert!(server.request().starts_with("GET /2023/day/1/input"));
+        assert_eq!(
+            fs::read_to_string(input_path(&dir, 2023, 1)).unwrap(),
+            "1abc2\n"
+        );
+
+        assert_eq!(
+            download_day(&client, &dir, 2023, 1, &session, false),
+            DownloadOutcome::Cached
+        );
+        assert_eq!(
+            download_day(&client, &dir, 2023, 1, &session, true),
+            DownloadOutcome::Saved
+        );
+        assert_eq!(
+            fs::read_to_string(input_path(&dir, 2023, 1)).unwrap(),
+            "new\n"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn refuses_error_pages() {
+        let (_server, client, dir) = setup(
+            "errors",
+            vec![
+                (
+                    404,
+                    "Please don't repeatedly request this endpoint before it unlocks!",
+                ),
+                (
+                    400,
+                    "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+                ),
+                (503, "busy"),
+            ],
+        );
+        let session = session();
+
+        assert_eq!(
+            download_day(&client, &dir, 2023, 1, &session, false),
+            DownloadOutcome::Locked
+        );
+        assert_eq!(
+            download_day(&client, &dir, 2023, 2, &session, false),
+            DownloadOutcome::BadSession
+        );
+        assert_eq!(
+            download_day(&client, &dir, 2023, 3, &session, false),
+            DownloadOutcome::Failed(String::from("unexpected status 503 Service Unavailable"))
+        );
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn download_reports_failures() {
+        let (_server, client, dir) = setup(
+            "range",
+            vec![(200, "1\n"), (404, "locked"), (200, "1\n"), (400, "log in")],
+        );
+        let session = session();
+
+        assert!(download(&client, &dir, 2023, 2, &session, true));
+        assert!(!download(&client, &dir, 2023, 2, &session, true));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
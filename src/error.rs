@@ -0,0 +1,188 @@
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::str::FromStr;
+
+/// Input that could not be parsed: the 1-based line and column of the offending text,
+/// what was expected there and what was found instead.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: &'static str,
+    pub text: String,
+}
+
+/// Anything that stops a day from being solved.
+#[derive(Debug)]
+pub enum Error {
+    Io {
+        path: String,
+        source: io::Error,
+    },
+    Parse(ParseError),
+    /// The solution panicked, with the panic's message when it had one.
+    Panic(String),
+}
+
+impl ParseError {
+    /// An error at the start of the input, moved into place with [`ParseError::at`]
+    /// or [`ParseError::on_line`] by whoever knows where `text` came from.
+    pub fn new(expected: &'static str, text: &str) -> ParseError {
+        ParseError {
+            line: 1,
+            column: 1,
+            expected,
+            text: text.to_string(),
+        }
+    }
+
+    pub fn at(self, line: usize, column: usize) -> ParseError {
+        ParseError {
+            line,
+            column,
+            ..self
+        }
+    }
+
+    /// Moves an error found while parsing a single line to line `line` of the input.
+    pub fn on_line(self, line: usize) -> ParseError {
+        ParseError { line, ..self }
+    }
+
+    /// Places the error at `part` when it is a slice of `line`, counting columns in
+    /// characters. Any other `part` leaves the column where it was.
+    pub fn within(self, line: &str, part: &str) -> ParseError {
+        let start = line.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize).wrapping_sub(start);
+        if offset > line.len() || offset + part.len() > line.len() {
+            return self;
+        }
+        let Some(before) = line.get(..offset) else {
+            return self;
+        };
+        ParseError {
+            column: before.chars().count() + 1,
+            ..self
+        }
+    }
+}
+
+/// Parses `part`, a slice of `line`, reporting where it sits in the line on failure.
+pub fn parse_field<T: FromStr>(
+    line: &str,
+    part: &str,
+    expected: &'static str,
+) -> Result<T, ParseError> {
+    part.parse()
+        .map_err(|_| ParseError::new(expected, part).within(line, part))
+}
+
+impl Error {
+    /// Whether the day failed only because its input hasn't been downloaded.
+    pub fn is_missing_input(&self) -> bool {
+        matches!(self, Error::Io { source, .. } if source.kind() == io::ErrorKind::NotFound)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {:?}",
+            self.line, self.column, self.expected, self.text
+        )
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "unable to read {path}: {source}"),
+            Error::Parse(error) => write!(f, "unable to parse input, {error}"),
+            Error::Panic(message) => write!(f, "the solution panicked: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Parse(error) => Some(error),
+            Error::Panic(_) => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Error {
+        Error::Parse(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_error_test() {
+        let line = "12 x4 7";
+        let error = parse_field::<u32>(line, &line[3..5], "a number").unwrap_err();
+
+        assert_eq!(error, ParseError::new("a number", "x4").at(1, 4));
+        assert_eq!(
+            error.on_line(3).to_string(),
+            "line 3, column 4: expected a number, found \"x4\""
+        );
+        assert_eq!(parse_field::<u32>(line, &line[6..], "a number"), Ok(7));
+    }
+
+    #[test]
+    fn within_test() {
+        let line = "é x4";
+        let other = String::from("x4");
+
+        assert_eq!(
+            ParseError::new("a number", "x4")
+                .within(line, &line[3..])
+                .column,
+            3
+        );
+        assert_eq!(
+            ParseError::new("a number", "x4")
+                .within(line, &other)
+                .column,
+            1
+        );
+        assert_eq!(
+            ParseError::new("a number", "é")
+                .within(&line[3..], line)
+                .column,
+            1
+        );
+        assert_eq!(
+            ParseError::new("a number", "x4")
+                .at(2, 9)
+                .within(&line[3..], line),
+            ParseError::new("a number", "x4").at(2, 9)
+        );
+    }
+
+    #[test]
+    fn error_test() {
+        let missing = Error::Io {
+            path: String::from("./input/2023/day1.txt"),
+            source: io::Error::from(io::ErrorKind::NotFound),
+        };
+        let parse = Error::from(ParseError::new("a digit", "x"));
+
+        assert!(missing.is_missing_input());
+        assert!(!parse.is_missing_input());
+        assert_eq!(
+            parse.to_string(),
+            "unable to parse input, line 1, column 1: expected a digit, found \"x\""
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use crate::answers::{PartAnswers, Sections};
+use crate::puzzle::Example;
+use crate::store;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const EXAMPLES_DIR: &str = "./examples";
+
+/// Where tests look for fixtures, independent of the directory they run from.
+const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples");
+
+pub fn example_path(dir: &Path, year: u16, day: u8, example: usize) -> PathBuf {
+    dir.join(year.to_string())
+        .join(format!("day{day}-{example}.txt"))
+}
+
+/// Sections of [`ExampleAnswers`], one per example of the day.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Examples;
+
+impl Sections for Examples {
+    const PREFIX: &'static str = "example";
+    const EXPECTED: &'static str = "an [exampleN] section";
+    type Key = usize;
+}
+
+/// Expected answers for the examples of a single day, stored as
+/// `examples/{year}/day{N}.toml`:
+///
+/// ```toml
+/// [example1]
+/// part_1 = "32000000"
+/// ```
+pub type ExampleAnswers = PartAnswers<Examples>;
+
+impl ExampleAnswers {
+    pub fn path(dir: &Path, year: u16, day: u8) -> PathBuf {
+        dir.join(year.to_string()).join(format!("day{day}.toml"))
+    }
+
+    /// Loads the answers for `day`, or an empty set if none were found yet.
+    pub fn load(dir: &Path, year: u16, day: u8) -> io::Result<ExampleAnswers> {
+        store::load(&Self::path(dir, year, day))
+    }
+
+    pub fn save(&self, dir: &Path, year: u16, day: u8) -> io::Result<()> {
+        store::save(&Self::path(dir, year, day), self)
+    }
+}
+
+/// Writes each example to `dir/{year}/day{N}-{k}.txt`, numbered from 1, and merges the
+/// detected answers into `dir/{year}/day{N}.toml`. Returns the paths written.
+///
+/// Fixtures that already exist may have been fixed up by hand, so they and their
+/// answers are kept unless `force` is set; only answers missing so far are added.
+pub fn save_examples(
+    examples: &[Example],
+    dir: &Path,
+    year: u16,
+    day: u8,
+    force: bool,
+) -> io::Result<Vec<PathBuf>> {
+    let mut answers = ExampleAnswers::load(dir, year, day)?;
+    let mut paths = vec![];
+
+    for (index, example) in examples.iter().enumerate() {
+        let path = example_path(dir, year, day, index + 1);
+        let keep = !force && path.exists();
+        if !keep {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, &example.input)?;
+            paths.push(path);
+        }
+
+        for (part, answer) in example.answers.iter().enumerate() {
+            let part = part as u8 + 1;
+            if let Some(answer) = answer {
+                if !keep || answers.get(index + 1, part).is_none() {
+                    answers.set(index + 1, part, answer);
+                }
+            }
+        }
+    }
+
+    if !answers.is_empty() {
+        answers.save(dir, year, day)?;
+    }
+
+    Ok(paths)
+}
+
+/// The input of example `k` for a day, for use in tests.
+pub fn example(year: u16, day: u8, k: usize) -> String {
+    let path = example_path(Path::new(FIXTURES_DIR), year, day, k);
+
+    fs::read_to_string(&path).unwrap_or_else(|error| {
+        panic!(
+            "Unable to read {}: {error}, run `aoc read -y {year} --day {day}` to extract it",
+            path.display()
+        )
+    })
+}
+
+/// The expected answer to `part` for example `k`, for use in tests.
+pub fn example_answer(year: u16, day: u8, k: usize, part: u8) -> String {
+    let dir = Path::new(FIXTURES_DIR);
+    let answers = ExampleAnswers::load(dir, year, day).unwrap();
+
+    match answers.get(k, part) {
+        Some(answer) => answer.to_string(),
+        None => panic!(
+            "No part {part} answer for example {k} in {}",
+            ExampleAnswers::path(dir, year, day).display()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParseError;
+    use std::env;
+    use std::str::FromStr;
+
+    #[test]
+    fn example_answers_round_trip() {
+        let mut answers = ExampleAnswers::default();
+        answers.set(3, 1, "11687500");
+        answers.set(1, 1, "32000000");
+        answers.set(1, 2, "a\"b");
+
+        assert_eq!(
+            answers.to_string(),
+            "[example1]\npart_1 = \"32000000\"\npart_2 = \"a\\\"b\"\n\n[example3]\npart_1 = \"11687500\"\n"
+        );
+        assert_eq!(
+            ExampleAnswers::from_str(&answers.to_string()).unwrap(),
+            answers
+        );
+        assert_eq!(
+            ExampleAnswers::from_str("[example1]\npart = \"1\""),
+            Err(ParseError::new("part_1 or part_2", "part").on_line(2))
+        );
+    }
+
+    #[test]
+    fn save_examples_test() {
+        let dir = env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let examples = vec![
+            Example {
+                input: String::from("1\n2\n"),
+                answers: [Some(String::from("3")), None],
+            },
+            Example {
+                input: String::from("4\n"),
+                answers: [None, Some(String::from("5"))],
+            },
+        ];
+
+        let mut answers = ExampleAnswers::default();
+        answers.set(1, 2, "edited by hand");
+        answers.save(&dir, 2023, 1).unwrap();
+
+        let paths = save_examples(&examples, &dir, 2023, 1, false).unwrap();
+
+        assert_eq!(
+            paths,
+            vec![dir.join("2023/day1-1.txt"), dir.join("2023/day1-2.txt")]
+        );
+        assert_eq!(fs::read_to_string(&paths[0]).unwrap(), "1\n2\n");
+        let answers = ExampleAnswers::load(&dir, 2023, 1).unwrap();
+        assert_eq!(answers.get(1, 1), Some("3"));
+        assert_eq!(answers.get(1, 2), Some("edited by hand"));
+        assert_eq!(answers.get(2, 2), Some("5"));
+
+        fs::write(&paths[0], "fixed\n").unwrap();
+        let mut detected = examples.clone();
+        detected[0].answers = [Some(String::from("4")), Some(String::from("6"))];
+        detected[1].input = String::from("7\n");
+
+        assert!(save_examples(&detected, &dir, 2023, 1, false)
+            .unwrap()
+            .is_empty());
+        assert_eq!(fs::read_to_string(&paths[0]).unwrap(), "fixed\n");
+        assert_eq!(fs::read_to_string(&paths[1]).unwrap(), "4\n");
+        let answers = ExampleAnswers::load(&dir, 2023, 1).unwrap();
+        assert_eq!(answers.get(1, 1), Some("3"));
+        assert_eq!(answers.get(1, 2), Some("edited by hand"));
+
+        assert_eq!(
+            save_examples(&detected, &dir, 2023, 1, true).unwrap(),
+            paths
+        );
+        assert_eq!(fs::read_to_string(&paths[0]).unwrap(), "1\n2\n");
+        assert_eq!(fs::read_to_string(&paths[1]).unwrap(), "7\n");
+        let answers = ExampleAnswers::load(&dir, 2023, 1).unwrap();
+        assert_eq!(answers.get(1, 1), Some("4"));
+        assert_eq!(answers.get(1, 2), Some("6"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn example_fixture() {
+        assert!(example(2023, 18, 1).starts_with("R 6 (#70c710)\n"));
+        assert_eq!(example_answer(2023, 18, 1, 1), "62");
+    }
+}
@@ -0,0 +1,338 @@
+use crate::error::ParseError;
+use crate::grid::Grid;
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A position on the plane, `y` growing downwards like rows in a puzzle input.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// The difference between two points.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    pub fn manhattan(&self, other: Point) -> i64 {
+        (*self - other).manhattan()
+    }
+
+    pub fn chebyshev(&self, other: Point) -> i64 {
+        (*self - other).chebyshev()
+    }
+
+    pub fn step(&self, direction: Direction) -> Point {
+        *self + direction.delta()
+    }
+
+    /// The neighbouring point in `direction`, if it is inside `grid`.
+    pub fn checked_step<T>(&self, direction: Direction, grid: &Grid<T>) -> Option<Point> {
+        let next = self.step(direction);
+        grid.contains(next).then_some(next)
+    }
+
+    /// The 4 orthogonal neighbours, clockwise from up.
+    pub fn neighbours(&self) -> [Point; 4] {
+        Direction::CARDINAL.map(|direction| self.step(direction))
+    }
+
+    /// All 8 neighbours, clockwise from up.
+    pub fn neighbours8(&self) -> [Point; 8] {
+        Direction::ALL.map(|direction| self.step(direction))
+    }
+}
+
+impl Vector {
+    pub const fn new(x: i64, y: i64) -> Vector {
+        Vector { x, y }
+    }
+
+    pub fn manhattan(&self) -> i64 {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn chebyshev(&self) -> i64 {
+        self.x.abs().max(self.y.abs())
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Point {
+        Point::new(x as i64, y as i64)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vector) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Vector) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Point) -> Vector {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Vector {
+        Vector::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: Vector) -> Vector {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: i64) -> Vector {
+        Vector::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Direction {
+    /// Up, right, down and left, clockwise.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+    /// Every direction, clockwise from up.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    fn from_index(index: usize) -> Direction {
+        Direction::ALL[index % 8]
+    }
+
+    fn index(&self) -> usize {
+        Direction::ALL.iter().position(|d| d == self).unwrap()
+    }
+
+    /// A quarter turn anticlockwise.
+    pub fn turn_left(&self) -> Direction {
+        Direction::from_index(self.index() + 6)
+    }
+
+    /// A quarter turn clockwise.
+    pub fn turn_right(&self) -> Direction {
+        Direction::from_index(self.index() + 2)
+    }
+
+    pub fn reverse(&self) -> Direction {
+        Direction::from_index(self.index() + 4)
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    /// The unit step in this direction.
+    pub fn delta(&self) -> Vector {
+        match self {
+            Direction::Up => Vector::new(0, -1),
+            Direction::UpRight => Vector::new(1, -1),
+            Direction::Right => Vector::new(1, 0),
+            Direction::DownRight => Vector::new(1, 1),
+            Direction::Down => Vector::new(0, 1),
+            Direction::DownLeft => Vector::new(-1, 1),
+            Direction::Left => Vector::new(-1, 0),
+            Direction::UpLeft => Vector::new(-1, -1),
+        }
+    }
+}
+
+/// Parses `^v<>`, `UDLR` and `NSEW`.
+impl TryFrom<char> for Direction {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'U' | 'N' => Ok(Direction::Up),
+            'v' | 'D' | 'S' => Ok(Direction::Down),
+            '<' | 'L' | 'W' => Ok(Direction::Left),
+            '>' | 'R' | 'E' => Ok(Direction::Right),
+            _ => Err(ParseError::new("a direction", &c.to_string())),
+        }
+    }
+}
+
+/// Parses a single `^v<>`, `UDLR` or `NSEW` character, or a diagonal such as `NE` or `UL`.
+impl FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseError::new("a direction", s);
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(c), None, None) => Direction::try_from(c).map_err(|_| error()),
+            (Some(a), Some(b), None) => match (Direction::try_from(a), Direction::try_from(b)) {
+                (Ok(Direction::Up), Ok(Direction::Right)) => Ok(Direction::UpRight),
+                (Ok(Direction::Up), Ok(Direction::Left)) => Ok(Direction::UpLeft),
+                (Ok(Direction::Down), Ok(Direction::Right)) => Ok(Direction::DownRight),
+                (Ok(Direction::Down), Ok(Direction::Left)) => Ok(Direction::DownLeft),
+                _ => Err(error()),
+            },
+            _ => Err(error()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_arithmetic() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -2);
+
+        assert_eq!(b - a, Vector::new(3, -4));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(a + Vector::new(1, 1) * 3, Point::new(4, 5));
+        assert_eq!(a - -Vector::new(1, 0), Point::new(2, 2));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+
+        let mut c = a;
+        c += Vector::new(2, 2);
+        c -= Vector::new(1, 0);
+        assert_eq!(c, Point::new(2, 4));
+    }
+
+    #[test]
+    fn turning() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::UpRight.turn_right(), Direction::DownRight);
+        assert_eq!(Direction::DownLeft.reverse(), Direction::UpRight);
+        assert!(Direction::UpLeft.is_diagonal());
+        assert!(!Direction::Down.is_diagonal());
+
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.reverse().delta(), -direction.delta());
+        }
+    }
+
+    #[test]
+    fn parse_direction() {
+        assert_eq!(Direction::try_from('^'), Ok(Direction::Up));
+        assert_eq!(Direction::try_from('R'), Ok(Direction::Right));
+        assert_eq!(Direction::try_from('S'), Ok(Direction::Down));
+        assert_eq!("<".parse(), Ok(Direction::Left));
+        assert_eq!("NE".parse(), Ok(Direction::UpRight));
+        assert_eq!("DL".parse(), Ok(Direction::DownLeft));
+        assert_eq!(
+            "NS".parse::<Direction>(),
+            Err(ParseError::new("a direction", "NS"))
+        );
+        assert_eq!(
+            Direction::try_from('x'),
+            Err(ParseError::new("a direction", "x"))
+        );
+    }
+
+    #[test]
+    fn grid_steps() {
+        let grid: Grid<Direction> = ">v\n<^".parse().unwrap();
+        let corner = Point::new(1, 1);
+
+        assert_eq!(grid[corner], Direction::Up);
+        assert_eq!(
+            corner.checked_step(grid[corner], &grid),
+            Some(Point::new(1, 0))
+        );
+        assert_eq!(corner.checked_step(Direction::Right, &grid), None);
+        assert_eq!(
+            Point::ORIGIN.neighbours(),
+            [
+                Point::new(0, -1),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(-1, 0)
+            ]
+        );
+        assert_eq!(Point::ORIGIN.neighbours8()[1], Point::new(1, -1));
+    }
+}
@@ -0,0 +1,409 @@
+use crate::error::ParseError;
+use crate::geometry::Point;
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// Offsets to the 4 orthogonal neighbours, clockwise from up.
+const DELTAS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+/// Offsets to all 8 neighbours, clockwise from up.
+const DELTAS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangular grid stored row by row. Cells are addressed by `(x, y)`, with
+/// `(0, 0)` in the top left corner and `y` growing downwards.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Panics unless `cells` holds exactly `width * height` values.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), width * height, "grid is not {width}x{height}");
+
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses one row per line, converting each character with `cell`. Lines are
+    /// trimmed so indented test input parses the same as a real input, and blank lines
+    /// may surround the grid but not split it.
+    pub fn parse(s: &str, cell: impl Fn(char) -> Option<T>) -> Result<Grid<T>, ParseError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+        let mut ended = false;
+
+        for (index, untrimmed) in s.lines().enumerate() {
+            let line = untrimmed.trim();
+            if line.is_empty() {
+                ended = height > 0;
+                continue;
+            }
+            let indent = untrimmed.len() - untrimmed.trim_start().len();
+            let indent = untrimmed[..indent].chars().count();
+            if ended {
+                return Err(ParseError::new("the end of the grid", line).at(index + 1, indent + 1));
+            }
+
+            let mut count = 0;
+            for (column, c) in line.chars().enumerate() {
+                cells.push(cell(c).ok_or_else(|| {
+                    ParseError::new("a grid cell", &c.to_string())
+                        .at(index + 1, indent + column + 1)
+                })?);
+                count += 1;
+            }
+            if *width.get_or_insert(count) != count {
+                return Err(ParseError::new("a row as wide as the first", line)
+                    .at(index + 1, indent + count.min(width.unwrap()) + 1));
+            }
+            height += 1;
+        }
+
+        Ok(Grid::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The position `(x, y)` if it lies inside the grid.
+    pub fn in_bounds(&self, x: isize, y: isize) -> Option<(usize, usize)> {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+
+        Some((x as usize, y as usize))
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.position(point).is_some()
+    }
+
+    /// The `(x, y)` position of `point`, if it lies inside the grid.
+    pub fn position(&self, point: Point) -> Option<(usize, usize)> {
+        self.in_bounds(point.x as isize, point.y as isize)
+    }
+
+    /// The position one `(dx, dy)` step away from `position`, if it is inside the grid.
+    pub fn step(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        self.in_bounds(x as isize + dx, y as isize + dy)
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        self.cells.get(y * self.width + x)
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        self.cells.get_mut(y * self.width + x)
+    }
+
+    /// The up to 4 orthogonal neighbours inside the grid, clockwise from up.
+    pub fn neighbours(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        DELTAS_4
+            .iter()
+            .filter_map(move |delta| self.step(position, *delta))
+    }
+
+    /// The up to 8 neighbours, diagonals included, inside the grid, clockwise from up.
+    pub fn neighbours8(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        DELTAS_8
+            .iter()
+            .filter_map(move |delta| self.step(position, *delta))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every position with its cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The first position, row by row, whose cell matches.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<(usize, usize)> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside the grid");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Builds a `width` by `height` grid where the cell at `(x, y)` is `f((x, y))`.
+    fn from_positions(width: usize, height: usize, f: impl Fn((usize, usize)) -> T) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(f)
+            .collect();
+
+        Grid::new(width, height, cells)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Swaps rows and columns, mirroring along the top left to bottom right diagonal.
+    pub fn transpose(&self) -> Grid<T> {
+        Grid::from_positions(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        Grid::from_positions(self.height, self.width, |(x, y)| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Grid<T> {
+        Grid::from_positions(self.height, self.width, |(x, y)| {
+            self[(self.width - 1 - y, x)].clone()
+        })
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        Grid::from_positions(self.width, self.height, |(x, y)| {
+            self[(self.width - 1 - x, y)].clone()
+        })
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T> {
+        Grid::from_positions(self.width, self.height, |(x, y)| {
+            self[(x, self.height - 1 - y)].clone()
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside the grid"
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside the grid"
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        match self.position(point) {
+            Some(position) => &self[position],
+            None => panic!("{point} is outside the grid"),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        match self.position(point) {
+            Some(position) => &mut self[position],
+            None => panic!("{point} is outside the grid"),
+        }
+    }
+}
+
+/// Parses character maps into any cell type that converts from `char`, e.g. `Grid<char>`.
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s, |c| T::try_from(c).ok())
+    }
+}
+
+/// Writes one line per row, without a trailing newline.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abc
+        def"
+        .parse()
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_test() {
+        let grid = grid();
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        let digits = Grid::parse("19\n28\n", |c| c.to_digit(10)).unwrap();
+        assert_eq!(digits.row(1), &[2, 8]);
+        assert_eq!(
+            Grid::parse("19\n2x\n", |c| c.to_digit(10)),
+            Err(ParseError::new("a grid cell", "x").at(2, 2))
+        );
+        assert_eq!(
+            "abc\nde".parse::<Grid<char>>(),
+            Err(ParseError::new("a row as wide as the first", "de").at(2, 3))
+        );
+
+        assert_eq!(
+            Grid::parse("\n  19\n  28\n\n", |c| c.to_digit(10)),
+            Ok(digits)
+        );
+        assert_eq!(
+            Grid::parse("19\n  2x", |c| c.to_digit(10)),
+            Err(ParseError::new("a grid cell", "x").at(2, 4))
+        );
+        assert_eq!(
+            "abc\n\ndef".parse::<Grid<char>>(),
+            Err(ParseError::new("the end of the grid", "def").at(3, 1))
+        );
+    }
+
+    #[test]
+    fn neighbours_test() {
+        let grid = grid();
+
+        assert_eq!(
+            grid.neighbours((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours((1, 1)).collect::<Vec<_>>(),
+            vec![(1, 0), (2, 1), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours8((1, 1)).collect::<Vec<_>>(),
+            vec![(1, 0), (2, 0), (2, 1), (0, 1), (0, 0)]
+        );
+        assert_eq!(grid.step((2, 1), (1, 0)), None);
+        assert_eq!(grid.in_bounds(-1, 0), None);
+        assert_eq!(grid.find(|c| *c == 'e'), Some((1, 1)));
+    }
+
+    #[test]
+    fn rows_and_columns_test() {
+        let grid = grid();
+
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&['a', 'b', 'c'], &['d', 'e', 'f']]
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    fn transform_test() {
+        let grid = grid();
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(
+            grid.rotate_clockwise().rotate_clockwise(),
+            grid.flip_horizontal().flip_vertical()
+        );
+    }
+
+    #[test]
+    fn map_test() {
+        let mut grid = grid().map(|c| *c as u32 - 'a' as u32);
+        grid[(0, 1)] = 9;
+
+        assert_eq!(grid.to_string(), "012\n945");
+    }
+}
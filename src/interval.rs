@@ -0,0 +1,303 @@
+use std::fmt::{self, Display, Formatter};
+use std::iter::Sum;
+use std::ops::{Range, Sub};
+
+/// A half-open range of values, `start..end`. Empty when `start >= end`.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+/// A set of values stored as sorted, disjoint intervals. Intervals that overlap or
+/// touch are coalesced as they are added.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    pub const fn new(start: T, end: T) -> Interval<T> {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        self.intersect(other).is_some()
+    }
+
+    /// The values in both intervals, `None` if there are none.
+    pub fn intersect(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        let interval = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!interval.is_empty()).then_some(interval)
+    }
+
+    /// The values below `at` and the values from `at` on, either of which may be `None`.
+    pub fn split(&self, at: T) -> (Option<Interval<T>>, Option<Interval<T>>) {
+        let below = Interval::new(self.start, self.end.min(at));
+        let above = Interval::new(self.start.max(at), self.end);
+        (
+            (!below.is_empty()).then_some(below),
+            (!above.is_empty()).then_some(above),
+        )
+    }
+
+    /// A single interval covering both, `None` if they neither overlap nor touch.
+    pub fn union(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+        if self.start > other.end || other.start > self.end {
+            return None;
+        }
+        Some(Interval::new(
+            self.start.min(other.start),
+            self.end.max(other.end),
+        ))
+    }
+
+    /// The values of `self` below and above `other`, either of which may be `None`.
+    pub fn subtract(&self, other: &Interval<T>) -> (Option<Interval<T>>, Option<Interval<T>>) {
+        if !self.overlaps(other) {
+            if self.is_empty() {
+                return (None, None);
+            }
+            if other.start < self.start {
+                return (None, Some(*self));
+            }
+            return (Some(*self), None);
+        }
+        (self.split(other.start).0, self.split(other.end).1)
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T>> Interval<T> {
+    /// The number of values in the interval.
+    pub fn len(&self) -> T {
+        self.end - self.start.min(self.end)
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Interval<T> {
+        Interval {
+            start: range.start,
+            end: range.end,
+        }
+    }
+}
+
+impl<T> From<Interval<T>> for Range<T> {
+    fn from(interval: Interval<T>) -> Range<T> {
+        interval.start..interval.end
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet { intervals: vec![] }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The disjoint intervals in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.intervals
+            .binary_search_by(|interval| {
+                if interval.end <= value {
+                    std::cmp::Ordering::Less
+                } else if interval.start > value {
+                    std::cmp::Ordering::Greater
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            })
+            .is_ok()
+    }
+
+    /// The smallest value in the set.
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    /// The value just past the largest in the set.
+    pub fn end(&self) -> Option<T> {
+        self.intervals.last().map(|interval| interval.end)
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        let first = self.intervals.partition_point(|i| i.end < interval.start);
+        let last = self.intervals.partition_point(|i| i.start <= interval.end);
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |acc, i| acc.union(i).unwrap());
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        self.iter().chain(other.iter()).copied().collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = vec![];
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            if let Some(interval) = x.intersect(y) {
+                result.push(interval);
+            }
+            if x.end <= y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        IntervalSet { intervals: result }
+    }
+
+    /// The values in `self` that are not in `other`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = vec![];
+        for interval in self.iter() {
+            let mut rest = Some(*interval);
+            for removed in other.iter() {
+                let Some(current) = rest else { break };
+                let (below, above) = current.subtract(removed);
+                result.extend(below);
+                rest = above;
+            }
+            result.extend(rest);
+        }
+        IntervalSet { intervals: result }
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Sum> IntervalSet<T> {
+    /// The number of values in the set.
+    pub fn len(&self) -> T {
+        self.iter().map(|interval| interval.len()).sum()
+    }
+}
+
+impl<T: Copy + Ord> Default for IntervalSet<T> {
+    fn default() -> IntervalSet<T> {
+        IntervalSet::new()
+    }
+}
+
+impl<T: Copy + Ord> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> IntervalSet<T> {
+        IntervalSet::from_iter([interval])
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> IntervalSet<T> {
+        let mut intervals: Vec<Interval<T>> = iter.into_iter().filter(|i| !i.is_empty()).collect();
+        intervals.sort();
+
+        let mut coalesced: Vec<Interval<T>> = vec![];
+        for interval in intervals {
+            match coalesced.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => coalesced.push(interval),
+            }
+        }
+        IntervalSet {
+            intervals: coalesced,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interval_test() {
+        let a = Interval::new(2, 8);
+        let b = Interval::from(5..12);
+
+        assert_eq!(a.len(), 6);
+        assert!(a.contains(2) && !a.contains(8));
+        assert_eq!(a.intersect(&b), Some(Interval::new(5, 8)));
+        assert_eq!(a.intersect(&Interval::new(8, 9)), None);
+        assert_eq!(a.union(&b), Some(Interval::new(2, 12)));
+        assert_eq!(a.union(&Interval::new(8, 9)), Some(Interval::new(2, 9)));
+        assert_eq!(a.union(&Interval::new(9, 10)), None);
+        assert_eq!(
+            a.split(4),
+            (Some(Interval::new(2, 4)), Some(Interval::new(4, 8)))
+        );
+        assert_eq!(a.split(1), (None, Some(a)));
+        assert_eq!(
+            a.subtract(&Interval::new(4, 6)),
+            (Some(Interval::new(2, 4)), Some(Interval::new(6, 8)))
+        );
+        assert_eq!(a.subtract(&b), (Some(Interval::new(2, 5)), None));
+        assert_eq!(a.subtract(&Interval::new(0, 1)), (None, Some(a)));
+        assert_eq!(a.subtract(&Interval::new(0, 10)), (None, None));
+        assert_eq!(Range::from(a), 2..8);
+    }
+
+    #[test]
+    fn interval_set_test() {
+        let mut set: IntervalSet<u64> = [
+            Interval::new(10, 20),
+            Interval::new(0, 5),
+            Interval::new(5, 7),
+            Interval::new(15, 25),
+            Interval::new(30, 30),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            vec![Interval::new(0, 7), Interval::new(10, 25)]
+        );
+        assert_eq!(set.len(), 22);
+        assert_eq!(set.min(), Some(0));
+        assert!(set.contains(6) && !set.contains(7) && set.contains(24));
+
+        set.insert(Interval::new(7, 10));
+        assert_eq!(set, IntervalSet::from(Interval::new(0, 25)));
+
+        let other: IntervalSet<u64> = [Interval::new(3, 5), Interval::new(20, 40)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            set.intersection(&other).iter().copied().collect::<Vec<_>>(),
+            vec![Interval::new(3, 5), Interval::new(20, 25)]
+        );
+        assert_eq!(
+            set.difference(&other).iter().copied().collect::<Vec<_>>(),
+            vec![Interval::new(0, 3), Interval::new(5, 20)]
+        );
+        assert_eq!(set.union(&other).end(), Some(40));
+    }
+}
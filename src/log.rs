@@ -0,0 +1,92 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much diagnostic output solutions write to stderr, raised by each `-d`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Level {
+    Off,
+    Debug,
+    Trace,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+
+impl Level {
+    /// The level for `-d` given `count` times.
+    pub fn from_count(count: u8) -> Level {
+        match count {
+            0 => Level::Off,
+            1 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Level::Off => "OFF",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+}
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => Level::Off,
+        1 => Level::Debug,
+        _ => Level::Trace,
+    }
+}
+
+/// Whether messages at `level` are written.
+pub fn enabled(level: Level) -> bool {
+    level != Level::Off && level <= self::level()
+}
+
+/// Writes a message to stderr when running with `-d` or more.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        $crate::log_at!($crate::log::Level::Debug, $($arg)*)
+    };
+}
+
+/// Writes a message to stderr when running with `-dd`.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        $crate::log_at!($crate::log::Level::Trace, $($arg)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! log_at {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::log::enabled($level) {
+            eprintln!(
+                "[{} {}] {}",
+                $level.label(),
+                module_path!(),
+                format_args!($($arg)*)
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_test() {
+        assert_eq!(Level::from_count(0), Level::Off);
+        assert_eq!(Level::from_count(1), Level::Debug);
+        assert_eq!(Level::from_count(3), Level::Trace);
+        assert!(Level::Debug < Level::Trace);
+        assert!(!enabled(Level::Off));
+    }
+}
@@ -1,8 +1,40 @@
-use crate::utils::{read_file_to_string, read_lines_to_vec};
+use crate::answers::{AnswerBook, Verdict};
+use crate::bench::{Baseline, BenchOptions};
+use crate::client::{AocClient, ClientConfig};
+use crate::config::Config;
+use crate::puzzle::Puzzle;
+use crate::session::{Session, SESSION_ENV};
+use crate::solution::{Answers, Entry, Registry};
+use crate::submit::{Feedback, GuessHistory, Precheck};
 use clap::{Parser, Subcommand};
-use reqwest::blocking::Client;
-use std::{fs::write, path::Path};
-
+use std::collections::{btree_map, hash_map, BTreeMap, HashMap};
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod config;
+pub mod cycle;
+pub mod download;
+pub mod error;
+pub mod examples;
+pub mod geometry;
+pub mod grid;
+pub mod interval;
+pub mod log;
+pub mod math;
+pub mod polygon;
+pub mod puzzle;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod session;
+pub mod solution;
+pub mod store;
+pub mod submit;
+#[cfg(test)]
+mod test_server;
 pub mod utils;
 pub mod y2021;
 pub mod y2023;
@@ -11,276 +43,588 @@ pub mod y2024;
 #[derive(Subcommand, Debug)]
 enum Commands {
     Run {
-        #[arg(long, default_value_t = 25)]
+        /// Day to run, every registered day of the year when omitted
+        #[arg(long)]
+        day: Option<u8>,
+
+        /// Run every registered day of every year
+        #[arg(long, conflicts_with = "day")]
+        all: bool,
+
+        /// Run this many days at once, and both parts of a day side by side
+        #[arg(short, long, default_value_t = 1)]
+        jobs: usize,
+    },
+    /// Save accepted answers to answers/{year}.toml
+    Record {
+        #[arg(long)]
         day: u8,
+
+        /// Only record this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Record this answer instead of running the solution
+        #[arg(long, requires = "part")]
+        answer: Option<String>,
+    },
+    /// Check solutions against the recorded answers
+    Verify {
+        /// Day to verify, every registered day of the year when omitted
+        #[arg(long)]
+        day: Option<u8>,
+
+        /// Verify every registered day of every year
+        #[arg(long, conflicts_with = "day")]
+        all: bool,
+
+        /// Run this many days at once, and both parts of a day side by side
+        #[arg(short, long, default_value_t = 1)]
+        jobs: usize,
     },
+    /// Time every registered day of the year over repeated runs, against the saved baseline
+    Bench {
+        /// Day to benchmark, every registered day of the year when omitted
+        #[arg(long)]
+        day: Option<u8>,
+
+        /// Benchmark every registered day of every year
+        #[arg(long, conflicts_with = "day")]
+        all: bool,
+
+        /// Untimed runs before measuring
+        #[arg(long, default_value_t = 3)]
+        warmup: usize,
+
+        /// Timed runs to take statistics over
+        #[arg(long, default_value_t = 10)]
+        samples: usize,
+
+        /// Save the medians to bench/{year}.toml for later runs to compare against
+        #[arg(long)]
+        save: bool,
+    },
+    /// Download puzzle inputs for days 1 through --day
     Download {
-        #[arg(short, long)]
-        cookie: String,
-
         #[arg(long, default_value_t = 25)]
         day: u8,
+
+        /// Download again even if the input is already cached
+        #[arg(long)]
+        force: bool,
     },
+    /// Fetch the puzzle description, save it as Markdown next to the input and print it.
+    /// Its example blocks are extracted to examples/{year}/day{N}-{k}.txt
+    Read {
+        #[arg(long)]
+        day: u8,
+
+        /// Overwrite examples that were already extracted
+        #[arg(long)]
+        force: bool,
+    },
+    /// Submit an answer and record the server's response
+    Submit {
+        #[arg(long)]
+        day: u8,
+
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Submit this answer instead of running the solution
+        #[arg(long)]
+        answer: Option<String>,
+    },
+    /// Create src/y{year}/day{N}.rs from the template and register it
+    New {
+        #[arg(long)]
+        day: u8,
+
+        /// Also download the input, puzzle description and examples
+        #[arg(long)]
+        download: bool,
+    },
+    /// Store the adventofcode.com session cookie for later requests
+    Login {
+        /// Session token, read from stdin when omitted to keep it out of shell history
+        token: Option<String>,
+    },
+    /// Check which user the stored session cookie belongs to
+    Whoami,
 }
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Cli {
-    #[arg(short, long, default_value_t = 2024)]
+    #[arg(short, long, global = true, default_value_t = 2024)]
     year: u16,
 
     #[command(subcommand)]
     command: Option<Commands>,
 
-    /// Turn debugging information on
-    #[arg(short, long, action = clap::ArgAction::Count)]
+    /// Write diagnostics to stderr, `-dd` for more detail
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
     debug: u8,
-}
-
-fn aoc_2021() {
-    let input =
-        read_lines_to_vec("./input/2021/day3.txt").expect("Unable to parse file into integers");
 
-    let result_1 = crate::y2021::day3::part_1(&input);
-    println!("Result from day3 part 1: {}", result_1);
+    /// Advent of Code server, e.g. a local stand-in for testing
+    #[arg(long, global = true, env = "AOC_BASE_URL")]
+    base_url: Option<String>,
 
-    let result_2 = crate::y2021::day3::part_2(&input);
-    println!("Result from day3 part 2: {}", result_2);
+    /// User-Agent sent with every request
+    #[arg(long, global = true, env = "AOC_USER_AGENT")]
+    user_agent: Option<String>,
 
-    let input =
-        read_lines_to_vec("./input/2021/day4.txt").expect("Unable to parse file into integers");
+    /// HTTP timeout in seconds
+    #[arg(long, global = true, env = "AOC_TIMEOUT")]
+    timeout: Option<u64>,
 
-    let result_1 = crate::y2021::day4::part_1(&input);
-    println!("Result from day4 part 1: {}", result_1);
-
-    let result_2 = crate::y2021::day4::part_2(&input);
-    println!("Result from day4 part 2: {}", result_2);
+    /// Read the session cookie from this file instead of $AOC_SESSION or `aoc login`
+    #[arg(long, global = true)]
+    cookie_file: Option<PathBuf>,
 }
 
-fn aoc_2023() {
-    let input =
-        read_lines_to_vec("./input/2023/day1.txt").expect("Unable to parse file into integers");
-
-    let result_1 = crate::y2023::day1::part_1(&input);
-    println!("Result from day1 part 1: {}", result_1);
-
-    let result_2 = crate::y2023::day1::part_2(&input);
-    println!("Result from day1 part 2: {}", result_2);
-
-    let input =
-        read_lines_to_vec("./input/2023/day2.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day2::part_1(&input);
-    println!("Result from day2 part 1: {}", result_1);
-
-    let result_2 = crate::y2023::day2::part_2(&input);
-    println!("Result from day2 part 2: {}", result_2);
-
-    let input =
-        read_lines_to_vec("./input/2023/day3.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day3::part_1(&input);
-    println!("Result from day3 part 1: {}", result_1);
-
-    let result_2 = crate::y2023::day3::part_2(&input);
-    println!("Result from day3 part 2: {}", result_2);
-
-    let input =
-        read_lines_to_vec("./input/2023/day4.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day4::part_1(&input);
-    println!("Result from day4 part 1: {}", result_1);
-
-    let result_2 = crate::y2023::day4::part_2(&input);
-    println!("Result from day4 part 2: {}", result_2);
-
-    let input =
-        read_lines_to_vec("./input/2023/day5.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day5::part_1(&input);
-    println!("Result from day5 part 1: {}", result_1);
-
-    // let result_2 = crate::y2023::day5::part_2(&input);
-    let result_2 = 0; // too slow
-    println!("Result from day5 part 2: {}", result_2);
-
-    let input =
-        read_lines_to_vec("./input/2023/day6.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day6::part_1(&input);
-    println!("Result from day6 part 1: {}", result_1);
-
-    let result_2 = crate::y2023::day6::part_2(&input);
-    println!("Result from day6 part 2: {}", result_2);
-
-    let input =
-        read_lines_to_vec("./input/2023/day7.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day7::part_1(&input);
-    println!("Result from day7 part 1: {}", result_1);
-
-    let result_2 = crate::y2023::day7::part_2(&input);
-    println!("Result from day7 part 2: {}", result_2);
-
-    let input =
-        read_lines_to_vec("./input/2023/day8.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day8::part_1(&input);
-    println!("Result from day8 part 1: {}", result_1);
-
-    let result_2 = crate::y2023::day8::part_2(&input);
-    println!("Result from day8 part 2: {}", result_2);
+impl Cli {
+    fn client(&self) -> Option<AocClient> {
+        let config = match Config::load() {
+            Ok(config) => config,
+            Err(error) => {
+                println!("Unable to load config: {error}");
+                return None;
+            }
+        };
+        let client_config = ClientConfig::resolve(
+            self.base_url.clone(),
+            self.user_agent.clone(),
+            self.timeout,
+            &config,
+        );
+
+        match AocClient::new(client_config) {
+            Ok(client) => Some(client),
+            Err(error) => {
+                println!("Unable to build HTTP client: {error}");
+                None
+            }
+        }
+    }
 
-    let input =
-        read_lines_to_vec("./input/2023/day9.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day9::part_1(&input);
-    println!("Result from day9 part 1: {}", result_1);
+    fn session(&self) -> Option<Session> {
+        match Session::resolve(self.cookie_file.as_deref()) {
+            Ok(Some(session)) => Some(session),
+            Ok(None) => {
+                println!("No session cookie found, set {SESSION_ENV}, pass --cookie-file or run `aoc login`");
+                None
+            }
+            Err(error) => {
+                println!("Unable to read session cookie: {error}");
+                None
+            }
+        }
+    }
+}
 
-    let result_2 = crate::y2023::day9::part_2(&input);
-    println!("Result from day9 part 2: {}", result_2);
+fn select_entries(registry: &Registry, year: u16, day: Option<u8>, all: bool) -> Vec<&Entry> {
+    let entries: Vec<&Entry> = match (all, day) {
+        (true, _) => registry.iter().collect(),
+        (false, Some(day)) => registry.get(year, day).into_iter().collect(),
+        (false, None) => registry.year(year).collect(),
+    };
+
+    if entries.is_empty() {
+        match day {
+            Some(day) => println!("No solution registered for year {year} day {day}"),
+            None => println!("No solutions registered for year {year}"),
+        }
+    }
+    entries
+}
 
-    let input =
-        read_file_to_string("./input/2023/day10.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day10::part_1(&input);
-    println!("Result from day10 part 1: {}", result_1);
+/// Returns false if a day failed, or if the one day asked for has no input.
+fn run(year: u16, day: Option<u8>, all: bool, jobs: usize) -> bool {
+    let registry = Registry::new();
+    let entries = select_entries(&registry, year, day, all);
+    if entries.is_empty() {
+        return false;
+    }
 
-    let result_2 = crate::y2023::day10::part_2(&input);
-    println!("Result from day10 part 2: {}", result_2);
+    let runs = runner::run_entries(Path::new(download::INPUT_DIR), entries, jobs);
+    print!("{}", runner::render_table(&runs));
 
-    let input =
-        read_file_to_string("./input/2023/day11.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day11::part_1(&input);
-    println!("Result from day11 part 1: {}", result_1);
+    let mut succeeded = true;
+    for run in &runs {
+        if let Err(error) = &run.result {
+            if day.is_some() || !error.is_missing_input() {
+                println!("{} day {}: {error}", run.year, run.day);
+                succeeded = false;
+            }
+        }
+    }
+    succeeded
+}
 
-    let result_2 = crate::y2023::day11::part_2(&input);
-    println!("Result from day11 part 2: {}", result_2);
+/// Runs the registered solution for one day, printing why if it can't.
+fn solve_day(year: u16, day: u8) -> Option<Answers> {
+    let registry = Registry::new();
+    let Some(entry) = registry.get(year, day) else {
+        println!("No solution registered for year {year} day {day}");
+        return None;
+    };
+
+    match runner::run_entry(Path::new(download::INPUT_DIR), entry).result {
+        Ok(outcome) => Some(outcome.answers),
+        Err(error) => {
+            println!("{year} day {day}: {error}");
+            None
+        }
+    }
+}
 
-    let input =
-        read_file_to_string("./input/2023/day12.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day12::part_1(&input);
-    println!("Result from day12 part 1: {}", result_1);
+/// Returns false if nothing could be recorded.
+fn record(year: u16, day: u8, part: Option<u8>, answer: Option<&str>) -> bool {
+    let dir = Path::new(answers::ANSWERS_DIR);
+    let mut book = match AnswerBook::load(dir, year) {
+        Ok(book) => book,
+        Err(error) => {
+            println!(
+                "Unable to load {}: {error}",
+                AnswerBook::path(dir, year).display()
+            );
+            return false;
+        }
+    };
 
-    let result_2 = crate::y2023::day12::part_2(&input);
-    println!("Result from day12 part 2: {}", result_2);
+    let answers: Vec<(u8, String)> = match (part, answer) {
+        (Some(part), Some(answer)) => vec![(part, answer.to_string())],
+        _ => {
+            let Some(answers) = solve_day(year, day) else {
+                return false;
+            };
+            vec![(1, answers.part_1), (2, answers.part_2)]
+                .into_iter()
+                .filter(|(p, _)| part.is_none() || part == Some(*p))
+                .collect()
+        }
+    };
 
-    let input =
-        read_file_to_string("./input/2023/day13.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day13::part_1(&input);
-    println!("Result from day13 part 1: {}", result_1);
+    for (part, answer) in answers {
+        println!("Recording {year} day {day} part {part}: {answer}");
+        book.set(day, part, &answer);
+    }
 
-    let result_2 = crate::y2023::day13::part_2(&input);
-    println!("Result from day13 part 2: {}", result_2);
+    if let Err(error) = book.save(dir, year) {
+        println!(
+            "Unable to save {}: {error}",
+            AnswerBook::path(dir, year).display()
+        );
+        return false;
+    }
+    true
+}
 
-    let input =
-        read_file_to_string("./input/2023/day14.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day14::part_1(&input);
-    println!("Result from day14 part 1: {}", result_1);
+/// Returns false if any recorded answer no longer matches.
+fn verify(year: u16, day: Option<u8>, all: bool, jobs: usize) -> bool {
+    let registry = Registry::new();
+    let entries = select_entries(&registry, year, day, all);
+    let dir = Path::new(answers::ANSWERS_DIR);
+    let mut books: HashMap<u16, AnswerBook> = HashMap::new();
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for run in runner::run_entries(Path::new(download::INPUT_DIR), entries, jobs) {
+        let book = match books.entry(run.year) {
+            hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            hash_map::Entry::Vacant(entry) => match AnswerBook::load(dir, run.year) {
+                Ok(book) => entry.insert(book),
+                Err(error) => {
+                    println!("Unable to load answers for {}: {error}", run.year);
+                    return false;
+                }
+            },
+        };
+
+        let outcome = match run.result {
+            Ok(outcome) => outcome,
+            Err(error) if error.is_missing_input() => {
+                println!("{} day {:>2}: NO INPUT", run.year, run.day);
+                continue;
+            }
+            Err(error) => {
+                failed += 1;
+                println!("{} day {:>2}: ERROR ({error})", run.year, run.day);
+                continue;
+            }
+        };
+
+        let parts = [(1, outcome.answers.part_1), (2, outcome.answers.part_2)];
+        for (part, actual) in parts {
+            let label = format!("{} day {:>2} part {part}", run.year, run.day);
+            match Verdict::check(book.get(run.day, part), &actual) {
+                Verdict::Pass => {
+                    passed += 1;
+                    println!("{label}: PASS");
+                }
+                Verdict::Fail { expected, actual } => {
+                    failed += 1;
+                    println!("{label}: FAIL (expected {expected}, got {actual})");
+                }
+                Verdict::Missing => {
+                    missing += 1;
+                    println!("{label}: MISSING (got {actual})");
+                }
+            }
+        }
+    }
 
-    let result_2 = crate::y2023::day14::part_2(&input);
-    println!("Result from day14 part 2: {}", result_2);
+    println!("{passed} passed, {failed} failed, {missing} missing");
+    failed == 0
+}
 
-    let input =
-        read_file_to_string("./input/2023/day15.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day15::part_1(&input);
-    println!("Result from day15 part 1: {}", result_1);
+/// Returns false if a day failed, or if the one day asked for has no input.
+fn bench(year: u16, day: Option<u8>, all: bool, options: BenchOptions, save: bool) -> bool {
+    let registry = Registry::new();
+    let entries = select_entries(&registry, year, day, all);
+    if entries.is_empty() {
+        return false;
+    }
 
-    let result_2 = crate::y2023::day15::part_2(&input);
-    println!("Result from day15 part 2: {}", result_2);
+    let dir = Path::new(bench::BENCH_DIR);
+    let mut baselines: BTreeMap<u16, Baseline> = BTreeMap::new();
+    let mut benches = vec![];
+    for entry in entries {
+        if let btree_map::Entry::Vacant(vacant) = baselines.entry(entry.year) {
+            match Baseline::load(dir, entry.year) {
+                Ok(baseline) => vacant.insert(baseline),
+                Err(error) => {
+                    println!(
+                        "Unable to load {}: {error}",
+                        Baseline::path(dir, entry.year).display()
+                    );
+                    return false;
+                }
+            };
+        }
+        benches.push(bench::bench_entry(
+            Path::new(download::INPUT_DIR),
+            entry,
+            options,
+        ));
+    }
+    print!("{}", bench::render_table(&benches, &baselines));
+
+    let mut succeeded = true;
+    for bench in &benches {
+        if let Err(error) = &bench.result {
+            if day.is_some() || !error.is_missing_input() {
+                println!("{} day {}: {error}", bench.year, bench.day);
+                succeeded = false;
+            }
+        }
+    }
 
-    let input =
-        read_file_to_string("./input/2023/day16.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day16::part_1(&input);
-    println!("Result from day16 part 1: {}", result_1);
+    if save {
+        for (year, baseline) in baselines.iter_mut() {
+            baseline.update(benches.iter().filter(|b| b.year == *year));
+            let path = Baseline::path(dir, *year);
+            match baseline.save(dir, *year) {
+                Ok(_) => println!("Saved baseline to {}", path.display()),
+                Err(error) => {
+                    println!("Unable to save {}: {error}", path.display());
+                    succeeded = false;
+                }
+            }
+        }
+    }
+    succeeded
+}
 
-    let result_2 = crate::y2023::day16::part_2(&input);
-    println!("Result from day16 part 2: {}", result_2);
+/// Fetches and saves the puzzle description, printing where it went.
+fn read_puzzle(client: &AocClient, session: &Session, year: u16, day: u8) -> Option<Puzzle> {
+    let puzzle = match Puzzle::fetch(client, session, year, day) {
+        Ok(puzzle) if puzzle.parts() > 0 => puzzle,
+        Ok(_) => {
+            println!("No puzzle description found for {year} day {day}, is it unlocked yet?");
+            return None;
+        }
+        Err(error) => {
+            println!("Unable to fetch the puzzle for {year} day {day}: {error}");
+            return None;
+        }
+    };
 
-    let input =
-        read_file_to_string("./input/2023/day17.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day17::part_1(&input);
-    println!("Result from day17 part 1: {}", result_1);
+    match puzzle.save(Path::new(download::INPUT_DIR), year, day) {
+        Ok(path) => println!("Saved {} part(s) to {}", puzzle.parts(), path.display()),
+        Err(error) => println!("Unable to save the puzzle description: {error}"),
+    }
 
-    let result_2 = crate::y2023::day17::part_2(&input);
-    println!("Result from day17 part 2: {}", result_2);
+    Some(puzzle)
+}
 
-    let input =
-        read_file_to_string("./input/2023/day18.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day18::part_1(&input);
-    println!("Result from day18 part 1: {}", result_1);
+/// Extracts the puzzle's examples into fixtures, keeping existing ones unless `force`.
+fn extract_examples(puzzle: &Puzzle, year: u16, day: u8, force: bool) {
+    let dir = Path::new(examples::EXAMPLES_DIR);
+    let found = puzzle.examples();
+    match examples::save_examples(&found, dir, year, day, force) {
+        Ok(paths) => {
+            if !paths.is_empty() {
+                println!(
+                    "Saved {} example(s) to {}",
+                    paths.len(),
+                    dir.join(year.to_string()).display()
+                );
+            }
+            if paths.len() < found.len() {
+                println!(
+                    "Kept {} existing example(s), pass --force to overwrite them",
+                    found.len() - paths.len()
+                );
+            }
+        }
+        Err(error) => println!("Unable to save the examples: {error}"),
+    }
+}
 
-    let result_2 = crate::y2023::day18::part_2(&input);
-    println!("Result from day18 part 2: {}", result_2);
+/// Returns true if the answer is, or already was, accepted.
+fn submit(
+    client: &AocClient,
+    session: &Session,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: Option<String>,
+) -> bool {
+    let answer = match answer {
+        Some(answer) => answer,
+        None => match solve_day(year, day) {
+            Some(answers) if part == 1 => answers.part_1,
+            Some(answers) => answers.part_2,
+            None => return false,
+        },
+    };
+
+    let dir = Path::new(submit::GUESSES_DIR);
+    let mut history = match GuessHistory::load(dir, year) {
+        Ok(history) => history,
+        Err(error) => {
+            println!(
+                "Unable to load {}: {error}",
+                GuessHistory::path(dir, year).display()
+            );
+            return false;
+        }
+    };
 
-    let input =
-        read_file_to_string("./input/2023/day19.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day19::part_1(&input);
-    println!("Result from day19 part 1: {}", result_1);
+    match history.check(day, part, &answer) {
+        Precheck::Fresh => {}
+        Precheck::Known(Feedback::Correct) => {
+            println!("{answer} was already accepted for {year} day {day} part {part}");
+            return true;
+        }
+        Precheck::Known(feedback) => {
+            println!("Not submitting {answer}, it was already rejected: {feedback}");
+            return false;
+        }
+        Precheck::AboveHigh(high) => {
+            println!("Warning: {answer} is not below {high}, which was too high")
+        }
+        Precheck::BelowLow(low) => {
+            println!("Warning: {answer} is not above {low}, which was too low")
+        }
+    }
 
-    let result_2 = crate::y2023::day19::part_2(&input);
-    println!("Result from day19 part 2: {}", result_2);
+    println!("Submitting {year} day {day} part {part}: {answer}");
+    let feedback = match submit::submit(client, session, year, day, part, &answer) {
+        Ok(feedback) => feedback,
+        Err(error) => {
+            println!("Unable to submit answer: {error}");
+            return false;
+        }
+    };
+    println!("{feedback}");
+
+    history.record(day, part, &answer, &feedback);
+    if let Err(error) = history.save(dir, year) {
+        println!(
+            "Unable to save {}: {error}",
+            GuessHistory::path(dir, year).display()
+        );
+    }
 
-    let input =
-        read_file_to_string("./input/2023/day20.txt").expect("Unable to parse file into integers");
-    let result_1 = crate::y2023::day20::part_1(&input);
-    println!("Result from day20 part 1: {}", result_1);
+    if feedback == Feedback::Correct {
+        record(year, day, Some(part), Some(&answer));
+        if part == 1 {
+            // Part 2 only appears in the description once part 1 is solved. Its examples
+            // are left for `aoc read`, which won't overwrite fixtures edited since.
+            read_puzzle(client, session, year, day);
+        }
+    }
 
-    let result_2 = crate::y2023::day20::part_2(&input);
-    println!("Result from day20 part 2: {}", result_2);
+    matches!(feedback, Feedback::Correct | Feedback::AlreadySolved)
 }
 
-fn aoc_2024(day: u8, _debug: u8) {
-    let filename = format!("./input/2024/day{day}.txt");
-    let input = read_file_to_string(&filename).expect("Unable to parse file into integers");
-
-    let result_1;
-    let result_2;
-    match day {
-        1 => {
-            result_1 = crate::y2024::day1::part_1(&input);
-            result_2 = crate::y2024::day1::part_2(&input);
-        }
-        2 => {
-            result_1 = crate::y2024::day2::part_1(&input);
-            result_2 = crate::y2024::day2::part_2(&input);
+fn new_day(year: u16, day: u8) -> bool {
+    match scaffold::scaffold(Path::new("."), year, day) {
+        Ok(changed) => {
+            for path in changed {
+                println!("Updated {}", path.display());
+            }
+            true
         }
-        3 => {
-            result_1 = crate::y2024::day3::part_1(&input);
-            result_2 = crate::y2024::day3::part_2(&input);
+        Err(error) => {
+            println!("Unable to create {year} day {day}: {error}");
+            false
         }
-        4 => {
-            result_1 = crate::y2024::day4::part_1(&input);
-            result_2 = crate::y2024::day4::part_2(&input);
+    }
+}
+
+fn login(token: Option<&str>) -> Option<Session> {
+    let token = match token {
+        Some(token) => token.to_string(),
+        None => {
+            println!("Paste the value of the adventofcode.com session cookie:");
+            let mut line = String::new();
+            if let Err(error) = io::stdin().lock().read_line(&mut line) {
+                println!("Unable to read session cookie: {error}");
+                return None;
+            }
+            line
         }
-        5 => {
-            result_1 = crate::y2024::day5::part_1(&input);
-            result_2 = crate::y2024::day5::part_2(&input);
+    };
+    let Some(session) = Session::new(&token) else {
+        println!("Session cookie is empty");
+        return None;
+    };
+    let Some(path) = Session::path() else {
+        println!("Unable to find a config directory, set $XDG_CONFIG_HOME or $HOME");
+        return None;
+    };
+
+    match session.save(&path) {
+        Ok(_) => {
+            println!("Saved session cookie to {}", path.display());
+            Some(session)
         }
-        _ => {
-            panic!("Day does not exist");
+        Err(error) => {
+            println!(
+                "Unable to save session cookie to {}: {error}",
+                path.display()
+            );
+            None
         }
     }
-    println!("Result from day {day} part 1: {}", result_1);
-    println!("Result from day {day} part 2: {}", result_2);
 }
 
-fn download(year: u16, day: u8, cookie: &str) {
-    let client = Client::builder().build().unwrap();
-
-    for day in 1..day + 1 {
-        let url = format!("https://adventofcode.com/{year}/day/{day}/input");
-
-        match client
-            .get(url)
-            .header(reqwest::header::COOKIE, cookie)
-            .send()
-        {
-            Ok(response) => {
-                let body = response.bytes().unwrap();
-                let path_string = format!("./input/{year}/day{day}.txt");
-                let out_path = Path::new(&path_string);
-                let result = write(out_path, &body);
-                match result {
-                    Ok(_) => println!("Saved day {day} to {path_string}"),
-                    Err(_) => println!("Error saving day {day} to {path_string}"),
-                }
-            }
-            Err(error) => {
-                println!("Error downloading year {year} day {day}");
-                println!("{error}");
-            }
+fn whoami(client: &AocClient, session: &Session) -> bool {
+    match session::whoami(client, session) {
+        Ok(Some(user)) => {
+            println!("Logged in as {user}");
+            true
+        }
+        Ok(None) => {
+            println!("The session cookie was not accepted, it may have expired");
+            false
+        }
+        Err(error) => {
+            println!("Unable to check the session cookie: {error}");
+            false
         }
     }
 }
@@ -290,26 +634,122 @@ fn main() {
 
     println!("AoC year {}!", cli.year);
 
-    match cli.debug {
-        0 => println!("Debug mode is off"),
-        1 => println!("Debug mode is kind of on"),
-        2 => println!("Debug mode is on"),
-        _ => println!("Don't be crazy"),
-    }
+    log::set_level(log::Level::from_count(cli.debug));
 
     match &cli.command {
-        Some(Commands::Run { day }) => {
+        Some(Commands::Run { day, all, jobs }) => {
             println!("Running AoC year {}!", cli.year);
-            match cli.year {
-                2021 => aoc_2021(),
-                2023 => aoc_2023(),
-                _ => aoc_2024(*day, cli.debug),
+            let succeeded = run(cli.year, *day, *all, *jobs);
+            if !succeeded {
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Record { day, part, answer }) => {
+            let recorded = record(cli.year, *day, *part, answer.as_deref());
+            if !recorded {
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Verify { day, all, jobs }) => {
+            let passed = verify(cli.year, *day, *all, *jobs);
+            if !passed {
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Bench {
+            day,
+            all,
+            warmup,
+            samples,
+            save,
+        }) => {
+            let options = BenchOptions {
+                warmup: *warmup,
+                samples: *samples,
+            };
+            let succeeded = bench(cli.year, *day, *all, options, *save);
+            if !succeeded {
+                std::process::exit(1);
             }
         }
-        Some(Commands::Download { cookie, day }) => {
+        Some(Commands::Download { day, force }) => {
             println!("Downloading AoC year {}!", cli.year);
 
-            download(cli.year, *day, cookie);
+            let (Some(client), Some(session)) = (cli.client(), cli.session()) else {
+                std::process::exit(1);
+            };
+            let downloaded = download::download(
+                &client,
+                Path::new(download::INPUT_DIR),
+                cli.year,
+                *day,
+                &session,
+                *force,
+            );
+            if !downloaded {
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Read { day, force }) => {
+            let (Some(client), Some(session)) = (cli.client(), cli.session()) else {
+                std::process::exit(1);
+            };
+            let Some(puzzle) = read_puzzle(&client, &session, cli.year, *day) else {
+                std::process::exit(1);
+            };
+            extract_examples(&puzzle, cli.year, *day, *force);
+            print!("{}", puzzle.to_terminal());
+        }
+        Some(Commands::Submit { day, part, answer }) => {
+            let (Some(client), Some(session)) = (cli.client(), cli.session()) else {
+                std::process::exit(1);
+            };
+            let accepted = submit(&client, &session, cli.year, *day, *part, answer.clone());
+            if !accepted {
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::New { day, download }) => {
+            if !new_day(cli.year, *day) {
+                std::process::exit(1);
+            }
+            if *download {
+                let (Some(client), Some(session)) = (cli.client(), cli.session()) else {
+                    std::process::exit(1);
+                };
+                let dir = Path::new(download::INPUT_DIR);
+                let outcome = download::download_day(&client, dir, cli.year, *day, &session, false);
+                println!(
+                    "Input {outcome} ({})",
+                    download::input_path(dir, cli.year, *day).display()
+                );
+                let puzzle = read_puzzle(&client, &session, cli.year, *day);
+                if let Some(puzzle) = &puzzle {
+                    extract_examples(puzzle, cli.year, *day, false);
+                }
+                if outcome.is_failure() || puzzle.is_none() {
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Login { token }) => {
+            let Some(session) = login(token.as_deref()) else {
+                std::process::exit(1);
+            };
+            let Some(client) = cli.client() else {
+                std::process::exit(1);
+            };
+            if !whoami(&client, &session) {
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Whoami) => {
+            let (Some(client), Some(session)) = (cli.client(), cli.session()) else {
+                std::process::exit(1);
+            };
+            if !whoami(&client, &session) {
+                std::process::exit(1);
+            }
         }
         None => {}
     }
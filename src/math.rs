@@ -0,0 +1,146 @@
+use std::ops::{Div, Mul, Rem, Sub};
+
+/// The greatest common divisor, always non-negative. `gcd(0, 0)` is 0.
+pub fn gcd<T>(a: T, b: T) -> T
+where
+    T: Copy + PartialOrd + Default + Sub<Output = T> + Rem<Output = T>,
+{
+    let zero = T::default();
+    let (mut a, mut b) = (a, b);
+    while b != zero {
+        (a, b) = (b, a % b);
+    }
+    if a < zero {
+        zero - a
+    } else {
+        a
+    }
+}
+
+/// The lowest common multiple, always non-negative. 0 if either argument is.
+pub fn lcm<T>(a: T, b: T) -> T
+where
+    T: Copy + PartialOrd + Default,
+    T: Sub<Output = T> + Rem<Output = T> + Div<Output = T> + Mul<Output = T>,
+{
+    let zero = T::default();
+    if a == zero || b == zero {
+        return zero;
+    }
+    let multiple = a / gcd(a, b) * b;
+    if multiple < zero {
+        zero - multiple
+    } else {
+        multiple
+    }
+}
+
+/// `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = extended_gcd_wide(a as i128, b as i128);
+    (g as i64, x as i64, y as i64)
+}
+
+fn extended_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` in `0..modulus` with `a * x ≡ 1 (mod modulus)`, `None` unless `a` and
+/// `modulus` are coprime.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// `base.pow(exponent) % modulus` without overflowing.
+pub fn mod_pow(base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result as u64
+}
+
+/// Solves `x ≡ residue (mod modulus)` for every `(residue, modulus)` pair at once, the
+/// moduli need not be coprime. Returns the smallest non-negative `x` and the lcm of
+/// the moduli, or `None` if the congruences contradict each other, a modulus is zero
+/// or the lcm doesn't fit in an `i64`.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let (mut residue, mut modulus): (i128, i128) = (0, 1);
+    for &(r, m) in congruences {
+        if m == 0 {
+            return None;
+        }
+        let (r, m) = ((r as i128).rem_euclid(m as i128), m as i128);
+        let (g, p, _) = extended_gcd_wide(modulus, m);
+        if (r - residue) % g != 0 {
+            return None;
+        }
+        let step = m / g;
+        let k = ((r - residue) / g * p).rem_euclid(step);
+        residue += modulus * k;
+        modulus = i64::try_from(modulus * step).ok()?.into();
+        residue = residue.rem_euclid(modulus);
+    }
+
+    Some((i64::try_from(residue).ok()?, i64::try_from(modulus).ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_lcm_test() {
+        assert_eq!(gcd(12_u64, 18), 6);
+        assert_eq!(gcd(-12_i32, 18), 6);
+        assert_eq!(gcd(0_u8, 7), 7);
+        assert_eq!(lcm(4_u64, 6), 12);
+        assert_eq!(lcm(-4_i64, 6), 12);
+        assert_eq!(lcm(0_usize, 6), 0);
+        assert_eq!([2_u64, 3, 4, 5].into_iter().fold(1, lcm), 60);
+    }
+
+    #[test]
+    fn modular_test() {
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+    }
+
+    #[test]
+    fn crt_test() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(0, 4), (2, 6)]), Some((8, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(1, 3), (2, 0)]), None);
+        assert_eq!(crt(&[(1, i64::MAX), (0, 2)]), None);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::geometry::{Direction, Point};
+use crate::math::gcd;
+
+/// A closed polygon on the integer lattice, the last vertex joining back to the first.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Point>) -> Polygon {
+        Polygon { vertices }
+    }
+
+    /// The polygon traced by walking each `(direction, distance)` move in turn from
+    /// `start`, which the walk is expected to end back at.
+    pub fn from_moves(start: Point, moves: impl IntoIterator<Item = (Direction, i64)>) -> Polygon {
+        let mut vertices = vec![];
+        let mut position = start;
+        for (direction, distance) in moves {
+            vertices.push(position);
+            position += direction.delta() * distance;
+        }
+
+        Polygon { vertices }
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(a, b)| (*a, *b))
+    }
+
+    /// Twice the enclosed area by the shoelace formula, which keeps it an integer.
+    pub fn double_area(&self) -> i64 {
+        self.edges()
+            .map(|(a, b)| a.x * b.y - b.x * a.y)
+            .sum::<i64>()
+            .abs()
+    }
+
+    /// The number of lattice points on the edges, the perimeter for axis-aligned edges.
+    pub fn boundary_points(&self) -> i64 {
+        self.edges()
+            .map(|(a, b)| {
+                let delta = b - a;
+                gcd(delta.x, delta.y)
+            })
+            .sum()
+    }
+
+    /// The number of lattice points strictly inside, by Pick's theorem.
+    pub fn interior_points(&self) -> i64 {
+        (self.double_area() - self.boundary_points()) / 2 + 1
+    }
+
+    /// Every lattice point inside or on the polygon, the number of tiles a loop of
+    /// tiles through the vertices covers.
+    pub fn enclosed_points(&self) -> i64 {
+        self.interior_points() + self.boundary_points()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polygon_test() {
+        let square = Polygon::from_moves(
+            Point::ORIGIN,
+            [
+                (Direction::Right, 4),
+                (Direction::Down, 4),
+                (Direction::Left, 4),
+                (Direction::Up, 4),
+            ],
+        );
+
+        assert_eq!(square.vertices().len(), 4);
+        assert_eq!(square.double_area(), 32);
+        assert_eq!(square.boundary_points(), 16);
+        assert_eq!(square.interior_points(), 9);
+        assert_eq!(square.enclosed_points(), 25);
+    }
+
+    #[test]
+    fn diagonal_polygon_test() {
+        let triangle = Polygon::new(vec![Point::new(0, 0), Point::new(4, 0), Point::new(0, 4)]);
+
+        assert_eq!(triangle.double_area(), 16);
+        assert_eq!(triangle.boundary_points(), 12);
+        assert_eq!(triangle.interior_points(), 3);
+    }
+}
@@ -0,0 +1,338 @@
+use crate::client::AocClient;
+use crate::session::Session;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+static ARTICLE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?s)<article class="day-desc">(.*?)</article>"#).unwrap());
+static TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"<(/?)([a-zA-Z0-9]+)([^>]*)>"#).unwrap());
+static PRE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap());
+static ANSWER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<code><em>([^<]*)</em></code>").unwrap());
+static HREF_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"href="([^"]*)""#).unwrap());
+
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Style {
+    Markdown,
+    Terminal,
+}
+
+/// A `<pre><code>` block from the description, with the answers stated after it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Example {
+    pub input: String,
+    pub answers: [Option<String>; 2],
+}
+
+/// The description of one day, one `<article class="day-desc">` per unlocked part.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Puzzle {
+    parts: Vec<String>,
+}
+
+pub fn puzzle_path(dir: &Path, year: u16, day: u8) -> PathBuf {
+    dir.join(year.to_string()).join(format!("day{day}.md"))
+}
+
+impl Puzzle {
+    pub fn parse(page: &str) -> Puzzle {
+        Puzzle {
+            parts: ARTICLE_REGEX
+                .captures_iter(page)
+                .map(|captures| captures[1].to_string())
+                .collect(),
+        }
+    }
+
+    pub fn fetch(
+        client: &AocClient,
+        session: &Session,
+        year: u16,
+        day: u8,
+    ) -> reqwest::Result<Puzzle> {
+        let page = client
+            .page(&format!("/{year}/day/{day}"), session)?
+            .error_for_status()?
+            .text()?;
+
+        Ok(Puzzle::parse(&page))
+    }
+
+    /// Number of parts whose description is unlocked, 2 once part 1 is solved.
+    pub fn parts(&self) -> usize {
+        self.parts.len()
+    }
+
+    pub fn to_markdown(&self) -> String {
+        self.render(Style::Markdown)
+    }
+
+    pub fn to_terminal(&self) -> String {
+        self.render(Style::Terminal)
+    }
+
+    /// Every `<pre><code>` block in order. The last emphasized code after a block, and
+    /// before the next one, is taken as that block's answer for the part it appears in.
+    /// A part without blocks of its own, which is how part 2 usually reuses the part 1
+    /// example, gives its answer to the last block seen.
+    pub fn examples(&self) -> Vec<Example> {
+        let mut examples: Vec<Example> = vec![];
+
+        for (part, html) in self.parts.iter().enumerate().take(2) {
+            let answers: Vec<(usize, String)> = ANSWER_REGEX
+                .captures_iter(html)
+                .map(|c| (c.get(0).unwrap().start(), decode_entities(&c[1])))
+                .collect();
+            let blocks: Vec<(usize, usize, String)> = PRE_REGEX
+                .captures_iter(html)
+                .map(|c| {
+                    let block = c.get(0).unwrap();
+                    let input = decode_entities(&TAG_REGEX.replace_all(&c[1], ""));
+                    (block.start(), block.end(), input)
+                })
+                .collect();
+
+            if blocks.is_empty() {
+                if let (Some(example), Some((_, answer))) = (examples.last_mut(), answers.last()) {
+                    example.answers[part] = Some(answer.clone());
+                }
+                continue;
+            }
+
+            for (index, (_, end, input)) in blocks.iter().enumerate() {
+                let next = blocks.get(index + 1).map_or(html.len(), |b| b.0);
+                let mut example = Example {
+                    input: input.clone(),
+                    answers: [None, None],
+                };
+                example.answers[part] = answers
+                    .iter()
+                    .rev()
+                    .find(|(start, _)| start > end && *start < next)
+                    .map(|(_, answer)| answer.clone());
+                examples.push(example);
+            }
+        }
+
+        examples
+    }
+
+    /// Writes the Markdown description to `dir/{year}/day{day}.md`.
+    pub fn save(&self, dir: &Path, year: u16, day: u8) -> io::Result<PathBuf> {
+        let path = puzzle_path(dir, year, day);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, self.to_markdown())?;
+
+        Ok(path)
+    }
+
+    fn render(&self, style: Style) -> String {
+        let parts: Vec<String> = self.parts.iter().map(|part| render(part, style)).collect();
+
+        parts.join("\n")
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Renders the small subset of HTML used in puzzle descriptions.
+fn render(html: &str, style: Style) -> String {
+    let mut out = String::new();
+    let mut pre: Option<String> = None;
+    let mut links: Vec<String> = vec![];
+    let mut code_depth = 0;
+    let mut last = 0;
+
+    let push_text = |out: &mut String, pre: &mut Option<String>, text: &str| match pre {
+        Some(block) => block.push_str(&decode_entities(text)),
+        None if text.trim().is_empty() && text.contains('\n') => {}
+        None => out.push_str(&decode_entities(&text.replace('\n', " "))),
+    };
+
+    for captures in TAG_REGEX.captures_iter(html) {
+        let tag = captures.get(0).unwrap();
+        push_text(&mut out, &mut pre, &html[last..tag.start()]);
+        last = tag.end();
+
+        let closing = &captures[1] == "/";
+        let name = captures[2].to_ascii_lowercase();
+        match (name.as_str(), closing, style) {
+            ("h2", false, Style::Markdown) => out.push_str("## "),
+            ("h2", false, Style::Terminal) => out.push_str(BOLD),
+            ("h2", true, Style::Markdown) => out.push_str("\n\n"),
+            ("h2", true, Style::Terminal) => out.push_str(&format!("{RESET}\n\n")),
+            ("p", true, _) => out.push_str("\n\n"),
+            ("ul", true, _) => out.push('\n'),
+            ("li", false, _) => out.push_str("- "),
+            ("li", true, _) => out.push('\n'),
+            ("pre", false, _) => pre = Some(String::new()),
+            ("pre", true, _) => {
+                let block = pre.take().unwrap_or_default();
+                let block = block.trim_end_matches('\n');
+                match style {
+                    Style::Markdown => out.push_str(&format!("```\n{block}\n```\n\n")),
+                    Style::Terminal => {
+                        for line in block.lines() {
+                            out.push_str(&format!("    {line}\n"));
+                        }
+                        out.push('\n');
+                    }
+                }
+            }
+            ("code", _, _) if pre.is_some() => {}
+            ("code", false, _) => {
+                code_depth += 1;
+                if style == Style::Markdown {
+                    out.push('`');
+                }
+            }
+            ("code", true, _) => {
+                code_depth -= 1;
+                if style == Style::Markdown {
+                    out.push('`');
+                }
+            }
+            ("em", _, _) if pre.is_some() => {}
+            ("em", _, Style::Markdown) if code_depth == 0 => out.push('*'),
+            ("em", false, Style::Terminal) => out.push_str(BOLD),
+            ("em", true, Style::Terminal) => out.push_str(RESET),
+            ("a", false, _) => {
+                let href = HREF_REGEX
+                    .captures(&captures[3])
+                    .map(|href| decode_entities(&href[1]))
+                    .unwrap_or_default();
+                links.push(href);
+                if style == Style::Markdown {
+                    out.push('[');
+                }
+            }
+            ("a", true, Style::Markdown) => {
+                out.push_str(&format!("]({})", links.pop().unwrap_or_default()));
+            }
+            ("a", true, Style::Terminal) => {
+                links.pop();
+            }
+            _ => {}
+        }
+    }
+    push_text(&mut out, &mut pre, &html[last..]);
+
+    format!("{}\n", out.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::ClientConfig;
+    use crate::test_server::TestServer;
+
+    const PAGE: &str = "<main>
+<article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is <em>wrong</em> with <a href=\"/2023/about\">global snow</a> production.</p>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+<em>a1b2c3d4e5f</em>
+</code></pre>
+<ul><li>The value of <code>1abc2</code> is <code><em>12</em></code> &amp; more.</li></ul>
+</article>
+<p>Your puzzle answer was <code>54951</code>.</p>
+<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>It looks like some digits are &quot;spelled out&quot;.</p></article>
+</main>";
+
+    #[test]
+    fn to_markdown_test() {
+        let puzzle = Puzzle::parse(PAGE);
+
+        assert_eq!(puzzle.parts(), 2);
+        assert_eq!(
+            puzzle.to_markdown(),
+            "## --- Day 1: Trebuchet?! ---
+
+Something is *wrong* with [global snow](/2023/about) production.
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+```
+
+- The value of `1abc2` is `12` & more.
+
+## --- Part Two ---
+
+It looks like some digits are \"spelled out\".
+"
+        );
+    }
+
+    #[test]
+    fn to_terminal_test() {
+        let puzzle = Puzzle::parse(PAGE);
+        let terminal = puzzle.to_terminal();
+
+        assert!(terminal.starts_with("\x1b[1m--- Day 1: Trebuchet?! ---\x1b[0m\n\n"));
+        assert!(terminal.contains("Something is \x1b[1mwrong\x1b[0m with global snow production."));
+        assert!(terminal.contains("\n    1abc2\n    pqr3stu8vwx\n    a1b2c3d4e5f\n\n"));
+        assert!(terminal.contains("- The value of 1abc2 is \x1b[1m12\x1b[0m & more."));
+    }
+
+    #[test]
+    fn examples_test() {
+        let examples = Puzzle::parse(PAGE).examples();
+
+        assert_eq!(
+            examples,
+            vec![Example {
+                input: String::from("1abc2\npqr3stu8vwx\na1b2c3d4e5f\n"),
+                answers: [Some(String::from("12")), None],
+            }]
+        );
+
+        let page = "<article class=\"day-desc\"><pre><code>a &lt; b\n</code></pre><p>gives <code><em>1</em></code></p>
+<pre><code>c\n</code></pre><p>and <code><em>2</em></code> or <code><em>3</em></code></p></article>
+<article class=\"day-desc\"><p>Now it is <code><em>4</em></code>.</p></article>";
+        let examples = Puzzle::parse(page).examples();
+
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].input, "a < b\n");
+        assert_eq!(examples[0].answers, [Some(String::from("1")), None]);
+        assert_eq!(
+            examples[1].answers,
+            [Some(String::from("3")), Some(String::from("4"))]
+        );
+    }
+
+    #[test]
+    fn fetch_test() {
+        let server = TestServer::new(vec![(200, PAGE)]);
+        let client = AocClient::new(ClientConfig {
+            base_url: server.url.clone(),
+            ..ClientConfig::default()
+        })
+        .unwrap();
+        let session = Session::new("abc").unwrap();
+
+        let puzzle = Puzzle::fetch(&client, &session, 2023, 1).unwrap();
+        assert!(server.request().starts_with("GET /2023/day/1 HTTP/1.1\r\n"));
+        assert_eq!(puzzle, Puzzle::parse(PAGE));
+    }
+}
@@ -0,0 +1,296 @@
+use crate::error::{Error, ParseError};
+use crate::solution::{Entry, Outcome};
+use crate::utils::read_file_to_string;
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::mpsc::channel;
+use std::time::Duration;
+use workerpool::thunk::{Thunk, ThunkWorker};
+use workerpool::Pool;
+
+#[derive(Debug)]
+pub struct DayRun {
+    pub year: u16,
+    pub day: u8,
+    pub result: Result<Outcome, Error>,
+}
+
+/// Solves one day with its input from `dir`.
+pub fn run_entry(dir: &Path, entry: &Entry) -> DayRun {
+    solve_entry(dir, entry, entry.solve)
+}
+
+/// A panicking solution fails its day rather than taking down the run or, on a worker
+/// thread, leaving the run waiting for a day that never finishes.
+fn solve_entry(
+    dir: &Path,
+    entry: &Entry,
+    solve: fn(&str) -> Result<Outcome, ParseError>,
+) -> DayRun {
+    let path = entry.input_path(dir);
+    let result = match read_file_to_string(&path) {
+        Ok(input) => match panic::catch_unwind(AssertUnwindSafe(|| solve(&input))) {
+            Ok(outcome) => outcome.map_err(Error::from),
+            Err(payload) => Err(Error::Panic(panic_message(payload))),
+        },
+        Err(source) => Err(Error::Io { path, source }),
+    };
+
+    DayRun {
+        year: entry.year,
+        day: entry.day,
+        result,
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload
+            .downcast_ref::<&str>()
+            .map_or_else(|| String::from("no message"), |message| message.to_string()),
+    }
+}
+
+/// Runs every entry, returning the runs in the order given. With more than one job the
+/// days are spread over that many worker threads and each day's parts run side by
+/// side, so the timings include some contention.
+pub fn run_entries<'a>(
+    dir: &Path,
+    entries: impl IntoIterator<Item = &'a Entry>,
+    jobs: usize,
+) -> Vec<DayRun> {
+    if jobs <= 1 {
+        return entries
+            .into_iter()
+            .map(|entry| run_entry(dir, entry))
+            .collect();
+    }
+
+    let pool = Pool::<ThunkWorker<(usize, DayRun)>>::new(jobs);
+    let (tx, rx) = channel();
+    let mut n_entries = 0;
+    for (index, entry) in entries.into_iter().enumerate() {
+        let entry = *entry;
+        let dir = dir.to_path_buf();
+        pool.execute_to(
+            tx.clone(),
+            Thunk::of(move || (index, solve_entry(&dir, &entry, entry.solve_concurrently))),
+        );
+        n_entries += 1;
+    }
+    // Only the workers' senders are left, so the results end even if a worker dies.
+    drop(tx);
+
+    let mut runs: Vec<(usize, DayRun)> = rx.iter().take(n_entries).collect();
+    runs.sort_by_key(|(index, _)| *index);
+    runs.into_iter().map(|(_, run)| run).collect()
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_secs_f64() * 1_000_000.0;
+    if micros < 1_000.0 {
+        format!("{:.1}µs", micros)
+    } else if micros < 1_000_000.0 {
+        format!("{:.2}ms", micros / 1_000.0)
+    } else {
+        format!("{:.2}s", micros / 1_000_000.0)
+    }
+}
+
+const HEADERS: [&str; 8] = [
+    "Year", "Day", "Answer 1", "Answer 2", "Parse", "Part 1", "Part 2", "Total",
+];
+const RIGHT_ALIGNED: [bool; 8] = [true, true, false, false, true, true, true, true];
+
+/// Renders one row per day plus a total row, with every column padded to its widest cell.
+pub fn render_table(runs: &[DayRun]) -> String {
+    let mut rows: Vec<[String; 8]> = vec![];
+    let mut total = Duration::ZERO;
+
+    for run in runs {
+        let row = match &run.result {
+            Ok(outcome) => {
+                total += outcome.timings.total();
+                [
+                    run.year.to_string(),
+                    run.day.to_string(),
+                    outcome.answers.part_1.clone(),
+                    outcome.answers.part_2.clone(),
+                    format_duration(outcome.timings.parse),
+                    format_duration(outcome.timings.part_1),
+                    format_duration(outcome.timings.part_2),
+                    format_duration(outcome.timings.total()),
+                ]
+            }
+            Err(error) => [
+                run.year.to_string(),
+                run.day.to_string(),
+                String::from(if error.is_missing_input() {
+                    "no input"
+                } else {
+                    "error"
+                }),
+                String::from("-"),
+                String::from("-"),
+                String::from("-"),
+                String::from("-"),
+                String::from("-"),
+            ],
+        };
+        rows.push(row);
+    }
+
+    let mut widths: Vec<usize> = HEADERS.iter().map(|h| h.chars().count()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let header: Vec<String> = HEADERS.iter().map(|h| h.to_string()).collect();
+    let mut out = String::new();
+    out.push_str(&render_row(&header, &widths, &RIGHT_ALIGNED));
+    out.push_str(&render_rule(&widths));
+    for row in &rows {
+        out.push_str(&render_row(row, &widths, &RIGHT_ALIGNED));
+    }
+    out.push_str(&render_rule(&widths));
+
+    let total = format_duration(total);
+    let label_width = widths.iter().sum::<usize>() + 2 * (widths.len() - 1) - total.chars().count();
+    out.push_str(&format!("{:<label_width$}{}\n", "Total", total));
+
+    out
+}
+
+/// One table line, two spaces between columns, with `right_aligned[i]` for column `i`.
+pub fn render_row(cells: &[String], widths: &[usize], right_aligned: &[bool]) -> String {
+    let line: Vec<String> = cells
+        .iter()
+        .zip(widths)
+        .zip(right_aligned)
+        .map(|((cell, width), right)| {
+            if *right {
+                format!("{:>width$}", cell)
+            } else {
+                format!("{:<width$}", cell)
+            }
+        })
+        .collect();
+
+    format!("{}\n", line.join("  ").trim_end())
+}
+
+pub fn render_rule(widths: &[usize]) -> String {
+    let len = widths.iter().sum::<usize>() + 2 * (widths.len() - 1);
+    format!("{}\n", "-".repeat(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{Answers, Timings};
+    use std::{env, fs, io};
+
+    #[test]
+    fn format_duration_test() {
+        assert_eq!(format_duration(Duration::from_nanos(1_500)), "1.5µs");
+        assert_eq!(format_duration(Duration::from_micros(2_345)), "2.35ms");
+        assert_eq!(format_duration(Duration::from_millis(3_210)), "3.21s");
+    }
+
+    #[test]
+    fn run_entries_order_test() {
+        // No inputs exist for this year, so every day fails fast but must still come
+        // back in order.
+        let entries: Vec<Entry> = (1..=12)
+            .rev()
+            .map(|day| Entry::new::<crate::y2024::day1::Solver>(1900, day))
+            .collect();
+
+        let runs = run_entries(Path::new("./input"), &entries, 4);
+        let days: Vec<u8> = runs.iter().map(|run| run.day).collect();
+        assert_eq!(days, (1..=12).rev().collect::<Vec<u8>>());
+        assert!(runs.iter().all(|run| run.result.is_err()));
+    }
+
+    #[test]
+    fn run_entries_panic_test() {
+        fn panics(_: &str) -> Result<Outcome, ParseError> {
+            panic!("day 2 broke")
+        }
+
+        let dir = env::temp_dir().join(format!("aoc-runner-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("2024")).unwrap();
+        let mut entries: Vec<Entry> = (1..=3)
+            .map(|day| Entry::new::<crate::y2024::day1::Solver>(2024, day))
+            .collect();
+        entries[1].solve = panics;
+        entries[1].solve_concurrently = panics;
+        for entry in &entries {
+            fs::write(entry.input_path(&dir), "3   4\n4   3\n").unwrap();
+        }
+
+        for jobs in [1, 2] {
+            let runs = run_entries(&dir, &entries, jobs);
+            let days: Vec<u8> = runs.iter().map(|run| run.day).collect();
+            assert_eq!(days, vec![1, 2, 3]);
+            assert!(runs[0].result.is_ok());
+            assert!(runs[2].result.is_ok());
+            assert!(
+                matches!(&runs[1].result, Err(Error::Panic(message)) if message == "day 2 broke")
+            );
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn render_table_test() {
+        let runs = vec![
+            DayRun {
+                year: 2023,
+                day: 1,
+                result: Ok(Outcome {
+                    answers: Answers {
+                        part_1: String::from("142"),
+                        part_2: String::from("281"),
+                    },
+                    timings: Timings {
+                        parse: Duration::from_micros(10),
+                        part_1: Duration::from_micros(20),
+                        part_2: Duration::from_micros(30),
+                    },
+                }),
+            },
+            DayRun {
+                year: 2023,
+                day: 12,
+                result: Err(Error::Io {
+                    path: String::from("./input/2023/day12.txt"),
+                    source: io::Error::from(io::ErrorKind::NotFound),
+                }),
+            },
+            DayRun {
+                year: 2023,
+                day: 13,
+                result: Err(Error::from(ParseError::new("a row", ""))),
+            },
+        ];
+
+        assert_eq!(
+            render_table(&runs),
+            "Year  Day  Answer 1  Answer 2   Parse  Part 1  Part 2   Total
+-------------------------------------------------------------
+2023    1  142       281       10.0µs  20.0µs  30.0µs  60.0µs
+2023   12  no input  -              -       -       -       -
+2023   13  error     -              -       -       -       -
+-------------------------------------------------------------
+Total                                                  60.0µs
+"
+        );
+    }
+}
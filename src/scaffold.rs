@@ -0,0 +1,383 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+static DAY_MOD_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^pub mod day(\d+);\n").unwrap());
+static ENTRIES_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)vec!\[[^\]]*\]").unwrap());
+static ENTRY_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Entry::new::<day(\d+)::Solver>").unwrap());
+static YEAR_MOD_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^pub mod y\d+;\n").unwrap());
+static EXTEND_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^( *)registry\.extend\(crate::y\d+::solutions\(\)\);\n").unwrap()
+});
+
+pub fn render_template(year: u16, day: u8) -> String {
+    TEMPLATE
+        .replace("{{year}}", &year.to_string())
+        .replace("{{day}}", &day.to_string())
+}
+
+fn render_entries(year: u16, days: &[u8]) -> String {
+    let entries: Vec<String> = days
+        .iter()
+        .map(|day| format!("Entry::new::<day{day}::Solver>({year}, {day})"))
+        .collect();
+
+    match entries.as_slice() {
+        [entry] => format!("vec![{entry}]"),
+        _ => format!(
+            "vec![\n{}    ]",
+            entries
+                .iter()
+                .map(|entry| format!("        {entry},\n"))
+                .collect::<String>()
+        ),
+    }
+}
+
+/// A new `y{year}/mod.rs` holding just `day`.
+pub fn new_year_module(year: u16, day: u8) -> String {
+    format!(
+        "use crate::solution::Entry;\n\npub mod day{day};\n\npub fn solutions() -> Vec<Entry> {{\n    {}\n}}\n",
+        render_entries(year, &[day])
+    )
+}
+
+/// Adds `day` to the `pub mod` list and `solutions()` of a year's `mod.rs`, keeping
+/// both in the order rustfmt and the registry use.
+pub fn register_day(mod_rs: &str, year: u16, day: u8) -> String {
+    let mut modules: Vec<u8> = DAY_MOD_REGEX
+        .captures_iter(mod_rs)
+        .map(|c| c[1].parse().unwrap())
+        .collect();
+    let mut days: Vec<u8> = ENTRY_REGEX
+        .captures_iter(mod_rs)
+        .map(|c| c[1].parse().unwrap())
+        .collect();
+
+    let mut out = mod_rs.to_string();
+    if !modules.contains(&day) {
+        modules.push(day);
+        modules.sort_by_key(|day| day.to_string());
+        let block: String = modules
+            .iter()
+            .map(|day| format!("pub mod day{day};\n"))
+            .collect();
+
+        let insert_at = DAY_MOD_REGEX.find(&out).map(|m| m.start());
+        out = DAY_MOD_REGEX.replace_all(&out, "").to_string();
+        match insert_at {
+            Some(index) => out.insert_str(index, &block),
+            None => {
+                let index = out.find("\npub fn solutions").map_or(out.len(), |i| i + 1);
+                out.insert_str(index, &format!("{block}\n"));
+            }
+        }
+    }
+
+    if !days.contains(&day) {
+        days.push(day);
+        days.sort();
+        let entries = render_entries(year, &days);
+        out = ENTRIES_REGEX.replace(&out, entries.as_str()).to_string();
+    }
+
+    out
+}
+
+/// Declares `y{year}` after the other year modules in `main.rs`, `None` if it already is.
+pub fn register_year_module(main_rs: &str, year: u16) -> Option<String> {
+    let declaration = format!("pub mod y{year};\n");
+    if main_rs.contains(&declaration) {
+        return None;
+    }
+
+    let index = YEAR_MOD_REGEX.find_iter(main_rs).last()?.end();
+    let mut out = main_rs.to_string();
+    out.insert_str(index, &declaration);
+
+    Some(out)
+}
+
+/// Adds `y{year}::solutions()` to `Registry::new`, `None` if it is already there.
+pub fn register_year_solutions(solution_rs: &str, year: u16) -> Option<String> {
+    if solution_rs.contains(&format!("crate::y{year}::solutions()")) {
+        return None;
+    }
+
+    let last = EXTEND_REGEX.captures_iter(solution_rs).last()?;
+    let mut out = solution_rs.to_string();
+    out.insert_str(
+        last.get(0).unwrap().end(),
+        &format!(
+            "{}registry.extend(crate::y{year}::solutions());\n",
+            &last[1]
+        ),
+    );
+
+    Some(out)
+}
+
+/// A file to write, with what it held before so the write can be undone.
+struct Edit {
+    path: PathBuf,
+    original: Option<String>,
+    contents: String,
+}
+
+/// The edit `f` makes to the file at `path`, if any.
+fn update(path: PathBuf, f: impl Fn(&str) -> Option<String>) -> io::Result<Option<Edit>> {
+    let original = fs::read_to_string(&path)?;
+    Ok(f(&original).map(|contents| Edit {
+        path,
+        original: Some(original),
+        contents,
+    }))
+}
+
+/// Writes every edit in order. If one fails, the files already written are put back
+/// the way they were before returning the error.
+fn write_all(edits: &[Edit]) -> io::Result<()> {
+    for (done, edit) in edits.iter().enumerate() {
+        if let Err(error) = fs::write(&edit.path, &edit.contents) {
+            for edit in edits[..done].iter().rev() {
+                let _ = match &edit.original {
+                    Some(original) => fs::write(&edit.path, original),
+                    None => fs::remove_file(&edit.path),
+                };
+            }
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+/// Creates `src/y{year}/day{day}.rs` under `root` and registers it, creating and
+/// declaring the year module if needed. Returns every file created or changed.
+///
+/// Every file is read and updated in memory before any is written, and a failed write
+/// undoes the others, so an error leaves no half-registered day behind.
+pub fn scaffold(root: &Path, year: u16, day: u8) -> io::Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("day {day} is not between 1 and 25"),
+        ));
+    }
+    let src = root.join("src");
+    let year_dir = src.join(format!("y{year}"));
+    let day_path = year_dir.join(format!("day{day}.rs"));
+    if !src.join("main.rs").exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "{} not found, run from the repository root",
+                src.join("main.rs").display()
+            ),
+        ));
+    }
+    if day_path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", day_path.display()),
+        ));
+    }
+
+    let mut edits = vec![Edit {
+        path: day_path,
+        original: None,
+        contents: render_template(year, day),
+    }];
+
+    let mod_path = year_dir.join("mod.rs");
+    edits.push(match fs::read_to_string(&mod_path) {
+        Ok(mod_rs) => Edit {
+            contents: register_day(&mod_rs, year, day),
+            path: mod_path,
+            original: Some(mod_rs),
+        },
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Edit {
+            path: mod_path,
+            original: None,
+            contents: new_year_module(year, day),
+        },
+        Err(error) => return Err(error),
+    });
+
+    edits.extend(update(src.join("main.rs"), |s| {
+        register_year_module(s, year)
+    })?);
+    edits.extend(update(src.join("solution.rs"), |s| {
+        register_year_solutions(s, year)
+    })?);
+    edits.retain(|edit| edit.original.as_ref() != Some(&edit.contents));
+
+    let created_dir = !year_dir.exists();
+    fs::create_dir_all(&year_dir)?;
+    if let Err(error) = write_all(&edits) {
+        if created_dir {
+            let _ = fs::remove_dir(&year_dir);
+        }
+        return Err(error);
+    }
+
+    Ok(edits.into_iter().map(|edit| edit.path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn register_day_test() {
+        let mod_rs = "use crate::solution::Entry;
+
+pub mod day1;
+pub mod day2;
+pub mod day9;
+
+pub mod direction;
+
+pub fn solutions() -> Vec<Entry> {
+    vec![
+        Entry::new::<day1::Solver>(2023, 1),
+        Entry::new::<day2::Solver>(2023, 2),
+        Entry::new::<day9::Solver>(2023, 9),
+    ]
+}
+";
+
+        assert_eq!(
+            register_day(mod_rs, 2023, 10),
+            "use crate::solution::Entry;
+
+pub mod day1;
+pub mod day10;
+pub mod day2;
+pub mod day9;
+
+pub mod direction;
+
+pub fn solutions() -> Vec<Entry> {
+    vec![
+        Entry::new::<day1::Solver>(2023, 1),
+        Entry::new::<day2::Solver>(2023, 2),
+        Entry::new::<day9::Solver>(2023, 9),
+        Entry::new::<day10::Solver>(2023, 10),
+    ]
+}
+"
+        );
+        assert_eq!(register_day(mod_rs, 2023, 2), mod_rs);
+    }
+
+    #[test]
+    fn new_year_module_test() {
+        let mod_rs = new_year_module(2025, 3);
+
+        assert_eq!(
+            mod_rs,
+            "use crate::solution::Entry;
+
+pub mod day3;
+
+pub fn solutions() -> Vec<Entry> {
+    vec![Entry::new::<day3::Solver>(2025, 3)]
+}
+"
+        );
+        assert_eq!(
+            register_day(&mod_rs, 2025, 1),
+            "use crate::solution::Entry;
+
+pub mod day1;
+pub mod day3;
+
+pub fn solutions() -> Vec<Entry> {
+    vec![
+        Entry::new::<day1::Solver>(2025, 1),
+        Entry::new::<day3::Solver>(2025, 3),
+    ]
+}
+"
+        );
+    }
+
+    #[test]
+    fn register_year_test() {
+        let main_rs = "pub mod utils;\npub mod y2021;\npub mod y2024;\n\nfn main() {}\n";
+        let solution_rs = "        registry.extend(crate::y2021::solutions());\n        registry.extend(crate::y2024::solutions());\n\n        registry\n";
+
+        assert_eq!(
+            register_year_module(main_rs, 2025).unwrap(),
+            "pub mod utils;\npub mod y2021;\npub mod y2024;\npub mod y2025;\n\nfn main() {}\n"
+        );
+        assert_eq!(register_year_module(main_rs, 2024), None);
+        assert_eq!(
+            register_year_solutions(solution_rs, 2025).unwrap(),
+            "        registry.extend(crate::y2021::solutions());\n        registry.extend(crate::y2024::solutions());\n        registry.extend(crate::y2025::solutions());\n\n        registry\n"
+        );
+        assert_eq!(register_year_solutions(solution_rs, 2021), None);
+    }
+
+    #[test]
+    fn scaffold_test() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/main.rs"), "pub mod y2024;\n").unwrap();
+
+        assert_eq!(
+            scaffold(&root, 2025, 26).unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            scaffold(&root, 2025, 1).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+        assert!(!root.join("src/y2025").exists());
+        assert_eq!(
+            fs::read_to_string(root.join("src/main.rs")).unwrap(),
+            "pub mod y2024;\n"
+        );
+
+        fs::write(
+            root.join("src/solution.rs"),
+            "    registry.extend(crate::y2024::solutions());\n",
+        )
+        .unwrap();
+
+        let changed = scaffold(&root, 2025, 1).unwrap();
+
+        assert_eq!(
+            changed,
+            vec![
+                root.join("src/y2025/day1.rs"),
+                root.join("src/y2025/mod.rs"),
+                root.join("src/main.rs"),
+                root.join("src/solution.rs"),
+            ]
+        );
+        assert!(fs::read_to_string(root.join("src/y2025/day1.rs"))
+            .unwrap()
+            .contains("example(2025, 1, 1)"));
+        assert_eq!(
+            scaffold(&root, 2025, 1).unwrap_err().kind(),
+            io::ErrorKind::AlreadyExists
+        );
+        assert_eq!(
+            scaffold(&root, 2025, 2).unwrap(),
+            vec![
+                root.join("src/y2025/day2.rs"),
+                root.join("src/y2025/mod.rs"),
+            ]
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
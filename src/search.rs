@@ -0,0 +1,292 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Every state reached so far, with its best known cost and the index of the state it
+/// was reached from.
+struct Explored<S, C> {
+    nodes: Vec<(S, C, Option<usize>)>,
+    index: HashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Explored<S, C> {
+    fn new(start: S, cost: C) -> Explored<S, C> {
+        Explored {
+            nodes: vec![(start.clone(), cost, None)],
+            index: HashMap::from([(start, 0)]),
+        }
+    }
+
+    fn path(&self, mut index: usize) -> Vec<S> {
+        let mut path = vec![self.nodes[index].0.clone()];
+        while let Some(parent) = self.nodes[index].2 {
+            path.push(self.nodes[parent].0.clone());
+            index = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    fn costs(self) -> HashMap<S, C> {
+        self.nodes
+            .into_iter()
+            .map(|(state, cost, _)| (state, cost))
+            .collect()
+    }
+}
+
+/// Runs A* from `start` until `is_goal` is popped, or every reachable state is
+/// settled when there is no goal. Returns the explored states and the goal index.
+fn best_first<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> (Explored<S, C>, Option<usize>)
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut explored = Explored::new(start, C::default());
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse((
+        heuristic(&explored.nodes[0].0),
+        C::default(),
+        0usize,
+    )));
+
+    while let Some(Reverse((_, cost, current))) = heap.pop() {
+        if cost > explored.nodes[current].1 {
+            continue;
+        }
+        let state = explored.nodes[current].0.clone();
+        if is_goal(&state) {
+            return (explored, Some(current));
+        }
+
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            let index = match explored.index.entry(next) {
+                Entry::Vacant(entry) => {
+                    let index = explored.nodes.len();
+                    explored
+                        .nodes
+                        .push((entry.key().clone(), next_cost, Some(current)));
+                    entry.insert(index);
+                    index
+                }
+                Entry::Occupied(entry) => {
+                    let index = *entry.get();
+                    let node = &mut explored.nodes[index];
+                    if next_cost >= node.1 {
+                        continue;
+                    }
+                    node.1 = next_cost;
+                    node.2 = Some(current);
+                    index
+                }
+            };
+            let estimate = next_cost + heuristic(&explored.nodes[index].0);
+            heap.push(Reverse((estimate, next_cost, index)));
+        }
+    }
+
+    (explored, None)
+}
+
+/// The cheapest path from `start` to a state matching `is_goal`, both ends included,
+/// and its cost. `successors` yields each neighbouring state with the cost to move there.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], guided by a `heuristic` that must never overestimate the
+/// remaining cost to a goal.
+pub fn astar<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let (explored, goal) = best_first(start, successors, heuristic, is_goal);
+    let goal = goal?;
+
+    Some((explored.path(goal), explored.nodes[goal].1))
+}
+
+/// The cheapest cost to every state reachable from `start`.
+pub fn dijkstra_all<S, C, I>(start: S, successors: impl FnMut(&S) -> I) -> HashMap<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let (explored, _) = best_first(start, successors, |_| C::default(), |_| false);
+
+    explored.costs()
+}
+
+fn breadth_first<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> (Explored<S, usize>, Option<usize>)
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut explored = Explored::new(start, 0);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(current) = queue.pop_front() {
+        let (state, steps, _) = explored.nodes[current].clone();
+        if is_goal(&state) {
+            return (explored, Some(current));
+        }
+
+        for next in successors(&state) {
+            if let Entry::Vacant(entry) = explored.index.entry(next) {
+                let index = explored.nodes.len();
+                explored
+                    .nodes
+                    .push((entry.key().clone(), steps + 1, Some(current)));
+                entry.insert(index);
+                queue.push_back(index);
+            }
+        }
+    }
+
+    (explored, None)
+}
+
+/// The shortest path, by number of moves, from `start` to a state matching `is_goal`,
+/// both ends included.
+pub fn bfs<S, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let (explored, goal) = breadth_first(start, successors, is_goal);
+
+    Some(explored.path(goal?))
+}
+
+/// The number of moves to every state reachable from `start`.
+pub fn bfs_all<S, I>(start: S, successors: impl FnMut(&S) -> I) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let (explored, _) = breadth_first(start, successors, |_| false);
+
+    explored.costs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Point;
+    use crate::grid::Grid;
+
+    fn maze() -> Grid<char> {
+        "S.#.
+        .##.
+        ...E"
+            .parse()
+            .unwrap()
+    }
+
+    fn open_neighbours(grid: &Grid<char>, point: &Point) -> Vec<Point> {
+        point
+            .neighbours()
+            .into_iter()
+            .filter(|p| grid.contains(*p) && grid[*p] != '#')
+            .collect()
+    }
+
+    #[test]
+    fn bfs_test() {
+        let grid = maze();
+        let end = Point::new(3, 2);
+
+        let path = bfs(Point::ORIGIN, |p| open_neighbours(&grid, p), |p| *p == end).unwrap();
+        assert_eq!(path.len(), 6);
+        assert_eq!(path[0], Point::ORIGIN);
+        assert_eq!(path[5], end);
+
+        let distances = bfs_all(Point::ORIGIN, |p| open_neighbours(&grid, p));
+        assert_eq!(distances[&end], 5);
+        assert_eq!(distances[&Point::new(3, 0)], 7);
+        assert_eq!(distances.get(&Point::new(2, 0)), None);
+        assert_eq!(
+            bfs(Point::ORIGIN, |p| open_neighbours(&grid, p), |p| p.x > 5),
+            None
+        );
+    }
+
+    #[test]
+    fn dijkstra_test() {
+        let grid = Grid::parse(
+            "131
+            191
+            111",
+            |c| c.to_digit(10),
+        )
+        .unwrap();
+        let end = Point::new(2, 2);
+        let successors = |p: &Point| {
+            p.neighbours()
+                .into_iter()
+                .filter(|n| grid.contains(*n))
+                .map(|n| (n, grid[n]))
+                .collect::<Vec<_>>()
+        };
+
+        let (path, cost) = dijkstra(Point::ORIGIN, successors, |p| *p == end).unwrap();
+        assert_eq!(cost, 4);
+        assert_eq!(
+            path,
+            vec![
+                Point::new(0, 0),
+                Point::new(0, 1),
+                Point::new(0, 2),
+                Point::new(1, 2),
+                Point::new(2, 2)
+            ]
+        );
+
+        let (_, cost) = astar(
+            Point::ORIGIN,
+            successors,
+            |p| p.manhattan(end) as u32,
+            |p| *p == end,
+        )
+        .unwrap();
+        assert_eq!(cost, 4);
+
+        let costs = dijkstra_all(Point::ORIGIN, successors);
+        assert_eq!(costs[&Point::new(2, 0)], 4);
+        assert_eq!(costs[&Point::new(1, 1)], 10);
+    }
+}
@@ -0,0 +1,181 @@
+use crate::client::AocClient;
+use crate::config::config_dir;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+static USER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"<div class="user">([^<]*)"#).unwrap());
+
+/// The adventofcode.com `session` cookie value.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Session {
+    token: String,
+}
+
+impl Session {
+    /// Accepts either the bare token or a pasted `session=...` cookie.
+    pub fn new(token: &str) -> Option<Session> {
+        let token = token.trim();
+        let token = token.strip_prefix("session=").unwrap_or(token).trim();
+        if token.is_empty() {
+            return None;
+        }
+
+        Some(Session {
+            token: token.to_string(),
+        })
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn cookie(&self) -> String {
+        format!("session={}", self.token)
+    }
+
+    /// `$XDG_CONFIG_HOME/aoc/session`, falling back to `~/.config/aoc/session`.
+    pub fn path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("session"))
+    }
+
+    pub fn read_from(path: &Path) -> io::Result<Session> {
+        Session::new(&fs::read_to_string(path)?).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} is empty", path.display()),
+            )
+        })
+    }
+
+    /// An explicit `cookie_file` wins over `$AOC_SESSION`, which wins over the
+    /// session file written by `aoc login`.
+    pub fn resolve(cookie_file: Option<&Path>) -> io::Result<Option<Session>> {
+        if let Some(path) = cookie_file {
+            return Session::read_from(path).map(Some);
+        }
+        if let Some(session) = env::var(SESSION_ENV).ok().and_then(|v| Session::new(&v)) {
+            return Ok(Some(session));
+        }
+
+        match Session::path() {
+            Some(path) => match Session::read_from(&path) {
+                Ok(session) => Ok(Some(session)),
+                Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+                Err(error) => Err(error),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Writes the token to `path`, readable only by the current user.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+            options.mode(0o600);
+            // `mode` only applies to new files, so tighten an existing one too.
+            if path.exists() {
+                fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+            }
+        }
+
+        io::Write::write_all(
+            &mut options.open(path)?,
+            format!("{}\n", self.token).as_bytes(),
+        )
+    }
+}
+
+/// The user name shown in the page header, if the page was served to a logged in user.
+pub fn parse_user(page: &str) -> Option<String> {
+    USER_REGEX
+        .captures(page)
+        .map(|captures| captures[1].trim().to_string())
+        .filter(|user| !user.is_empty())
+}
+
+/// Asks the server who `session` belongs to, `None` if it isn't logged in.
+pub fn whoami(client: &AocClient, session: &Session) -> reqwest::Result<Option<String>> {
+    let page = client.page("/", session)?.error_for_status()?.text()?;
+
+    Ok(parse_user(&page))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::ClientConfig;
+    use crate::test_server::TestServer;
+
+    #[test]
+    fn session_new() {
+        assert_eq!(Session::new("  abc123\n").unwrap().token(), "abc123");
+        assert_eq!(
+            Session::new("session=abc123").unwrap().cookie(),
+            "session=abc123"
+        );
+        assert_eq!(Session::new(" \n"), None);
+        assert_eq!(Session::new("session="), None);
+    }
+
+    #[test]
+    fn save_and_resolve() {
+        let dir = env::temp_dir().join(format!("aoc-session-{}", std::process::id()));
+        let path = dir.join("session");
+        let _ = fs::remove_dir_all(&dir);
+
+        Session::new("abc123").unwrap().save(&path).unwrap();
+        assert_eq!(
+            Session::resolve(Some(&path)).unwrap(),
+            Session::new("abc123")
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        assert!(Session::resolve(Some(&dir.join("missing"))).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn whoami_test() {
+        let server = TestServer::new(vec![
+            (
+                200,
+                "<header><div class=\"user\">jessebond2 <span class=\"star-count\">50*</span></div></header>",
+            ),
+            (200, "<header><a href=\"/auth/login\">[Log In]</a></header>"),
+        ]);
+        let client = AocClient::new(ClientConfig {
+            base_url: server.url.clone(),
+            ..ClientConfig::default()
+        })
+        .unwrap();
+        let session = Session::new("abc123").unwrap();
+
+        assert_eq!(
+            whoami(&client, &session).unwrap(),
+            Some(String::from("jessebond2"))
+        );
+        let request = server.request();
+        assert!(request.starts_with("GET / HTTP/1.1\r\n"));
+        assert!(request.contains("cookie: session=abc123\r\n"));
+
+        assert_eq!(whoami(&client, &session).unwrap(), None);
+    }
+}
@@ -0,0 +1,234 @@
+use crate::download;
+use crate::error::ParseError;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::panic;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A single day's puzzle. The input is parsed once and shared by both parts, so
+/// malformed input is reported by `parse` before either part runs. The parts may run
+/// on separate threads, hence the `Sync` bound.
+pub trait Solution {
+    type Input: Sync;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_1(input: &Self::Input) -> impl Display;
+    fn part_2(input: &Self::Input) -> impl Display;
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Answers {
+    pub part_1: String,
+    pub part_2: String,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part_1: Duration,
+    pub part_2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part_1 + self.part_2
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub answers: Answers,
+    pub timings: Timings,
+}
+
+pub fn solve<S: Solution>(input: &str) -> Result<Outcome, ParseError> {
+    let now = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse = now.elapsed();
+
+    let now = Instant::now();
+    let part_1 = S::part_1(&parsed).to_string();
+    let part_1_time = now.elapsed();
+
+    let now = Instant::now();
+    let part_2 = S::part_2(&parsed).to_string();
+    let part_2_time = now.elapsed();
+
+    Ok(Outcome {
+        answers: Answers { part_1, part_2 },
+        timings: Timings {
+            parse,
+            part_1: part_1_time,
+            part_2: part_2_time,
+        },
+    })
+}
+
+/// Like [`solve`], but runs part 1 and part 2 side by side once the input is parsed.
+pub fn solve_concurrently<S: Solution>(input: &str) -> Result<Outcome, ParseError> {
+    let now = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse = now.elapsed();
+
+    let timed = |part: fn(&S::Input) -> String| {
+        let now = Instant::now();
+        let answer = part(&parsed);
+        (answer, now.elapsed())
+    };
+    let ((part_1, part_1_time), (part_2, part_2_time)) = thread::scope(|scope| {
+        let part_2 = scope.spawn(|| timed(|input| S::part_2(input).to_string()));
+        let part_1 = timed(|input| S::part_1(input).to_string());
+        match part_2.join() {
+            Ok(part_2) => (part_1, part_2),
+            Err(panic) => panic::resume_unwind(panic),
+        }
+    });
+
+    Ok(Outcome {
+        answers: Answers { part_1, part_2 },
+        timings: Timings {
+            parse,
+            part_1: part_1_time,
+            part_2: part_2_time,
+        },
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub solve: fn(&str) -> Result<Outcome, ParseError>,
+    pub solve_concurrently: fn(&str) -> Result<Outcome, ParseError>,
+}
+
+impl Entry {
+    pub fn new<S: Solution>(year: u16, day: u8) -> Entry {
+        Entry {
+            year,
+            day,
+            solve: solve::<S>,
+            solve_concurrently: solve_concurrently::<S>,
+        }
+    }
+
+    pub fn input_path(&self, dir: &Path) -> String {
+        download::input_path(dir, self.year, self.day)
+            .display()
+            .to_string()
+    }
+}
+
+/// Every registered day, keyed by `(year, day)`.
+pub struct Registry {
+    entries: BTreeMap<(u16, u8), Entry>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        let mut registry = Registry {
+            entries: BTreeMap::new(),
+        };
+
+        registry.extend(crate::y2021::solutions());
+        registry.extend(crate::y2023::solutions());
+        registry.extend(crate::y2024::solutions());
+
+        registry
+    }
+
+    fn extend(&mut self, entries: Vec<Entry>) {
+        for entry in entries {
+            self.entries.insert((entry.year, entry.day), entry);
+        }
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<&Entry> {
+        self.entries.get(&(year, day))
+    }
+
+    pub fn year(&self, year: u16) -> impl Iterator<Item = &Entry> {
+        self.entries
+            .range((year, 0)..=(year, u8::MAX))
+            .map(|(_, e)| e)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.values()
+    }
+
+    pub fn years(&self) -> Vec<u16> {
+        let mut years: Vec<u16> = self.entries.keys().map(|(year, _)| *year).collect();
+        years.dedup();
+        years
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn lines(input: &str) -> Vec<String> {
+    input.lines().map(String::from).collect()
+}
+
+/// Shows a part that has no answer for this input as `-`.
+pub fn or_dash(answer: Option<impl Display>) -> String {
+    answer.map_or(String::from("-"), |answer| answer.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_keys() {
+        let registry = Registry::new();
+
+        assert!(registry.get(2021, 3).is_some());
+        assert!(registry.get(2023, 25).is_some());
+        assert!(registry.get(2024, 25).is_some());
+        assert!(registry.get(2022, 1).is_none());
+        assert_eq!(registry.year(2024).count(), 25);
+        assert_eq!(registry.year(2023).count(), 25);
+        assert_eq!(registry.years(), vec![2021, 2023, 2024]);
+    }
+
+    #[test]
+    fn solve_test() {
+        let outcome =
+            (registry_entry(2024, 1).solve)("3   4\n4   3\n2   5\n1   3\n3   9\n3   3").unwrap();
+
+        assert_eq!(
+            outcome.answers,
+            Answers {
+                part_1: String::from("11"),
+                part_2: String::from("31"),
+            }
+        );
+        assert_eq!(
+            (registry_entry(2024, 1).solve)("3   4\n4   x").unwrap_err(),
+            ParseError::new("a number", "x").at(2, 5)
+        );
+    }
+
+    #[test]
+    fn solve_concurrently_test() {
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3";
+        let entry = registry_entry(2024, 1);
+
+        assert_eq!(
+            (entry.solve_concurrently)(input).unwrap().answers,
+            (entry.solve)(input).unwrap().answers
+        );
+        assert!((entry.solve_concurrently)("3   4\n4   x").is_err());
+    }
+
+    fn registry_entry(year: u16, day: u8) -> Entry {
+        *Registry::new().get(year, day).unwrap()
+    }
+}
@@ -0,0 +1,275 @@
+//! The small subset of TOML that answers, guesses, baselines and the config are kept
+//! in: `[section]` headers followed by `key = value` pairs, with blank lines and `#`
+//! comments skipped. Strings are double quoted and numbers bare.
+
+use crate::error::ParseError;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A section name, key or value, remembering where it was read from.
+#[derive(Debug, Clone, Copy)]
+pub struct Field<'a> {
+    text: &'a str,
+    line: &'a str,
+    number: usize,
+}
+
+/// A line that says something: a section header or a `key = value` pair.
+#[derive(Debug, Clone, Copy)]
+pub enum Line<'a> {
+    Section(Field<'a>),
+    Pair(Field<'a>, Field<'a>),
+}
+
+impl<'a> Field<'a> {
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// An error placed at this field.
+    pub fn error(&self, expected: &'static str) -> ParseError {
+        ParseError::new(expected, self.text)
+            .on_line(self.number)
+            .within(self.line, self.text)
+    }
+
+    pub fn parse<T: FromStr>(&self, expected: &'static str) -> Result<T, ParseError> {
+        self.text.parse().map_err(|_| self.error(expected))
+    }
+
+    /// The number following `prefix`, as in `[day12]`.
+    pub fn number_after<T: FromStr>(
+        &self,
+        prefix: &str,
+        expected: &'static str,
+    ) -> Result<T, ParseError> {
+        self.text
+            .strip_prefix(prefix)
+            .and_then(|number| number.parse().ok())
+            .ok_or_else(|| self.error(expected))
+    }
+
+    /// The contents of a quoted string, with its escapes undone.
+    pub fn string(&self) -> Result<String, ParseError> {
+        self.text
+            .strip_prefix('"')
+            .and_then(|text| text.strip_suffix('"'))
+            .and_then(unescape)
+            .ok_or_else(|| self.error("a quoted string"))
+    }
+}
+
+/// The section headers and pairs of `s`, in order.
+pub fn lines<'a>(s: &'a str) -> impl Iterator<Item = Result<Line<'a>, ParseError>> {
+    s.lines().enumerate().filter_map(|(index, line)| {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            return None;
+        }
+        let field = |text: &'a str| Field {
+            text: text.trim(),
+            line,
+            number: index + 1,
+        };
+
+        if let Some(name) = trimmed.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+            return Some(Ok(Line::Section(field(name))));
+        }
+        Some(
+            split_pair(trimmed)
+                .map(|(key, value)| Line::Pair(field(key), field(value)))
+                .ok_or_else(|| {
+                    ParseError::new("key = value", trimmed)
+                        .on_line(index + 1)
+                        .within(line, trimmed)
+                }),
+        )
+    })
+}
+
+/// Splits at the first `=` after the key, which may be a quoted string holding `=`.
+fn split_pair(line: &str) -> Option<(&str, &str)> {
+    let key_end = if line.starts_with('"') {
+        let mut escaped = false;
+        let (end, _) = line.char_indices().skip(1).find(|&(_, c)| {
+            let closes = c == '"' && !escaped;
+            escaped = c == '\\' && !escaped;
+            closes
+        })?;
+        end + 1
+    } else {
+        line.find('=')?
+    };
+
+    let (key, rest) = line.split_at(key_end);
+    Some((key, rest.trim_start().strip_prefix('=')?))
+}
+
+/// `s` as a quoted string, escaping anything that would end it or the line early.
+pub fn quote(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            _ => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn unescape(s: &str) -> Option<String> {
+    let mut out = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                'n' => out.push('\n'),
+                'r' => out.push('\r'),
+                't' => out.push('\t'),
+                c => out.push(c),
+            },
+            '"' => return None,
+            _ => out.push(c),
+        }
+    }
+    Some(out)
+}
+
+/// Writes `key = value` pairs under their `[section]` headers, with a blank line
+/// between sections. Pairs must already be grouped by section.
+pub fn write_sections<S, K, V>(
+    f: &mut Formatter,
+    pairs: impl IntoIterator<Item = (S, K, V)>,
+) -> fmt::Result
+where
+    S: Display + PartialEq,
+    K: Display,
+    V: Display,
+{
+    let mut last_section = None;
+    for (section, key, value) in pairs {
+        if last_section.as_ref() != Some(&section) {
+            if last_section.is_some() {
+                writeln!(f)?;
+            }
+            writeln!(f, "[{section}]")?;
+            last_section = Some(section);
+        }
+        writeln!(f, "{key} = {value}")?;
+    }
+    Ok(())
+}
+
+/// Reads and parses `path`, or returns the default if it doesn't exist yet.
+pub fn load<T>(path: &Path) -> io::Result<T>
+where
+    T: FromStr<Err = ParseError> + Default,
+{
+    match fs::read_to_string(path) {
+        Ok(contents) => contents
+            .parse()
+            .map_err(|error: ParseError| io::Error::new(io::ErrorKind::InvalidData, error)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(T::default()),
+        Err(error) => Err(error),
+    }
+}
+
+/// Writes `contents` to `path`, creating its directory if needed.
+pub fn save(path: &Path, contents: &impl Display) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(s: &str) -> Vec<(Option<&str>, &str, &str)> {
+        let mut section = None;
+        let mut pairs = vec![];
+        for line in lines(s) {
+            match line.unwrap() {
+                Line::Section(name) => section = Some(name.text()),
+                Line::Pair(key, value) => pairs.push((section, key.text(), value.text())),
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn lines_test() {
+        let input = "# comment
+        top = 1
+
+        [day1]
+        part_1 = \"a = b\"
+        \"x = \\\"y\\\"\" = \"wrong\"";
+
+        assert_eq!(
+            pairs(input),
+            vec![
+                (None, "top", "1"),
+                (Some("day1"), "part_1", "\"a = b\""),
+                (Some("day1"), "\"x = \\\"y\\\"\"", "\"wrong\""),
+            ]
+        );
+        assert_eq!(
+            lines("[day1]\n  part_1").nth(1).unwrap().unwrap_err(),
+            ParseError::new("key = value", "part_1").at(2, 3)
+        );
+    }
+
+    #[test]
+    fn field_test() {
+        let Some(Ok(Line::Pair(key, value))) = lines("\n[day3]\nday12 = \"a\\\\\\n\"").nth(1)
+        else {
+            panic!("expected a pair");
+        };
+
+        assert_eq!(key.number_after::<u8>("day", "a day"), Ok(12));
+        assert_eq!(
+            key.number_after::<u8>("part", "a part"),
+            Err(ParseError::new("a part", "day12").at(3, 1))
+        );
+        assert_eq!(value.string(), Ok(String::from("a\\\n")));
+        assert_eq!(
+            key.string(),
+            Err(ParseError::new("a quoted string", "day12").at(3, 1))
+        );
+        assert_eq!(
+            value.parse::<u32>("a number"),
+            Err(ParseError::new("a number", "\"a\\\\\\n\"").at(3, 9))
+        );
+    }
+
+    #[test]
+    fn quote_round_trip() {
+        for s in [
+            "plain",
+            "say \"hi\"",
+            "a\\b",
+            "two\nlines\r\n",
+            "tab\there",
+            "",
+        ] {
+            let quoted = quote(s);
+
+            assert!(!quoted.contains('\n'));
+            let pair = format!("k = {quoted}");
+            let Some(Ok(Line::Pair(_, value))) = lines(&pair).next() else {
+                panic!("expected a pair");
+            };
+            assert_eq!(value.string(), Ok(String::from(s)));
+        }
+    }
+}
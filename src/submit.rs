@@ -0,0 +1,365 @@
+use crate::client::AocClient;
+use crate::error::ParseError;
+use crate::session::Session;
+use crate::store::{self, Line};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub const GUESSES_DIR: &str = "./guesses";
+
+static ARTICLE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap());
+static TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]*>").unwrap());
+static WAIT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap());
+
+/// What the server said about a submitted answer.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Feedback {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Submitted too recently, try again in this many seconds.
+    Wait(u64),
+    AlreadySolved,
+    Unknown(String),
+}
+
+impl Feedback {
+    /// Parses the `<article>` of the page returned by the answer endpoint.
+    pub fn parse(page: &str) -> Feedback {
+        let text = match ARTICLE_REGEX.captures(page) {
+            Some(captures) => TAG_REGEX.replace_all(&captures[1], "").to_string(),
+            None => TAG_REGEX.replace_all(page, "").to_string(),
+        };
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        if text.contains("That's the right answer") {
+            Feedback::Correct
+        } else if text.contains("your answer is too high") {
+            Feedback::TooHigh
+        } else if text.contains("your answer is too low") {
+            Feedback::TooLow
+        } else if text.contains("That's not the right answer") {
+            Feedback::Wrong
+        } else if let Some(captures) = WAIT_REGEX.captures(&text) {
+            let minutes: u64 = captures.get(1).map_or(0, |m| m.as_str().parse().unwrap());
+            let seconds: u64 = captures[2].parse().unwrap();
+            Feedback::Wait(minutes * 60 + seconds)
+        } else if text.contains("You don't seem to be solving the right level") {
+            Feedback::AlreadySolved
+        } else {
+            Feedback::Unknown(text)
+        }
+    }
+
+    fn key(&self) -> Option<&'static str> {
+        match self {
+            Feedback::Correct => Some("correct"),
+            Feedback::TooHigh => Some("too_high"),
+            Feedback::TooLow => Some("too_low"),
+            Feedback::Wrong => Some("wrong"),
+            _ => None,
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Feedback> {
+        match key {
+            "correct" => Some(Feedback::Correct),
+            "too_high" => Some(Feedback::TooHigh),
+            "too_low" => Some(Feedback::TooLow),
+            "wrong" => Some(Feedback::Wrong),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Feedback {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Feedback::Correct => write!(f, "That's the right answer!"),
+            Feedback::TooHigh => write!(f, "That's not the right answer, it's too high"),
+            Feedback::TooLow => write!(f, "That's not the right answer, it's too low"),
+            Feedback::Wrong => write!(f, "That's not the right answer"),
+            Feedback::Wait(seconds) => write!(
+                f,
+                "Answered too recently, wait {}m {}s",
+                seconds / 60,
+                seconds % 60
+            ),
+            Feedback::AlreadySolved => write!(f, "This part is already solved"),
+            Feedback::Unknown(text) => write!(f, "Unrecognised response: {text}"),
+        }
+    }
+}
+
+/// POSTs `answer` for `part` and parses the response.
+pub fn submit(
+    client: &AocClient,
+    session: &Session,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> reqwest::Result<Feedback> {
+    let page = client
+        .answer(year, day, part, answer, session)?
+        .error_for_status()?
+        .text()?;
+
+    Ok(Feedback::parse(&page))
+}
+
+/// What the history already says about a guess, before it is submitted.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Precheck {
+    Fresh,
+    Known(Feedback),
+    /// At or above an answer that was too high.
+    AboveHigh(String),
+    /// At or below an answer that was too low.
+    BelowLow(String),
+}
+
+/// Every answer submitted for a single year, stored as `guesses/{year}.toml`:
+///
+/// ```toml
+/// [day3.part2]
+/// "1234" = "too_high"
+/// "1000" = "correct"
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct GuessHistory {
+    guesses: BTreeMap<(u8, u8), BTreeMap<String, Feedback>>,
+}
+
+impl GuessHistory {
+    pub fn path(dir: &Path, year: u16) -> PathBuf {
+        dir.join(format!("{year}.toml"))
+    }
+
+    /// Loads the history for `year`, or an empty one if nothing has been submitted yet.
+    pub fn load(dir: &Path, year: u16) -> io::Result<GuessHistory> {
+        store::load(&Self::path(dir, year))
+    }
+
+    pub fn save(&self, dir: &Path, year: u16) -> io::Result<()> {
+        store::save(&Self::path(dir, year), self)
+    }
+
+    /// Stores `feedback` if it says anything about the answer itself.
+    pub fn record(&mut self, day: u8, part: u8, answer: &str, feedback: &Feedback) {
+        if feedback.key().is_some() {
+            self.guesses
+                .entry((day, part))
+                .or_default()
+                .insert(answer.to_string(), feedback.clone());
+        }
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Precheck {
+        let Some(guesses) = self.guesses.get(&(day, part)) else {
+            return Precheck::Fresh;
+        };
+        if let Some(feedback) = guesses.get(answer) {
+            return Precheck::Known(feedback.clone());
+        }
+
+        let Ok(value) = answer.parse::<i64>() else {
+            return Precheck::Fresh;
+        };
+        let bound = |feedback: Feedback| {
+            guesses
+                .iter()
+                .filter(move |(_, f)| **f == feedback)
+                .filter_map(|(guess, _)| guess.parse::<i64>().ok())
+        };
+
+        if let Some(high) = bound(Feedback::TooHigh).min().filter(|high| value >= *high) {
+            Precheck::AboveHigh(high.to_string())
+        } else if let Some(low) = bound(Feedback::TooLow).max().filter(|low| value <= *low) {
+            Precheck::BelowLow(low.to_string())
+        } else {
+            Precheck::Fresh
+        }
+    }
+}
+
+impl std::fmt::Display for GuessHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        store::write_sections(
+            f,
+            self.guesses.iter().flat_map(|((day, part), guesses)| {
+                guesses.iter().map(move |(answer, feedback)| {
+                    (
+                        format!("day{day}.part{part}"),
+                        store::quote(answer),
+                        store::quote(feedback.key().unwrap()),
+                    )
+                })
+            }),
+        )
+    }
+}
+
+impl FromStr for GuessHistory {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut history = GuessHistory::default();
+        let mut section: Option<(u8, u8)> = None;
+
+        for line in store::lines(s) {
+            match line? {
+                Line::Section(name) => {
+                    let (day, part) = name
+                        .text()
+                        .strip_prefix("day")
+                        .and_then(|header| header.split_once(".part"))
+                        .and_then(|(day, part)| Some((day.parse().ok()?, part.parse().ok()?)))
+                        .ok_or_else(|| name.error("a [dayN.partM] section"))?;
+                    section = Some((day, part));
+                }
+                Line::Pair(answer, feedback) => {
+                    let guess = answer.string()?;
+                    let feedback = Feedback::from_key(&feedback.string()?)
+                        .ok_or_else(|| feedback.error("correct, too_high, too_low or wrong"))?;
+                    let (day, part) =
+                        section.ok_or_else(|| answer.error("a [dayN.partM] section"))?;
+                    history.record(day, part, &guess, &feedback);
+                }
+            }
+        }
+
+        Ok(history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::ClientConfig;
+    use crate::test_server::TestServer;
+
+    #[test]
+    fn parse_feedback() {
+        let page = |text: &str| format!("<main>\n<article><p>{text}</p></article>\n</main>");
+
+        assert_eq!(
+            Feedback::parse(&page("That's the right answer!  You are <em>one gold star</em> closer to restoring snow operations.")),
+            Feedback::Correct
+        );
+        assert_eq!(
+            Feedback::parse(&page("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.")),
+            Feedback::TooHigh
+        );
+        assert_eq!(
+            Feedback::parse(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Feedback::TooLow
+        );
+        assert_eq!(
+            Feedback::parse(&page("That's not the right answer.  If you're stuck, there are some general tips on the <a href=\"/2023/about\">about page</a>.")),
+            Feedback::Wrong
+        );
+        assert_eq!(
+            Feedback::parse(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.")),
+            Feedback::Wait(65)
+        );
+        assert_eq!(
+            Feedback::parse(&page(
+                "You gave an answer too recently.  You have 34s left to wait."
+            )),
+            Feedback::Wait(34)
+        );
+        assert_eq!(
+            Feedback::parse(&page("You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2023/day/3\">[Return to Day 3]</a>")),
+            Feedback::AlreadySolved
+        );
+        assert_eq!(
+            Feedback::parse(&page("Something <b>new</b>")),
+            Feedback::Unknown(String::from("Something new"))
+        );
+    }
+
+    #[test]
+    fn guess_history_check() {
+        let mut history = GuessHistory::default();
+        history.record(3, 2, "500", &Feedback::TooHigh);
+        history.record(3, 2, "900", &Feedback::TooHigh);
+        history.record(3, 2, "100", &Feedback::TooLow);
+        history.record(3, 2, "abc", &Feedback::Wrong);
+        history.record(3, 2, "300", &Feedback::Wait(30));
+
+        assert_eq!(history.check(3, 2, "abc"), Precheck::Known(Feedback::Wrong));
+        assert_eq!(
+            history.check(3, 2, "500"),
+            Precheck::Known(Feedback::TooHigh)
+        );
+        assert_eq!(
+            history.check(3, 2, "600"),
+            Precheck::AboveHigh(String::from("500"))
+        );
+        assert_eq!(
+            history.check(3, 2, "50"),
+            Precheck::BelowLow(String::from("100"))
+        );
+        assert_eq!(history.check(3, 2, "300"), Precheck::Fresh);
+        assert_eq!(history.check(3, 1, "600"), Precheck::Fresh);
+    }
+
+    #[test]
+    fn guess_history_round_trip() {
+        let mut history = GuessHistory::default();
+        history.record(12, 1, "say \"hi\"", &Feedback::Wrong);
+        history.record(3, 2, "500", &Feedback::TooHigh);
+        history.record(3, 2, "42", &Feedback::Correct);
+
+        assert_eq!(
+            history.to_string(),
+            "[day3.part2]\n\"42\" = \"correct\"\n\"500\" = \"too_high\"\n\n[day12.part1]\n\"say \\\"hi\\\"\" = \"wrong\"\n"
+        );
+        assert_eq!(
+            GuessHistory::from_str(&history.to_string()).unwrap(),
+            history
+        );
+        history.record(3, 2, "a = \"b\"\n", &Feedback::Wrong);
+        assert_eq!(
+            GuessHistory::from_str(&history.to_string()).unwrap(),
+            history
+        );
+        assert_eq!(
+            GuessHistory::from_str("[day3.part2]\n\"1\" = \"maybe\""),
+            Err(ParseError::new("correct, too_high, too_low or wrong", "\"maybe\"").at(2, 7))
+        );
+    }
+
+    #[test]
+    fn submit_request() {
+        let server = TestServer::new(vec![(
+            200,
+            "<article><p>That's the right answer!</p></article>",
+        )]);
+        let client = AocClient::new(ClientConfig {
+            base_url: server.url.clone(),
+            ..ClientConfig::default()
+        })
+        .unwrap();
+        let session = Session::new("abc").unwrap();
+
+        assert_eq!(
+            submit(&client, &session, 2023, 3, 2, "4 2").unwrap(),
+            Feedback::Correct
+        );
+        let request = server.request();
+        assert!(request.starts_with("POST /2023/day/3/answer HTTP/1.1\r\n"));
+        assert!(request.contains("cookie: session=abc\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=4+2"));
+    }
+}
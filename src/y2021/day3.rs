@@ -1,12 +1,14 @@
-fn convert_binary_string_to_i32(string: &String) -> i32 {
-    let sum: u32 = string
-        .chars()
-        .rev()
-        .enumerate()
-        .map(|(index, value)| value.to_digit(10).unwrap() << index)
-        .sum();
-    sum as i32
-}
+#![allow(
+    clippy::ptr_arg,
+    clippy::assign_op_pattern,
+    clippy::manual_retain,
+    clippy::iter_next_slice
+)]
+
+use crate::error::ParseError;
+use crate::solution::Solution;
+use crate::trace;
+use std::fmt::Display;
 
 fn convert_binary_vec_to_i32(vec: &Vec<i32>) -> i32 {
     vec.iter()
@@ -16,8 +18,8 @@ fn convert_binary_vec_to_i32(vec: &Vec<i32>) -> i32 {
         .sum()
 }
 
-fn gamma_and_epsilon_vecs(input: &Vec<String>, favor_gamma: bool) -> (Vec<i32>, Vec<i32>) {
-    let len = input[0].len();
+fn gamma_and_epsilon_vecs(input: &Vec<Vec<i32>>, favor_gamma: bool) -> (Vec<i32>, Vec<i32>) {
+    let len = input.first().map_or(0, Vec::len);
     let mut zero_count_vec = vec![0; len];
     let mut one_count_vec = vec![0; len];
 
@@ -25,26 +27,22 @@ fn gamma_and_epsilon_vecs(input: &Vec<String>, favor_gamma: bool) -> (Vec<i32>,
     let mut epsilon_vec: Vec<i32> = vec![0; len];
 
     for i in input.iter() {
-        for (i, c) in i.chars().enumerate() {
-            match c {
-                '0' => {
-                    zero_count_vec[i] = zero_count_vec[i] + 1;
-                }
-                '1' => {
-                    one_count_vec[i] = one_count_vec[i] + 1;
-                }
-                _ => {
-                    panic!("Something broke");
-                }
+        for (i, bit) in i.iter().enumerate() {
+            if *bit == 0 {
+                zero_count_vec[i] = zero_count_vec[i] + 1;
+            } else {
+                one_count_vec[i] = one_count_vec[i] + 1;
             }
         }
     }
 
     for i in 0..len {
-        // println!(
-        //     "idx {}, z count {}, o count {}",
-        //     i, zero_count_vec[i], one_count_vec[i]
-        // );
+        trace!(
+            "idx {}, z count {}, o count {}",
+            i,
+            zero_count_vec[i],
+            one_count_vec[i]
+        );
         if zero_count_vec[i] == one_count_vec[i] {
             if favor_gamma {
                 gamma_vec[i] = 1;
@@ -57,69 +55,101 @@ fn gamma_and_epsilon_vecs(input: &Vec<String>, favor_gamma: bool) -> (Vec<i32>,
             epsilon_vec[i] = 1;
         }
     }
-    // println!("gamma_vec {:?}, epsilon_vec {:?}", gamma_vec, epsilon_vec);
+    trace!("gamma_vec {:?}, epsilon_vec {:?}", gamma_vec, epsilon_vec);
     (gamma_vec, epsilon_vec)
 }
 
-fn oxygen_rating(input: &Vec<String>) -> i32 {
+fn oxygen_rating(input: &Vec<Vec<i32>>) -> i32 {
     let mut ratings = input.clone();
     let mut index = 0;
 
-    while ratings.len() > 1 {
+    // Identical numbers can't be told apart, so stop once every bit has been used.
+    while ratings.len() > 1 && index < ratings[0].len() {
         let zeros_or_ones: i32 = ratings
             .iter()
-            .map(|s| match s.chars().nth(index).unwrap() {
-                '0' => -1,
+            .map(|s| match s[index] {
+                0 => -1,
                 _ => 1,
             })
             .sum();
-        let mut char = '0';
+        let mut bit = 0;
         if zeros_or_ones >= 0 {
-            char = '1';
+            bit = 1;
         };
 
-        ratings = ratings
-            .into_iter()
-            .filter(|r| r.chars().nth(index).unwrap() == char)
-            .collect();
+        ratings = ratings.into_iter().filter(|r| r[index] == bit).collect();
 
         index += 1;
     }
 
-    let rating = ratings.iter().next().unwrap();
-    convert_binary_string_to_i32(rating)
+    ratings.iter().next().map_or(0, convert_binary_vec_to_i32)
 }
 
-fn co2_rating(input: &Vec<String>) -> i32 {
+fn co2_rating(input: &Vec<Vec<i32>>) -> i32 {
     let mut ratings = input.clone();
     let mut index = 0;
 
-    while ratings.len() > 1 {
+    // Identical numbers can't be told apart, so stop once every bit has been used.
+    while ratings.len() > 1 && index < ratings[0].len() {
         let zeros_or_ones: i32 = ratings
             .iter()
-            .map(|s| match s.chars().nth(index).unwrap() {
-                '0' => -1,
+            .map(|s| match s[index] {
+                0 => -1,
                 _ => 1,
             })
             .sum();
-        let mut char = '1';
+        let mut bit = 1;
         if zeros_or_ones >= 0 {
-            char = '0';
+            bit = 0;
         };
 
-        ratings = ratings
-            .into_iter()
-            .filter(|r| r.chars().nth(index).unwrap() == char)
-            .collect();
+        ratings = ratings.into_iter().filter(|r| r[index] == bit).collect();
 
         index += 1;
     }
 
-    let rating = ratings.iter().next().unwrap();
-    convert_binary_string_to_i32(rating)
+    ratings.iter().next().map_or(0, convert_binary_vec_to_i32)
 }
 
-pub fn part_1(input: &Vec<String>) -> i32 {
+/// One binary number per non-empty line, as its bits, all as wide as the first.
+pub fn parse(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    let mut numbers: Vec<Vec<i32>> = vec![];
+
+    for (index, line) in input.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let mut bits = vec![];
+        for (offset, c) in trimmed.char_indices() {
+            let bit = match c {
+                '0' => 0,
+                '1' => 1,
+                _ => {
+                    let text = &trimmed[offset..offset + c.len_utf8()];
+                    return Err(ParseError::new("a binary digit", text)
+                        .on_line(index + 1)
+                        .within(line, text));
+                }
+            };
+            bits.push(bit);
+        }
+        if numbers
+            .first()
+            .is_some_and(|first| first.len() != bits.len())
+        {
+            return Err(ParseError::new("a number as wide as the first", trimmed)
+                .on_line(index + 1)
+                .within(line, trimmed));
+        }
+        numbers.push(bits);
+    }
+
+    Ok(numbers)
+}
+
+pub fn part_1(input: &Vec<Vec<i32>>) -> i32 {
     let (gamma_vec, epsilon_vec) = gamma_and_epsilon_vecs(input, true);
     let epsilon = convert_binary_vec_to_i32(&epsilon_vec);
     let gamma = convert_binary_vec_to_i32(&gamma_vec);
@@ -127,91 +157,76 @@ pub fn part_1(input: &Vec<String>) -> i32 {
     epsilon * gamma
 }
 
-pub fn part_2(input: &Vec<String>) -> i32 {
+pub fn part_2(input: &Vec<Vec<i32>>) -> i32 {
     let oxygen_rating = oxygen_rating(input);
     let co2_scrubber_ratting = co2_rating(input);
 
     oxygen_rating * co2_scrubber_ratting
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_one_test() {
-        let input = r#"00100
-            11110
-            10110
-            10111
-            10101
-            01111
-            00111
-            11100
-            10000
-            11001
-            00010
-            01010"#;
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        let result = part_1(&lines);
-
-        assert_eq!(result, 198);
+        let input = parse(&example(2021, 3, 1)).unwrap();
+
+        assert_eq!(part_1(&input).to_string(), example_answer(2021, 3, 1, 1));
     }
 
     #[test]
     fn part_two_test() {
-        let input = r#"00100
-            11110
-            10110
-            10111
-            10101
-            01111
-            00111
-            11100
-            10000
-            11001
-            00010
-            01010"#;
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-
-        assert_eq!(part_2(&lines), 230)
+        let input = parse(&example(2021, 3, 1)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2021, 3, 1, 2));
     }
 
     #[test]
     fn oxygen_generator_rating_test() {
-        let input = r#"00100
-            11110
-            10110
-            10111
-            10101
-            01111
-            00111
-            11100
-            10000
-            11001
-            00010
-            01010"#;
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-
-        assert_eq!(oxygen_rating(&lines), 23);
+        let input = parse(&example(2021, 3, 1)).unwrap();
+
+        assert_eq!(oxygen_rating(&input), 23);
     }
 
     #[test]
     fn co2_rating_test() {
-        let input = r#"00100
-            11110
-            10110
-            10111
-            10101
-            01111
-            00111
-            11100
-            10000
-            11001
-            00010
-            01010"#;
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-
-        assert_eq!(co2_rating(&lines), 10);
+        let input = parse(&example(2021, 3, 1)).unwrap();
+
+        assert_eq!(co2_rating(&input), 10);
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(parse("101\n011\n"), Ok(vec![vec![1, 0, 1], vec![0, 1, 1]]));
+        assert_eq!(oxygen_rating(&parse("101\n101").unwrap()), 5);
+        assert_eq!(
+            parse("101\n0201"),
+            Err(ParseError::new("a binary digit", "2").at(2, 2))
+        );
+        assert_eq!(
+            parse("101\n0110"),
+            Err(ParseError::new("a number as wide as the first", "0110").at(2, 1))
+        );
+        assert_eq!(part_1(&vec![]), 0);
     }
 }
@@ -1,4 +1,7 @@
-use core::slice::Iter;
+use crate::error::{parse_field, ParseError};
+use crate::solution::Solution;
+use std::fmt::Display;
+use std::mem;
 
 #[derive(Debug, PartialEq)]
 struct BingoPosition {
@@ -9,8 +12,7 @@ struct BingoPosition {
 }
 
 impl BingoPosition {
-    pub fn build(value_str: &str, x: usize, y: usize) -> BingoPosition {
-        let value = value_str.parse::<i32>().unwrap();
+    pub fn build(value: i32, x: usize, y: usize) -> BingoPosition {
         BingoPosition {
             x,
             y,
@@ -26,8 +28,8 @@ impl BingoPosition {
 
 #[derive(Debug, PartialEq)]
 struct BingoBoard {
-    rows: Vec<Vec<Box<BingoPosition>>>,
-    bingo: Vec<Box<BingoPosition>>,
+    rows: Vec<Vec<BingoPosition>>,
+    bingo: Vec<BingoPosition>,
 }
 
 impl BingoBoard {
@@ -38,19 +40,27 @@ impl BingoBoard {
         }
     }
 
-    fn add_row(&mut self, row_str: &str) {
-        let positions: Vec<Box<BingoPosition>> = row_str
+    fn add_row(&mut self, row_str: &str) -> Result<(), ParseError> {
+        if self.rows.len() == 5 {
+            return Err(ParseError::new(
+                "a blank line between boards",
+                row_str.trim(),
+            ));
+        }
+        let positions: Vec<BingoPosition> = row_str
             .split_whitespace()
             .enumerate()
             .map(|(index, value)| {
-                Box::new(BingoPosition::build(
-                    value,
-                    index.try_into().unwrap(),
-                    self.rows.len(),
-                ))
+                let value = parse_field(row_str, value, "a number")?;
+                Ok(BingoPosition::build(value, index, self.rows.len()))
             })
-            .collect();
+            .collect::<Result<_, ParseError>>()?;
+        if positions.len() != 5 {
+            return Err(ParseError::new("a row of 5 numbers", row_str.trim())
+                .within(row_str, row_str.trim()));
+        }
         self.rows.push(positions);
+        Ok(())
     }
 
     // fn mark(&mut self, value: i32) {
@@ -73,6 +83,76 @@ impl BingoBoard {
     // }
 }
 
+/// The numbers drawn, in order, and the boards they are marked on.
+#[derive(Debug, PartialEq)]
+pub struct Bingo {
+    moves: Vec<i32>,
+    boards: Vec<BingoBoard>,
+}
+
+/// The moves on the first line, then boards of 5 rows separated by blank lines.
+pub fn parse(input: &str) -> Result<Bingo, ParseError> {
+    let mut lines = input.lines().enumerate();
+    let moves = match lines.next() {
+        Some((_, line)) if !line.trim().is_empty() => line
+            .split(',')
+            .map(|value| parse_field(line, value.trim(), "a move"))
+            .collect::<Result<_, _>>()?,
+        _ => vec![],
+    };
+
+    let mut boards = vec![];
+    let mut board = BingoBoard::build();
+    let mut end_board = |board: &mut BingoBoard, line: usize| {
+        match board.rows.len() {
+            0 => {}
+            5 => boards.push(mem::replace(board, BingoBoard::build())),
+            _ => return Err(ParseError::new("a board of 5 rows", "").on_line(line)),
+        }
+        Ok(())
+    };
+    let mut last = 1;
+    for (index, line) in lines {
+        last = index + 1;
+        if line.trim().is_empty() {
+            end_board(&mut board, last)?;
+        } else {
+            board.add_row(line).map_err(|e| e.on_line(last))?;
+        }
+    }
+    end_board(&mut board, last + 1)?;
+
+    Ok(Bingo { moves, boards })
+}
+
+pub fn part_1(input: &Bingo) -> i32 {
+    let _moves = &input.moves;
+    let _boards = &input.boards;
+    0
+}
+
+pub fn part_2(_input: &Bingo) -> i32 {
+    0
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Bingo;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,7 +245,7 @@ mod tests {
     //     );
     // }
     #[test]
-    fn build_boards_test() {
+    fn parse_test() {
         let input = r#"1,2,3
 
         83 11 47 61 45
@@ -173,44 +253,31 @@ mod tests {
         53 52 10 57 15
         64 50 54 28 87
         26 85 63 25 86"#;
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        let boards = build_boards(lines.iter());
+        let bingo = parse(input).unwrap();
 
-        assert_eq!(boards.len(), 1);
-        assert_eq!(boards[0].rows.len(), 5);
+        assert_eq!(bingo.moves, vec![1, 2, 3]);
+        assert_eq!(bingo.boards.len(), 1);
+        assert_eq!(bingo.boards[0].rows.len(), 5);
+        assert_eq!(bingo.boards[0].rows[2][1], BingoPosition::build(52, 1, 2));
     }
-}
-
-fn build_boards(mut input_iter: Iter<'_, String>) -> Vec<BingoBoard> {
-    let mut boards: Vec<BingoBoard> = vec![];
-
-    if let Some(_i) = input_iter.next() {
-        let mut board = BingoBoard::build();
 
-        board.add_row(input_iter.next().expect("Row not working"));
-        board.add_row(input_iter.next().expect("Row not working"));
-        board.add_row(input_iter.next().expect("Row not working"));
-        board.add_row(input_iter.next().expect("Row not working"));
-        board.add_row(input_iter.next().expect("Row not working"));
-
-        boards.push(board);
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse("1,x,3").unwrap_err(),
+            ParseError::new("a move", "x").at(1, 3)
+        );
+        assert_eq!(
+            parse("1\n\n1 2 3 4 5\n1 2 x 4 5").unwrap_err(),
+            ParseError::new("a number", "x").at(4, 5)
+        );
+        assert_eq!(
+            parse("1\n\n1 2 3 4 5\n1 2 3 4 5").unwrap_err(),
+            ParseError::new("a board of 5 rows", "").on_line(5)
+        );
+        assert_eq!(
+            parse("1\n\n1 2 3 4").unwrap_err(),
+            ParseError::new("a row of 5 numbers", "1 2 3 4").on_line(3)
+        );
     }
-    boards
-}
-
-pub fn part_1(input: &Vec<String>) -> i32 {
-    let mut input_iter = input.iter();
-    let _moves: Vec<i32> = input_iter
-        .next()
-        .expect("No moves")
-        .split(",")
-        .map(|s| s.parse::<i32>().expect("Can't parse move value"))
-        .collect();
-
-    let mut _boards = build_boards(input_iter);
-    0
-}
-
-pub fn part_2(_input: &Vec<String>) -> i32 {
-    0
 }
@@ -1,2 +1,11 @@
+use crate::solution::Entry;
+
 pub mod day3;
 pub mod day4;
+
+pub fn solutions() -> Vec<Entry> {
+    vec![
+        Entry::new::<day3::Solver>(2021, 3),
+        Entry::new::<day4::Solver>(2021, 4),
+    ]
+}
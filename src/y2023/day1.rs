@@ -1,131 +1,143 @@
-fn parse_line(line: &String) -> u32 {
-    let mut digits = line.chars().filter_map(|s| s.to_digit(10));
-    let first = digits.next().expect("Missing first digit");
-    let last = digits.last().unwrap_or(first);
+use crate::error::ParseError;
+use crate::solution::Solution;
+use crate::trace;
+use std::fmt::Display;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// One line of the calibration document, reduced to the digits each part reads.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CalibrationLine {
+    /// Only the numeric digits.
+    pub digits: Vec<u32>,
+    /// Numeric and spelled-out digits, overlapping words included.
+    pub spelled: Vec<u32>,
+}
 
-    first * 10 + last
+fn parse_line(line: &str) -> Vec<u32> {
+    line.chars().filter_map(|s| s.to_digit(10)).collect()
 }
 
-fn parse_line2(line: &String) -> u32 {
-    let mut digit_vec: Vec<u32> = vec![];
-    let chars: Vec<_> = line.chars().collect();
-
-    let mut i = 0;
-    while i < line.len() {
-        let char = chars[i];
-        match char {
-            '0'..='9' => {
-                digit_vec.push(char.to_digit(10).unwrap());
-                i += 1;
-                continue;
-            }
-            _ => {}
-        }
-        if i + 3 <= line.len() {
-            let slice = &line[i..i + 3];
-
-            match slice {
-                "one" => {
-                    digit_vec.push(1);
-                }
-                "two" => {
-                    digit_vec.push(2);
-                }
-                "six" => {
-                    digit_vec.push(6);
-                }
-                _ => {}
-            }
-        }
-        if i + 4 <= line.len() {
-            let slice = &line[i..i + 4];
-
-            match slice {
-                "four" => {
-                    digit_vec.push(4);
-                }
-                "five" => {
-                    digit_vec.push(5);
-                }
-                "nine" => {
-                    digit_vec.push(9);
-                }
-                _ => {}
-            }
-        }
-        if i + 5 <= line.len() {
-            let slice = &line[i..i + 5];
-
-            match slice {
-                "three" => {
-                    digit_vec.push(3);
-                }
-                "seven" => {
-                    digit_vec.push(7);
-                }
-                "eight" => {
-                    digit_vec.push(8);
-                }
-                _ => {}
-            }
-        }
+fn parse_line2(line: &str) -> Vec<u32> {
+    let digit_vec: Vec<u32> = line
+        .char_indices()
+        .filter_map(|(i, char)| {
+            char.to_digit(10).or_else(|| {
+                let word = WORDS.iter().position(|word| line[i..].starts_with(word))?;
+                Some(word as u32 + 1)
+            })
+        })
+        .collect();
+
+    trace!("Line {} becomes {:?}", line, digit_vec);
+    digit_vec
+}
 
-        i += 1
+/// The first and last digit as a two digit number, 0 for a line without digits.
+fn calibration_value(digits: &[u32]) -> u32 {
+    match (digits.first(), digits.last()) {
+        (Some(first), Some(last)) => first * 10 + last,
+        _ => 0,
     }
+}
 
-    let first = digit_vec[0];
-    let last = digit_vec[digit_vec.len() - 1];
+/// One calibration line per non-empty line, each holding at least one digit or
+/// spelled-out digit.
+pub fn parse(input: &str) -> Result<Vec<CalibrationLine>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let trimmed = line.trim();
+            let spelled = parse_line2(trimmed);
+            if spelled.is_empty() {
+                return Err(ParseError::new("a digit", trimmed)
+                    .on_line(index + 1)
+                    .within(line, trimmed));
+            }
+            Ok(CalibrationLine {
+                digits: parse_line(trimmed),
+                spelled,
+            })
+        })
+        .collect()
+}
 
-    let sum = first * 10 + last;
-    println!("Line {} becomes {:?} = sum {}", line, digit_vec, sum);
+pub fn part_1(input: &[CalibrationLine]) -> i32 {
+    let sum: u32 = input
+        .iter()
+        .map(|line| calibration_value(&line.digits))
+        .sum();
 
-    sum
+    sum as i32
 }
 
-pub fn part_1(input: &Vec<String>) -> i32 {
-    let sum: u32 = input.iter().map(|line| parse_line(line)).sum();
+pub fn part_2(input: &[CalibrationLine]) -> i32 {
+    let sum: u32 = input
+        .iter()
+        .map(|line| calibration_value(&line.spelled))
+        .sum();
 
     sum as i32
 }
 
-pub fn part_2(input: &Vec<String>) -> i32 {
-    let sum: u32 = input.iter().map(|line| parse_line2(line)).sum();
+pub struct Solver;
 
-    sum as i32
+impl Solution for Solver {
+    type Input = Vec<CalibrationLine>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = r#"1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet"#;
+        let input = parse(&example(2023, 1, 1)).unwrap();
 
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_1(&lines), 142)
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 1, 1, 1));
     }
 
     #[test]
     fn part_2_test() {
-        let input = r#"two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen"#;
-
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_2(&lines), 281)
+        let input = parse(&example(2023, 1, 2)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 1, 2, 2));
     }
 
     #[test]
     fn parse_line2_test() {
         let input = String::from("two1nine");
-        assert_eq!(parse_line2(&input), 29)
+        assert_eq!(calibration_value(&parse_line2(&input)), 29);
+        assert_eq!(parse_line2("eightwothree"), vec![8, 2, 3]);
+        assert_eq!(parse_line2("é7"), vec![7]);
+    }
+
+    #[test]
+    fn parse_test() {
+        let input = parse("a1b\neightwo").unwrap();
+
+        assert_eq!(part_1(&input), 11);
+        assert_eq!(part_2(&input), 11 + 82);
+        assert_eq!(
+            parse("a1b\n  xyz").unwrap_err(),
+            ParseError::new("a digit", "xyz").at(2, 3)
+        );
     }
 }
@@ -1,9 +1,10 @@
-use std::{
-    collections::{HashMap, HashSet},
-    str::FromStr,
-};
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::solution::Solution;
+use crate::{debug, trace};
+use std::{collections::HashSet, fmt::Display, str::FromStr};
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Pipe {
     NS,
     EW,
@@ -15,176 +16,302 @@ enum Pipe {
     S,
 }
 
-#[derive(Debug, Clone)]
-struct PipeNode {
-    x: i32,
-    y: i32,
-    pipe: Pipe,
-}
+impl Pipe {
+    const SHAPES: [Pipe; 6] = [Pipe::NS, Pipe::EW, Pipe::NE, Pipe::NW, Pipe::SW, Pipe::SE];
 
-impl PipeNode {
-    fn new(x: i32, y: i32, c: char) -> PipeNode {
-        let pipe = match c {
-            '|' => Pipe::NS,
-            '-' => Pipe::EW,
-            'L' => Pipe::NE,
-            'J' => Pipe::NW,
-            '7' => Pipe::SW,
-            'F' => Pipe::SE,
-            '.' => Pipe::X,
-            _ => Pipe::S,
-        };
+    fn from_char(c: char) -> Option<Pipe> {
+        match c {
+            '|' => Some(Pipe::NS),
+            '-' => Some(Pipe::EW),
+            'L' => Some(Pipe::NE),
+            'J' => Some(Pipe::NW),
+            '7' => Some(Pipe::SW),
+            'F' => Some(Pipe::SE),
+            '.' => Some(Pipe::X),
+            'S' => Some(Pipe::S),
+            _ => None,
+        }
+    }
 
-        PipeNode { x, y, pipe }
+    /// The offsets of the two tiles this pipe joins, none for the ground or `S`.
+    fn openings(&self) -> &'static [(isize, isize)] {
+        match self {
+            Pipe::NS => &[(0, -1), (0, 1)],
+            Pipe::EW => &[(-1, 0), (1, 0)],
+            Pipe::NE => &[(0, -1), (1, 0)],
+            Pipe::NW => &[(0, -1), (-1, 0)],
+            Pipe::SW => &[(0, 1), (-1, 0)],
+            Pipe::SE => &[(0, 1), (1, 0)],
+            Pipe::X | Pipe::S => &[],
+        }
     }
 }
 
+/// The pipe map with `S` replaced by the pipe hidden under it, and the tiles of the loop
+/// running through `S`.
 #[derive(Debug, Clone)]
-struct PipeGraph {
-    start: (i32, i32),
-    nodes: HashMap<(i32, i32), PipeNode>,
-    empty_count: i32,
+pub struct PipeGraph {
+    tiles: Grid<Pipe>,
+    on_loop: HashSet<(usize, usize)>,
 }
 
-impl PipeGraph {
-    fn next_to_start(&self) -> (i32, i32) {
-        // left
-        if let Some(node) = self.nodes.get(&(self.start.0 - 1, self.start.1)) {
-            if node.pipe == Pipe::EW || node.pipe == Pipe::NE || node.pipe == Pipe::SE {
-                return (self.start.0 - 1, self.start.1);
-            }
-        }
-        if let Some(node) = self.nodes.get(&(self.start.0 + 1, self.start.1)) {
-            if node.pipe == Pipe::EW || node.pipe == Pipe::NW || node.pipe == Pipe::SW {
-                return (self.start.0 + 1, self.start.1);
-            }
+/// Puts the pipe hidden under `start` in place and returns the loop through it. Any
+/// shape whose two openings both lead into a pipe opening back could be there, more
+/// than one when other pipes merely touch `start`, so the first whose loop closes is
+/// used. Otherwise returns where the first candidate's loop breaks, or `start` itself
+/// if no shape fits.
+fn infer_start_pipe(
+    tiles: &mut Grid<Pipe>,
+    start: (usize, usize),
+) -> Result<HashSet<(usize, usize)>, (usize, usize)> {
+    let mut broken = None;
+    for pipe in Pipe::SHAPES {
+        let fits = pipe.openings().iter().all(|&(dx, dy)| {
+            tiles
+                .step(start, (dx, dy))
+                .is_some_and(|next| tiles[next].openings().contains(&(-dx, -dy)))
+        });
+        if !fits {
+            continue;
         }
-        if let Some(node) = self.nodes.get(&(self.start.0, self.start.1 - 1)) {
-            if node.pipe == Pipe::NS || node.pipe == Pipe::NE || node.pipe == Pipe::NW {
-                return (self.start.0, self.start.1 - 1);
+
+        tiles[start] = pipe;
+        match trace_loop(tiles, start) {
+            Ok(on_loop) => return Ok(on_loop),
+            Err(at) => {
+                broken.get_or_insert(at);
             }
         }
-
-        (self.start.0, self.start.1 + 1)
     }
 
-    fn next_node(&self, node: &PipeNode, visited: &HashSet<(i32, i32)>) -> Option<(i32, i32)> {
-        let nodes = match node.pipe {
-            Pipe::NS => ((node.x, node.y - 1), (node.x, node.y + 1)),
-            Pipe::EW => ((node.x - 1, node.y), (node.x + 1, node.y)),
-            Pipe::NE => ((node.x, node.y - 1), (node.x + 1, node.y)),
-            Pipe::NW => ((node.x, node.y - 1), (node.x - 1, node.y)),
-            Pipe::SW => ((node.x, node.y + 1), (node.x - 1, node.y)),
-            Pipe::SE => ((node.x, node.y + 1), (node.x + 1, node.y)),
-            Pipe::X => return None,
-            Pipe::S => return Some(self.next_to_start()),
-        };
+    Err(broken.unwrap_or(start))
+}
 
-        if visited.contains(&nodes.0) {
-            if visited.contains(&nodes.1) {
-                return Some(self.start);
-            }
-            Some(nodes.1)
-        } else {
-            Some(nodes.0)
+/// An error at tile `(x, y)`, placed on the line and column of `s` it was read from.
+fn tile_error(s: &str, (x, y): (usize, usize), expected: &'static str) -> ParseError {
+    let row = s
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .nth(y);
+    let Some((index, line)) = row else {
+        return ParseError::new(expected, "");
+    };
+    let trimmed = line.trim_start();
+    let indent = line[..line.len() - trimmed.len()].chars().count();
+    let tile = trimmed.chars().nth(x).unwrap_or(' ');
+
+    ParseError::new(expected, &tile.to_string()).at(index + 1, indent + x + 1)
+}
+
+/// Follows the pipes from `start` round to it again, returning every tile on the way,
+/// or the first tile that doesn't lead on.
+fn trace_loop(
+    tiles: &Grid<Pipe>,
+    start: (usize, usize),
+) -> Result<HashSet<(usize, usize)>, (usize, usize)> {
+    let mut on_loop = HashSet::from([start]);
+    let mut previous = start;
+    let mut current = tiles.step(start, tiles[start].openings()[0]).ok_or(start)?;
+
+    while current != start {
+        on_loop.insert(current);
+        let exits: Vec<Option<(usize, usize)>> = tiles[current]
+            .openings()
+            .iter()
+            .map(|delta| tiles.step(current, *delta))
+            .collect();
+        if !exits.contains(&Some(previous)) {
+            return Err(current);
         }
+        let next = exits
+            .into_iter()
+            .flatten()
+            .find(|next| *next != previous)
+            .ok_or(current)?;
+
+        trace!("Visited {:?}, next {:?}", current, next);
+        (previous, current) = (current, next);
     }
-}
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParsePipeGraphError;
+    // The loop has to come back in through the other opening of `start`.
+    let closes = tiles[start]
+        .openings()
+        .iter()
+        .any(|delta| tiles.step(start, *delta) == Some(previous));
+    if !closes {
+        return Err(previous);
+    }
+
+    Ok(on_loop)
+}
 
 impl FromStr for PipeGraph {
-    type Err = ParsePipeGraphError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut nodes: HashMap<(i32, i32), PipeNode> = HashMap::new();
-        let mut start: (i32, i32) = (0, 0);
-        let mut empty_count = 0;
-
-        for (y, line) in s.lines().enumerate() {
-            for (x, char) in line.trim().chars().enumerate() {
-                let node = PipeNode::new(x as i32, y as i32, char);
-                if node.pipe == Pipe::S {
-                    start = (x as i32, y as i32);
-                }
-                if node.pipe == Pipe::X {
-                    empty_count += 1;
-                }
-                nodes.insert((node.x, node.y), node);
+        let mut tiles = Grid::parse(s, Pipe::from_char)?;
+
+        let Some(start) = tiles.find(|pipe| *pipe == Pipe::S) else {
+            return Err(ParseError::new(
+                "a starting tile 'S'",
+                s.lines().next().unwrap_or(""),
+            ));
+        };
+
+        let on_loop = infer_start_pipe(&mut tiles, start).map_err(|at| {
+            if at == start {
+                tile_error(s, at, "a starting tile joining two pipes")
+            } else {
+                tile_error(s, at, "a pipe continuing the loop")
             }
-        }
+        })?;
 
-        Ok(PipeGraph {
-            start,
-            nodes,
-            empty_count,
-        })
+        Ok(PipeGraph { tiles, on_loop })
     }
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let graph = PipeGraph::from_str(input).expect("Error parsing Graph");
-    let mut visited: HashSet<(i32, i32)> = HashSet::new();
-    let mut current = graph
-        .nodes
-        .get(&graph.start)
-        .expect("Missing starting node")
-        .clone();
-
-    // println!("Graph {:?}", graph);
-
-    loop {
-        visited.insert((current.x, current.y));
-        let next = graph
-            .next_node(&current, &visited)
-            .expect("Should have a next node");
-
-        // println!("Visited {:?}, next {:?}", visited, next);
-        current = graph.nodes.get(&next).expect("Missing next node").clone();
-        if current.pipe == Pipe::S {
-            break;
-        }
-    }
+pub fn part_1(graph: &PipeGraph) -> i32 {
+    trace!("Graph {:?}", graph);
 
-    println!(
+    let empty_count = graph
+        .tiles
+        .iter()
+        .filter(|(_, pipe)| **pipe == Pipe::X)
+        .count();
+    debug!(
         "Visited {}, empty_count {}, total nodes {}, visited + empty_count {}",
-        visited.len(),
-        graph.empty_count,
-        graph.nodes.len(),
-        visited.len() as i32 + graph.empty_count,
+        graph.on_loop.len(),
+        empty_count,
+        graph.tiles.width() * graph.tiles.height(),
+        graph.on_loop.len() + empty_count,
     );
 
-    visited.len() as i32 / 2
+    graph.on_loop.len() as i32 / 2
+}
+
+/// Scans each row keeping track of whether we're inside the loop. Only loop pipes that
+/// reach north flip the side, so `F--J` crosses the loop while `F--7` runs along it and
+/// tiles squeezed between two pipes are still seen as outside.
+pub fn part_2(graph: &PipeGraph) -> i32 {
+    let mut enclosed = 0;
+
+    for y in 0..graph.tiles.height() {
+        let mut inside = false;
+        for x in 0..graph.tiles.width() {
+            if graph.on_loop.contains(&(x, y)) {
+                if graph.tiles[(x, y)].openings().contains(&(0, -1)) {
+                    inside = !inside;
+                }
+            } else if inside {
+                enclosed += 1;
+            }
+        }
+    }
+
+    enclosed
 }
 
-pub fn part_2(_input: &str) -> i32 {
-    0
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = PipeGraph;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = ".....
-        .S-7.
-        .|.|.
-        .L-J.
-        .....";
+        let input: PipeGraph = example(2023, 10, 1).parse().unwrap();
 
-        assert_eq!(part_1(input), 4);
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 10, 1, 1));
     }
 
     #[test]
     fn part_1_test_2() {
-        let input = "..F7.
-        .FJ|.
-        SJ.L7
-        |F--J
-        LJ...";
+        let input: PipeGraph = example(2023, 10, 2).parse().unwrap();
+
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 10, 2, 1));
+    }
+
+    #[test]
+    fn start_pipe_test() {
+        let graph: PipeGraph = "..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...".parse().unwrap();
+        assert_eq!(graph.tiles[(0, 2)], Pipe::SE);
+
+        let graph: PipeGraph = ".....\n.S-7.\n.|.|.\n.L-J.".parse().unwrap();
+        assert_eq!(graph.tiles[(1, 1)], Pipe::SE);
+
+        // Every neighbour opens towards S, but only going down and right closes a loop.
+        let graph: PipeGraph = ".|...\n-S-7.\n.|.|.\n.L-J.".parse().unwrap();
+        assert_eq!(graph.tiles[(1, 1)], Pipe::SE);
+        assert_eq!(part_1(&graph), 4);
+    }
 
-        assert_eq!(part_1(input), 8);
+    #[test]
+    fn part_2_test() {
+        let input: PipeGraph = example(2023, 10, 3).parse().unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 10, 3, 2));
+    }
+
+    #[test]
+    fn part_2_squeeze_test() {
+        let input: PipeGraph = example(2023, 10, 4).parse().unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 10, 4, 2));
+    }
+
+    #[test]
+    fn part_2_larger_test() {
+        let input: PipeGraph = example(2023, 10, 5).parse().unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 10, 5, 2));
+    }
+
+    #[test]
+    fn part_2_junk_test() {
+        let input: PipeGraph = example(2023, 10, 6).parse().unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 10, 6, 2));
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            ".....\n.S-x.".parse::<PipeGraph>().unwrap_err(),
+            ParseError::new("a grid cell", "x").at(2, 4)
+        );
+        assert_eq!(
+            ".-7\n.|.".parse::<PipeGraph>().unwrap_err(),
+            ParseError::new("a starting tile 'S'", ".-7")
+        );
+        assert_eq!(
+            ".|.\n.S.\n...".parse::<PipeGraph>().unwrap_err(),
+            ParseError::new("a starting tile joining two pipes", "S").at(2, 2)
+        );
+        assert_eq!(
+            ".S-7\n.|.|\n.L-.".parse::<PipeGraph>().unwrap_err(),
+            ParseError::new("a pipe continuing the loop", ".").at(3, 4)
+        );
+        assert_eq!(
+            "\n  .S-7\n  .|.|\n  .L-.".parse::<PipeGraph>().unwrap_err(),
+            ParseError::new("a pipe continuing the loop", ".").at(4, 6)
+        );
     }
 }
@@ -1,4 +1,8 @@
-use std::collections::HashSet;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::solution::Solution;
+use crate::trace;
+use std::fmt::Display;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 struct Star {
@@ -7,76 +11,81 @@ struct Star {
 }
 
 impl Star {
-    fn expand(&self, x_factors: &[u32], y_factors: &[u32], factor: u32) -> Star {
+    /// Moves the star `added` further for every empty row or column before it.
+    fn expand(&self, x_factors: &[u32], y_factors: &[u32], added: u32) -> Star {
         Star {
-            x: self.x + ((factor - 1) * x_factors[self.x]) as usize,
-            y: self.y + ((factor - 1) * y_factors[self.y]) as usize,
+            x: self.x + (added * x_factors[self.x]) as usize,
+            y: self.y + (added * y_factors[self.y]) as usize,
         }
     }
 
     fn distance(&self, star: &Star) -> usize {
-        let mut distance: usize = if self.x > star.x {
-            self.x - star.x
-        } else {
-            star.x - self.x
-        };
-        distance += if self.y > star.y {
-            self.y - star.y
-        } else {
-            star.y - self.y
-        };
+        let mut distance: usize = self.x.abs_diff(star.x);
+        distance += self.y.abs_diff(star.y);
 
         distance
     }
 }
 
-pub fn part_1(input: &str) -> usize {
-    parse_and_expand(input, 2)
+/// The galaxies in the image, and for each row and column how many empty ones come
+/// before it, itself included.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Image {
+    stars: Vec<Star>,
+    x_factors: Vec<u32>,
+    y_factors: Vec<u32>,
 }
 
-pub fn parse_and_expand(input: &str, expansion_factor: u32) -> usize {
-    let mut stars: Vec<Star> = vec![];
-    let mut x_stars: HashSet<usize> = HashSet::new();
-    let mut y_stars: HashSet<usize> = HashSet::new();
-
-    for (y, line) in input.lines().enumerate() {
-        for (x, char) in line.trim().chars().enumerate() {
-            if char == '#' {
-                stars.push(Star { x, y });
-                x_stars.insert(x);
-                y_stars.insert(y);
-            }
-        }
-    }
+pub fn parse(input: &str) -> Result<Image, ParseError> {
+    let image = Grid::parse(input, |c| matches!(c, '.' | '#').then_some(c))?;
+    let stars: Vec<Star> = image
+        .iter()
+        .filter(|(_, c)| **c == '#')
+        .map(|((x, y), _)| Star { x, y })
+        .collect();
 
-    let mut x_expansion: Vec<u32> = vec![];
-    let mut y_expansion: Vec<u32> = vec![];
+    let mut x_factors: Vec<u32> = vec![];
+    let mut y_factors: Vec<u32> = vec![];
     let mut factor = 0;
-    for (y, _) in input.lines().enumerate() {
-        if !y_stars.contains(&y) {
+    for row in image.rows() {
+        if !row.contains(&'#') {
             factor += 1
         }
-        y_expansion.push(factor);
+        y_factors.push(factor);
     }
-    // println!("y factors {:?}", y_expansion);
+    trace!("y factors {:?}", y_factors);
 
     factor = 0;
-    for (x, _) in input.lines().next().unwrap().chars().enumerate() {
-        if !x_stars.contains(&x) {
+    for mut column in image.columns() {
+        if !column.any(|c| *c == '#') {
             factor += 1
         }
-        x_expansion.push(factor);
+        x_factors.push(factor);
     }
-    // println!("x factors {:?}", x_expansion);
+    trace!("x factors {:?}", x_factors);
+
+    Ok(Image {
+        stars,
+        x_factors,
+        y_factors,
+    })
+}
 
-    let expanded_stars: Vec<_> = stars
+pub fn part_1(image: &Image) -> usize {
+    expanded_distances(image, 2)
+}
+
+/// The sum of the distances between every pair of galaxies once each empty row and
+/// column is `expansion_factor` times as wide.
+pub fn expanded_distances(image: &Image, expansion_factor: u32) -> usize {
+    let expanded_stars: Vec<_> = image
+        .stars
         .iter()
-        .map(|star| star.expand(&x_expansion, &y_expansion, expansion_factor))
+        .map(|star| star.expand(&image.x_factors, &image.y_factors, expansion_factor - 1))
         .collect();
 
     let mut count = 0;
-    for n in 0..expanded_stars.len() - 1 {
-        let star = expanded_stars[n];
+    for (n, star) in expanded_stars.iter().enumerate() {
         for star2 in expanded_stars.iter().skip(n + 1) {
             count += star.distance(star2);
         }
@@ -85,28 +94,38 @@ pub fn parse_and_expand(input: &str, expansion_factor: u32) -> usize {
     count
 }
 
-pub fn part_2(input: &str) -> usize {
-    parse_and_expand(input, 1000000)
+pub fn part_2(image: &Image) -> usize {
+    expanded_distances(image, 1000000)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Image;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = "...#......
-        .......#..
-        #.........
-        ..........
-        ......#...
-        .#........
-        .........#
-        ..........
-        .......#..
-        #...#.....";
-
-        assert_eq!(part_1(input), 374);
+        let input = parse(&example(2023, 11, 1)).unwrap();
+
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 11, 1, 1));
     }
 
     #[test]
@@ -150,18 +169,17 @@ mod tests {
 
     #[test]
     fn part_2_test() {
-        let input = "...#......
-        .......#..
-        #.........
-        ..........
-        ......#...
-        .#........
-        .........#
-        ..........
-        .......#..
-        #...#.....";
-
-        assert_eq!(parse_and_expand(input, 10), 1030);
-        assert_eq!(parse_and_expand(input, 100), 8410);
+        let image = parse(&example(2023, 11, 1)).unwrap();
+
+        assert_eq!(expanded_distances(&image, 10), 1030);
+        assert_eq!(expanded_distances(&image, 100), 8410);
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse("...#\n.x..").unwrap_err(),
+            ParseError::new("a grid cell", "x").at(2, 2)
+        );
     }
 }
@@ -1,3 +1,6 @@
+use crate::error::{parse_field, ParseError};
+use crate::solution::Solution;
+use crate::{debug, trace};
 use core::fmt;
 use std::collections::HashMap;
 use std::sync::mpsc::channel;
@@ -50,21 +53,24 @@ impl SpringValidation {
     }
 }
 
+type DpMemo<'a> = HashMap<(&'a [Part], &'a [u32]), (u64, usize)>;
+
 struct PartCounts {
     good: usize,
     damaged: usize,
     unknown: usize,
 }
 
-#[derive(Debug, PartialEq)]
-struct SpringGroup {
+#[derive(Debug, PartialEq, Clone)]
+pub struct SpringGroup {
     springs: Vec<Part>,
     segments: Vec<u32>,
 }
 
 impl SpringGroup {
-    fn from_str2(s: &str) -> SpringGroup {
-        let base = SpringGroup::from_str(s).expect("Expected parsing");
+    /// The group five times over, joined by unknown springs.
+    fn unfold(&self) -> SpringGroup {
+        let base = self;
         let mut springs: Vec<Part> = vec![];
         let mut segments: Vec<u32> = vec![];
         for i in 0..5 {
@@ -78,8 +84,9 @@ impl SpringGroup {
         SpringGroup { springs, segments }
     }
 
-    fn from_str2_v2(s: &str) -> SpringGroup {
-        let spring_group = Self::from_str2(s);
+    /// The same group with runs of good springs collapsed into one.
+    fn compact(self) -> SpringGroup {
+        let spring_group = self;
         let mut springs: Vec<Part> = vec![];
         let mut last = Part::Unknown;
 
@@ -124,7 +131,6 @@ impl SpringGroup {
                 }
             }
             if validation.done {
-                // println!("Possibilitiy: {:?}", possibility);
                 possibilities += 1;
             }
         }
@@ -232,11 +238,11 @@ impl SpringGroup {
     }
 
     fn dp_possibilities(&self) -> u64 {
-        let mut memo: HashMap<(&[Part], &[u32]), (u64, usize)> = HashMap::new();
+        let mut memo: DpMemo = HashMap::new();
         fn helper<'a>(
             springs: &'a [Part],
             segments: &'a [u32],
-            memo: &mut HashMap<(&'a [Part], &'a [u32]), (u64, usize)>,
+            memo: &mut DpMemo<'a>,
         ) -> (u64, usize) {
             let sum = match memo.get(&(springs, segments)) {
                 Some(value) => value,
@@ -313,98 +319,111 @@ impl SpringGroup {
 
         let result = helper(&self.springs, &self.segments, &mut memo).0;
         if result == 0 {
-            println!("Probably a problem with {:?}", self);
+            debug!("Probably a problem with {:?}", self);
         }
         result
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct ParseSpringGroupError;
-
 impl FromStr for SpringGroup {
-    type Err = ParseSpringGroupError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut iter = s.trim().split(' ');
-        let springs: Vec<Part> = iter
-            .next()
-            .unwrap()
+        let s = s.trim();
+        let Some((springs, segments)) = s.split_once(' ') else {
+            return Err(ParseError::new("springs and group sizes", s));
+        };
+        let springs = springs
             .chars()
-            .map(|char| match char {
-                '.' => Part::Good,
-                '#' => Part::Damaged,
-                _ => Part::Unknown,
+            .enumerate()
+            .map(|(column, char)| match char {
+                '.' => Ok(Part::Good),
+                '#' => Ok(Part::Damaged),
+                '?' => Ok(Part::Unknown),
+                _ => Err(ParseError::new("a spring", &char.to_string()).at(1, column + 1)),
             })
-            .collect();
-        let segments: Vec<u32> = iter
-            .next()
-            .unwrap()
+            .collect::<Result<_, _>>()?;
+        let segments = segments
             .split(',')
-            .map(|piece| piece.parse::<u32>().expect("NaN"))
-            .collect();
+            .map(|piece| parse_field(s, piece, "a group size"))
+            .collect::<Result<_, _>>()?;
 
         Ok(SpringGroup { springs, segments })
     }
 }
 
-pub fn part_1(input: &str) -> usize {
+pub fn parse(input: &str) -> Result<Vec<SpringGroup>, ParseError> {
     input
         .lines()
-        .map(|line| {
-            SpringGroup::from_str(line)
-                .expect("Issue parsing SpringGroup")
-                .get_possibilities()
-        })
-        .sum()
+        .enumerate()
+        .map(|(index, line)| SpringGroup::from_str(line).map_err(|e| e.on_line(index + 1)))
+        .collect()
 }
 
-pub fn part_2(input: &str) -> u64 {
-    // let now = Instant::now();
-    let lines: Vec<_> = input.lines().collect();
-    let n_jobs = lines.len();
+pub fn part_1(groups: &[SpringGroup]) -> usize {
+    groups.iter().map(|group| group.get_possibilities()).sum()
+}
+
+pub fn part_2(groups: &[SpringGroup]) -> u64 {
+    let n_jobs = groups.len();
     let n_workers = 32;
     let pool = Pool::<ThunkWorker<u64>>::new(n_workers);
 
     let (tx, rx) = channel();
-    for line in input.lines() {
-        let string = String::from(line);
+    for group in groups {
+        let group = group.clone();
         pool.execute_to(
             tx.clone(),
-            Thunk::of(move || SpringGroup::from_str2_v2(&string).dp_possibilities()),
+            Thunk::of(move || group.unfold().compact().dp_possibilities()),
         );
     }
 
-    // let mut job_count = 0;
     let mut sum = 0;
-    for result in rx.iter().take(n_jobs) {
-        // job_count += 1;
-        // println!(
-        //     "Elapsed time: {:?}, count: {} of {}, sum {}",
-        //     now.elapsed(),
-        //     job_count,
-        //     n_jobs,
-        //     sum
-        // );
+    for (count, result) in rx.iter().take(n_jobs).enumerate() {
         sum += result;
+        trace!("{} of {} groups done, sum {}", count + 1, n_jobs, sum);
     }
     sum
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<SpringGroup>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
+
+    impl SpringGroup {
+        fn from_str2(s: &str) -> SpringGroup {
+            SpringGroup::from_str(s).unwrap().unfold()
+        }
+
+        fn from_str2_v2(s: &str) -> SpringGroup {
+            SpringGroup::from_str2(s).compact()
+        }
+    }
 
     #[test]
     fn part_1_test() {
-        let input = "???.### 1,1,3
-        .??..??...?##. 1,1,3
-        ?#?#?#?#?#?#?#? 1,3,1,6
-        ????.#...#... 4,1,1
-        ????.######..#####. 1,6,5
-        ?###???????? 3,2,1";
-
-        assert_eq!(part_1(input), 21);
+        let input = parse(&example(2023, 12, 1)).unwrap();
+
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 12, 1, 1));
     }
 
     #[test]
@@ -578,16 +597,25 @@ mod tests {
 
     #[test]
     fn part_2_test() {
-        let input = String::from(
-            "???.### 1,1,3
-        .??..??...?##. 1,1,3
-        ?#?#?#?#?#?#?#? 1,3,1,6
-        ????.#...#... 4,1,1
-        ????.######..#####. 1,6,5
-        ?###???????? 3,2,1",
-        );
+        let input = parse(&example(2023, 12, 1)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 12, 1, 2));
+    }
 
-        assert_eq!(part_2(&input), 525152);
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse("???.### 1,1,3\n.??x ?").unwrap_err(),
+            ParseError::new("a spring", "x").at(2, 4)
+        );
+        assert_eq!(
+            parse("???.### 1,x,3").unwrap_err(),
+            ParseError::new("a group size", "x").at(1, 11)
+        );
+        assert_eq!(
+            parse("???.###").unwrap_err(),
+            ParseError::new("springs and group sizes", "???.###")
+        );
     }
 
     #[test]
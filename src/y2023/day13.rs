@@ -1,3 +1,7 @@
+use crate::error::ParseError;
+use crate::solution::Solution;
+use crate::{debug, trace};
+use std::fmt::Display;
 use std::str::FromStr;
 
 fn bit_count(a: u32) -> u32 {
@@ -34,7 +38,7 @@ fn flip_bit(a: u32, index: usize) -> u32 {
 }
 
 #[derive(Debug, PartialEq)]
-struct MirrorField {
+pub struct MirrorField {
     rows: Vec<u32>,
     cols: Vec<u32>,
     string: String,
@@ -49,7 +53,7 @@ impl MirrorField {
         Self::reflection(&self.cols, None)
     }
 
-    fn is_reflection(vec: &Vec<u32>, n: usize) -> bool {
+    fn is_reflection(vec: &[u32], n: usize) -> bool {
         let mut n = n;
         let mut m = n + 1;
 
@@ -67,7 +71,7 @@ impl MirrorField {
         true
     }
 
-    fn find_single_bit_difference(vec: &Vec<u32>, n: usize) -> Vec<(usize, usize)> {
+    fn find_single_bit_difference(vec: &[u32], n: usize) -> Vec<(usize, usize)> {
         let mut n = n;
         let mut m = n + 1;
         let mut out = vec![];
@@ -86,7 +90,7 @@ impl MirrorField {
         out
     }
 
-    fn find_all_differences(vec: &Vec<u32>) -> Vec<(usize, usize)> {
+    fn find_all_differences(vec: &[u32]) -> Vec<(usize, usize)> {
         let mut result = vec![];
         for n in 0..vec.len() - 1 {
             result.append(&mut Self::find_single_bit_difference(vec, n))
@@ -94,10 +98,10 @@ impl MirrorField {
         result
     }
 
-    fn reflection(vec: &Vec<u32>, skip: Option<usize>) -> Option<usize> {
+    fn reflection(vec: &[u32], skip: Option<usize>) -> Option<usize> {
         let mut result = None;
-        let mut m = vec.len() / 2 + 1;
-        for n in (0..=(vec.len() / 2)).rev() {
+        for (i, n) in (0..=(vec.len() / 2)).rev().enumerate() {
+            let m = vec.len() / 2 + 1 + i;
             if Self::is_reflection(vec, n) && skip != Some(n + 1) {
                 result = Some(n);
                 break;
@@ -106,7 +110,6 @@ impl MirrorField {
                 result = Some(m);
                 break;
             }
-            m += 1;
         }
 
         result.map(|result| result + 1)
@@ -116,7 +119,7 @@ impl MirrorField {
         self.row_reflection().unwrap_or(0) * 100 + self.col_reflection().unwrap_or(0)
     }
 
-    fn score_2(&self, rows: &Vec<u32>, cols: &Vec<u32>) -> usize {
+    fn score_2(&self, rows: &[u32], cols: &[u32]) -> usize {
         let old_row = Self::row_reflection(self);
         let new_row = Self::reflection(rows, old_row);
         if old_row != new_row {
@@ -131,7 +134,7 @@ impl MirrorField {
 
     fn smudge_score(&self) -> usize {
         let original_score = Self::score(self);
-        // println!("Self {:?}", self);
+        trace!("Self {:?}", self);
         let row_possibilities = Self::find_all_differences(&self.rows);
         for pos in row_possibilities {
             let mut new_row = self.rows.clone();
@@ -139,21 +142,24 @@ impl MirrorField {
             let column_bit_index = bit_difference_index(new_row[pos.0], new_row[pos.1]);
             let column = new_col.len() - column_bit_index as usize - 1;
 
-            // println!("Posibilities in row {} and {}", pos.0, pos.1);
-            // println!(
-            //     "Should update column {} from bit index {}",
-            //     column, column_bit_index
-            // );
+            trace!("Posibilities in row {} and {}", pos.0, pos.1);
+            trace!(
+                "Should update column {} from bit index {}",
+                column,
+                column_bit_index
+            );
             new_row[pos.0] = flip_bit(new_row[pos.0], column_bit_index as usize);
-            // println!(
-            //     "Row before {:b}, after {:b}",
-            //     self.rows[pos.0], new_row[pos.0]
-            // );
+            trace!(
+                "Row before {:b}, after {:b}",
+                self.rows[pos.0],
+                new_row[pos.0]
+            );
             new_col[column] = flip_bit(new_col[column], new_row.len() - 1 - column);
-            // println!(
-            //     "Col before {:b}, after {:b}",
-            //     self.cols[column], new_col[column]
-            // );
+            trace!(
+                "Col before {:b}, after {:b}",
+                self.cols[column],
+                new_col[column]
+            );
             let new_score = Self::score_2(self, &new_row, &new_col);
             if new_score > 0 && new_score != original_score {
                 return new_score;
@@ -196,36 +202,48 @@ impl MirrorField {
             }
         }
 
-        println!("Problem with:\n{}", self.string);
+        debug!("Problem with:\n{}", self.string);
         0
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct ParseMirrorFieldError;
-
 impl FromStr for MirrorField {
-    type Err = ParseMirrorFieldError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut chars: Vec<Vec<char>> = vec![];
         let mut rows = vec![];
         let mut cols = vec![];
 
-        for row in s.lines() {
+        for (index, row) in s.lines().enumerate() {
             let mut number: u32 = 0b0;
             let char_row: Vec<_> = row.trim().chars().collect();
 
-            for char in char_row.iter() {
+            for (column, char) in char_row.iter().enumerate() {
                 let bit: u32 = match char {
                     '.' => 0b0,
-                    _ => 0b1,
+                    '#' => 0b1,
+                    _ => {
+                        return Err(ParseError::new("'.' or '#'", &char.to_string())
+                            .at(index + 1, column + 1))
+                    }
                 };
                 number = (number << 1) + bit;
             }
+            if chars
+                .first()
+                .is_some_and(|first| first.len() != char_row.len())
+            {
+                return Err(
+                    ParseError::new("a row as wide as the first", row.trim()).at(index + 1, 1)
+                );
+            }
             chars.push(char_row);
             rows.push(number);
         }
+        if chars.is_empty() {
+            return Err(ParseError::new("a pattern", s));
+        }
 
         for col in 0..chars[0].len() {
             let mut number: u32 = 0b0;
@@ -249,47 +267,59 @@ impl FromStr for MirrorField {
     }
 }
 
-pub fn part_1(input: &str) -> usize {
+/// Every blank-line separated pattern, with errors placed on the line of the input
+/// they were found on.
+pub fn parse(input: &str) -> Result<Vec<MirrorField>, ParseError> {
     let mut mirror_fields = vec![];
+    let mut line = 0;
     for section in input.split("\n\n") {
-        mirror_fields.push(MirrorField::from_str(section).unwrap());
+        let field = MirrorField::from_str(section).map_err(|e| {
+            let number = line + e.line;
+            e.on_line(number)
+        })?;
+        mirror_fields.push(field);
+        line += section.lines().count() + 1;
     }
 
+    Ok(mirror_fields)
+}
+
+pub fn part_1(mirror_fields: &[MirrorField]) -> usize {
     mirror_fields.iter().map(|mf| mf.score()).sum()
 }
 
-pub fn part_2(input: &str) -> usize {
-    let mut mirror_fields = vec![];
-    for section in input.split("\n\n") {
-        mirror_fields.push(MirrorField::from_str(section).unwrap());
+pub fn part_2(mirror_fields: &[MirrorField]) -> usize {
+    mirror_fields.iter().map(|mf| mf.smudge_score()).sum()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<MirrorField>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
     }
 
-    mirror_fields.iter().map(|mf| mf.smudge_score()).sum()
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = "#.##..##.
-        ..#.##.#.
-        ##......#
-        ##......#
-        ..#.##.#.
-        ..##..##.
-        #.#.##.#.
-
-        #...##..#
-        #....#..#
-        ..##..###
-        #####.##.
-        #####.##.
-        ..##..###
-        #....#..#";
-
-        assert_eq!(part_1(input), 405);
+        let input = parse(&example(2023, 13, 1)).unwrap();
+
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 13, 1, 1));
     }
 
     #[test]
@@ -364,37 +394,19 @@ mod tests {
 
     #[test]
     fn part_2_test() {
-        assert_eq!(
-            part_2(
-                "#.##..##.
-                ..#.##.#.
-                ##......#
-                ##......#
-                ..#.##.#.
-                ..##..##.
-                #.#.##.#."
-            ),
-            300
-        );
-        assert_eq!(
-            part_2(
-                "#...##..#
-                #....#..#
-                ..##..###
-                #####.##.
-                #####.##.
-                ..##..###
-                #....#..#"
-            ),
-            100
-        );
+        let input = parse(&example(2023, 13, 1)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 13, 1, 2));
+        assert_eq!(part_2(&input[..1]), 300);
+        assert_eq!(part_2(&input[1..]), 100);
     }
 
     #[test]
     fn part_2_input_test() {
         assert_ne!(
             part_2(
-                "##.#.#####.....
+                &parse(
+                    "##.#.#####.....
         ##...##.###.###
         ##...##.###.###
         ##.#.#####.....
@@ -405,48 +417,60 @@ mod tests {
         #...#...###....
         ..#.....#.##...
         ....#......#.##"
+                )
+                .unwrap()
             ),
             0
         );
         assert_ne!(
             part_2(
-                "#.###..
+                &parse(
+                    "#.###..
         #.###.#
         ##..##.
         ......#
         ..##...
         #....#.
         #....#."
+                )
+                .unwrap()
             ),
             0
         );
-                assert_ne!(
-                    part_2(
-                        "#.####.
+        assert_ne!(
+            part_2(
+                &parse(
+                    "#.####.
         ####..#
         #####..
         ....##.
         #..###.
         ####..#
         ####..#"
-                    ),
-                    0
-                );
-                assert_ne!(
-                    part_2(
-                        ".........##..##..
+                )
+                .unwrap()
+            ),
+            0
+        );
+        assert_ne!(
+            part_2(
+                &parse(
+                    ".........##..##..
         ........#..##..#.
         .######..######..
         ..#..#..##.##.##.
         .###.##..#.##.#..
         ###..####.####.##
         ##....##.#....#.#"
-                    ),
-                    0
-                );
+                )
+                .unwrap()
+            ),
+            0
+        );
         assert_ne!(
             part_2(
-                "####.#.......
+                &parse(
+                    "####.#.......
 #.#.###.####.
 .#..##.####..
 .....#.#....#
@@ -461,6 +485,8 @@ mod tests {
 #...#......#.
 ##.#..####.##
 .#.#..####.##"
+                )
+                .unwrap()
             ),
             0
         );
@@ -1,7 +1,11 @@
+use crate::cycle;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::solution::Solution;
+use crate::trace;
 use core::fmt;
 use std::{
     cmp::Ordering,
-    collections::HashMap,
     fmt::{Display, Formatter},
     str::FromStr,
 };
@@ -29,7 +33,7 @@ impl Display for Boulder {
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
-struct Dish {
+pub struct Dish {
     boulders: Vec<Boulder>,
     height: usize,
     width: usize,
@@ -93,28 +97,28 @@ impl Dish {
     fn tilt_up(&self) -> Dish {
         let columns = self.sort_by_column();
 
-        // println!("Tilt up");
+        trace!("Tilt up");
         self.tilt_high(columns, true)
     }
 
     fn tilt_down(&self) -> Dish {
         let columns = self.sort_by_column();
 
-        // println!("Tilt down");
+        trace!("Tilt down");
         self.tilt_low(columns, true)
     }
 
     fn tilt_left(&self) -> Dish {
         let rows = self.sort_by_row();
 
-        // println!("Tilt left");
+        trace!("Tilt left");
         self.tilt_high(rows, false)
     }
 
     fn tilt_right(&self) -> Dish {
         let rows = self.sort_by_row();
 
-        // println!("Tilt right");
+        trace!("Tilt right");
         self.tilt_low(rows, false)
     }
 
@@ -187,123 +191,87 @@ impl Dish {
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct ParseDishError;
-
 impl FromStr for Dish {
-    type Err = ParseDishError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut boulders: Vec<Boulder> = vec![];
-        let mut height = 0;
-        let mut width = 0;
-
-        for row in s.lines().enumerate() {
-            height += 1;
-            width = row.1.trim().len();
-
-            for col in row.1.trim().chars().enumerate() {
-                let boulder = match col.1 {
-                    'O' => Some(Boulder {
-                        shape: BoulderShape::Round,
-                        row: row.0,
-                        col: col.0,
-                    }),
-                    '#' => Some(Boulder {
-                        shape: BoulderShape::Square,
-                        row: row.0,
-                        col: col.0,
-                    }),
-                    _ => None,
+        let grid = Grid::parse(s, |c| matches!(c, 'O' | '#' | '.').then_some(c))?;
+        let boulders = grid
+            .iter()
+            .filter_map(|((col, row), c)| {
+                let shape = match c {
+                    'O' => BoulderShape::Round,
+                    '#' => BoulderShape::Square,
+                    _ => return None,
                 };
-
-                if let Some(boulder) = boulder {
-                    boulders.push(boulder);
-                }
-            }
-        }
+                Some(Boulder { shape, row, col })
+            })
+            .collect();
 
         Ok(Dish {
             boulders,
-            height,
-            width,
+            height: grid.height(),
+            width: grid.width(),
         })
     }
 }
 
-pub fn part_1(input: &str) -> u32 {
+/// Every blank-line separated dish, with errors placed on the line of the input they
+/// were found on.
+pub fn parse(input: &str) -> Result<Vec<Dish>, ParseError> {
     let mut dishes = vec![];
+    let mut line = 0;
     for section in input.split("\n\n") {
-        dishes.push(Dish::from_str(section).unwrap());
+        let dish = Dish::from_str(section).map_err(|e| {
+            let number = line + e.line;
+            e.on_line(number)
+        })?;
+        dishes.push(dish);
+        line += section.lines().count() + 1;
     }
 
-    dishes.iter().map(|d| d.tilt_up().score()).sum()
+    Ok(dishes)
 }
 
-pub fn part_2(input: &str) -> u32 {
-    let mut dish = Dish::from_str(input).unwrap();
-    let mut memo: HashMap<Dish, i32> = HashMap::new();
-
-    let mut cycle = 0;
-    let cycle_size;
+pub fn part_1(dishes: &[Dish]) -> u32 {
+    dishes.iter().map(|d| d.tilt_up().score()).sum()
+}
 
-    loop {
-        dish = dish.spin();
-        cycle += 1;
-        // println!("iter {} score {}", cycle, dish.score());
-
-        if let Some(result) = memo.get(&dish) {
-            // println!("Cycle detected, {}", result);
-            cycle_size = Some(cycle - result);
-            break;
-        } else {
-            memo.insert(dish.clone(), cycle);
-        }
-    }
+pub fn part_2(dishes: &[Dish]) -> u32 {
+    dishes
+        .iter()
+        .map(|dish| cycle::nth_state(dish.clone(), Dish::spin, 1_000_000_000).score())
+        .sum()
+}
 
-    let target_cycle = 1_000_000_000;
-    let target_cycle_remainder = target_cycle % cycle_size.unwrap();
+pub struct Solver;
 
-    loop {
-        dish = dish.spin();
-        cycle += 1;
+impl Solution for Solver {
+    type Input = Vec<Dish>;
 
-        // println!(
-        //     "Cycle size is {}, cycle position {}, target_cycle_remainder {}",
-        //     cycle_size,
-        //     cycle % cycle_size,
-        //     target_cycle_remainder
-        // );
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
 
-        if cycle % cycle_size.unwrap() == target_cycle_remainder {
-            break;
-        }
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
     }
 
-    dish.score()
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        assert_eq!(
-            part_1(
-                "O....#....
-        O.OO#....#
-        .....##...
-        OO.#O....O
-        .O.....O#.
-        O.#..O.#.#
-        ..O..#O..O
-        .......O..
-        #....###..
-        #OO..#...."
-            ),
-            136
-        );
+        let input = parse(&example(2023, 14, 1)).unwrap();
+
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 14, 1, 1));
     }
 
     #[test]
@@ -396,8 +364,16 @@ mod tests {
 
     #[test]
     fn part_2_test() {
-        let input = "";
+        let input = parse(&example(2023, 14, 1)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 14, 1, 2));
+    }
 
-        assert_eq!(part_2(input), 71503);
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse("O.#\n...\n\n.O.\n#x.").unwrap_err(),
+            ParseError::new("a grid cell", "x").at(5, 2)
+        );
     }
 }
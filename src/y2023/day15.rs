@@ -1,38 +1,58 @@
+use crate::error::{parse_field, ParseError};
+use crate::solution::Solution;
 use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 enum Mode {
     Add,
     Remove,
 }
 
-struct Lens<'a> {
-    label: &'a str,
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Lens {
+    label: String,
     mode: Mode,
     focal_length: u32,
     box_id: u32,
 }
 
-impl<'a> From<&'a str> for Lens<'a> {
-    fn from(s: &'a str) -> Lens<'a> {
-        if let Some(index) = s.chars().position(|c| c == '-') {
-            return Lens {
-                label: &s[..index],
-                mode: Mode::Remove,
-                focal_length: 0,
-                box_id: hash_value(&s[..index]),
-            };
-        }
-        if let Some(index) = s.chars().position(|c| c == '=') {
-            Lens {
-                label: &s[..index],
-                mode: Mode::Add,
-                focal_length: s[index + 1..].parse::<u32>().unwrap(),
-                box_id: hash_value(&s[..index]),
-            }
-        } else {
-            panic!("whoops")
-        }
+/// One comma-separated step of the initialization sequence, as written and as a lens
+/// operation.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Step {
+    text: String,
+    lens: Lens,
+}
+
+/// Parses `step`, a slice of `line`, reporting errors at their place in the line.
+fn parse_lens(line: &str, step: &str) -> Result<Lens, ParseError> {
+    if let Some(label) = step.strip_suffix('-') {
+        return Ok(Lens {
+            label: label.to_string(),
+            mode: Mode::Remove,
+            focal_length: 0,
+            box_id: hash_value(label),
+        });
+    }
+    let Some((label, focal_length)) = step.split_once('=') else {
+        return Err(ParseError::new("a step like rn=1 or cm-", step).within(line, step));
+    };
+
+    Ok(Lens {
+        label: label.to_string(),
+        mode: Mode::Add,
+        focal_length: parse_field(line, focal_length, "a focal length")?,
+        box_id: hash_value(label),
+    })
+}
+
+impl FromStr for Lens {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_lens(s, s)
     }
 }
 
@@ -42,14 +62,32 @@ fn hash_value(input: &str) -> u32 {
         .fold(0, |acc, char| ((acc + (char as u32)) * 17) % 256)
 }
 
-pub fn part_1(input: &str) -> u32 {
-    input.split(',').map(hash_value).sum()
+/// The initialization sequence. Newlines are ignored, so each line is split on its own.
+pub fn parse(input: &str) -> Result<Vec<Step>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .flat_map(|(index, line)| {
+            line.split(',').map(move |step| {
+                Ok(Step {
+                    text: step.to_string(),
+                    lens: parse_lens(line, step).map_err(|e| e.on_line(index + 1))?,
+                })
+            })
+        })
+        .collect()
+}
+
+pub fn part_1(steps: &[Step]) -> u32 {
+    steps.iter().map(|step| hash_value(&step.text)).sum()
 }
 
-pub fn part_2(input: &str) -> u32 {
-    let mut hash_map: HashMap<u32, Vec<Lens>> = HashMap::new();
+pub fn part_2(steps: &[Step]) -> u32 {
+    let mut hash_map: HashMap<u32, Vec<&Lens>> = HashMap::new();
 
-    for lens in input.split(',').map(Lens::from) {
+    for lens in steps.iter().map(|step| &step.lens) {
         let lenses = hash_map.entry(lens.box_id).or_default();
 
         if let Some(index) = lenses.iter().position(|l| l.label == lens.label) {
@@ -77,15 +115,34 @@ pub fn part_2(input: &str) -> u32 {
     sum
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Step>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+        let input = parse(&example(2023, 15, 1)).unwrap();
 
-        assert_eq!(part_1(input), 1320);
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 15, 1, 1));
     }
 
     #[test]
@@ -97,8 +154,20 @@ mod tests {
 
     #[test]
     fn part_2_test() {
-        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+        let input = parse(&example(2023, 15, 1)).unwrap();
 
-        assert_eq!(part_2(input), 145);
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 15, 1, 2));
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            "cm=x".parse::<Lens>().unwrap_err(),
+            ParseError::new("a focal length", "x").at(1, 4)
+        );
+        assert_eq!(
+            parse("rn=1,cm-\nqp=3,cm,qp-").unwrap_err(),
+            ParseError::new("a step like rn=1 or cm-", "cm").at(2, 6)
+        );
     }
 }
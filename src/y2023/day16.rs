@@ -1,298 +1,80 @@
+use crate::error::ParseError;
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::solution::Solution;
+use crate::trace;
 use std::cmp;
 use std::collections::HashSet;
-use super::direction::Direction;
+use std::fmt::Display;
+use std::str::FromStr;
 
 #[derive(Debug)]
 struct LightBeam {
-    row: usize,
-    col: usize,
+    position: Point,
     direction: Direction,
 }
 
-impl LightBeam {
-    fn try_new(
-        col: isize,
-        row: isize,
-        direction: Direction,
-        height: usize,
-        width: usize,
-    ) -> Option<LightBeam> {
-        if col < 0 || col as usize >= width || row < 0 || row as usize >= height {
-            return None;
-        }
-
-        Some(LightBeam {
-            row: row as usize,
-            col: col as usize,
-            direction,
-        })
-    }
+/// The mirrors and splitters laid out on the floor.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Contraption {
+    layout: Grid<char>,
 }
 
-struct Contraption {
-    layout: Vec<Vec<char>>,
-    width: usize,
-    height: usize,
-    energized: HashSet<(usize, usize)>,
-    visited: HashSet<(usize, usize, Direction)>,
+/// The tiles energized so far, and the beams already followed, which send the same
+/// light again.
+#[derive(Debug, Default)]
+struct Energized {
+    tiles: HashSet<Point>,
+    visited: HashSet<(Point, Direction)>,
 }
 
 impl Contraption {
-    fn energize(&mut self, lb: LightBeam) -> (Option<LightBeam>, Option<LightBeam>) {
-        if let Some(_) = self.visited.get(&(lb.col, lb.row, lb.direction)) {
-            return (None, None);
+    /// The directions a beam leaves a tile in, given the direction it entered it.
+    fn deflect(tile: char, direction: Direction) -> (Direction, Option<Direction>) {
+        match (tile, direction) {
+            ('-', Direction::Up | Direction::Down) => (Direction::Left, Some(Direction::Right)),
+            ('|', Direction::Left | Direction::Right) => (Direction::Up, Some(Direction::Down)),
+            ('/', Direction::Up | Direction::Down) => (direction.turn_right(), None),
+            ('/', _) => (direction.turn_left(), None),
+            ('\\', Direction::Up | Direction::Down) => (direction.turn_left(), None),
+            ('\\', _) => (direction.turn_right(), None),
+            _ => (direction, None),
         }
-        self.energized.insert((lb.col, lb.row));
-        self.visited.insert((lb.col, lb.row, lb.direction));
+    }
 
-        match self.layout[lb.row][lb.col] {
-            '.' => match lb.direction {
-                Direction::Up => (
-                    LightBeam::try_new(
-                        lb.col as isize,
-                        lb.row as isize - 1,
-                        lb.direction,
-                        self.height,
-                        self.width,
-                    ),
-                    None,
-                ),
-                Direction::Down => (
-                    LightBeam::try_new(
-                        lb.col as isize,
-                        lb.row as isize + 1,
-                        lb.direction,
-                        self.height,
-                        self.width,
-                    ),
-                    None,
-                ),
-                Direction::Left => (
-                    LightBeam::try_new(
-                        lb.col as isize - 1,
-                        lb.row as isize,
-                        lb.direction,
-                        self.height,
-                        self.width,
-                    ),
-                    None,
-                ),
-                Direction::Right => (
-                    LightBeam::try_new(
-                        lb.col as isize + 1,
-                        lb.row as isize,
-                        lb.direction,
-                        self.height,
-                        self.width,
-                    ),
-                    None,
-                ),
-            },
-            '-' => match lb.direction {
-                Direction::Up => (
-                    LightBeam::try_new(
-                        lb.col as isize - 1,
-                        lb.row as isize,
-                        Direction::Left,
-                        self.height,
-                        self.width,
-                    ),
-                    LightBeam::try_new(
-                        lb.col as isize + 1,
-                        lb.row as isize,
-                        Direction::Right,
-                        self.height,
-                        self.width,
-                    ),
-                ),
-                Direction::Down => (
-                    LightBeam::try_new(
-                        lb.col as isize - 1,
-                        lb.row as isize,
-                        Direction::Left,
-                        self.height,
-                        self.width,
-                    ),
-                    LightBeam::try_new(
-                        lb.col as isize + 1,
-                        lb.row as isize,
-                        Direction::Right,
-                        self.height,
-                        self.width,
-                    ),
-                ),
-                Direction::Left => (
-                    LightBeam::try_new(
-                        lb.col as isize - 1,
-                        lb.row as isize,
-                        lb.direction,
-                        self.height,
-                        self.width,
-                    ),
-                    None,
-                ),
-                Direction::Right => (
-                    LightBeam::try_new(
-                        lb.col as isize + 1,
-                        lb.row as isize,
-                        lb.direction,
-                        self.height,
-                        self.width,
-                    ),
-                    None,
-                ),
-            },
-            '|' => match lb.direction {
-                Direction::Up => (
-                    LightBeam::try_new(
-                        lb.col as isize,
-                        lb.row as isize - 1,
-                        lb.direction,
-                        self.height,
-                        self.width,
-                    ),
-                    None,
-                ),
-                Direction::Down => (
-                    LightBeam::try_new(
-                        lb.col as isize,
-                        lb.row as isize + 1,
-                        lb.direction,
-                        self.height,
-                        self.width,
-                    ),
-                    None,
-                ),
-                Direction::Left => (
-                    LightBeam::try_new(
-                        lb.col as isize,
-                        lb.row as isize - 1,
-                        Direction::Up,
-                        self.height,
-                        self.width,
-                    ),
-                    LightBeam::try_new(
-                        lb.col as isize,
-                        lb.row as isize + 1,
-                        Direction::Down,
-                        self.height,
-                        self.width,
-                    ),
-                ),
-                Direction::Right => (
-                    LightBeam::try_new(
-                        lb.col as isize,
-                        lb.row as isize - 1,
-                        Direction::Up,
-                        self.height,
-                        self.width,
-                    ),
-                    LightBeam::try_new(
-                        lb.col as isize,
-                        lb.row as isize + 1,
-                        Direction::Down,
-                        self.height,
-                        self.width,
-                    ),
-                ),
-            },
-            '/' => match lb.direction {
-                Direction::Up => (
-                    LightBeam::try_new(
-                        lb.col as isize + 1,
-                        lb.row as isize,
-                        Direction::Right,
-                        self.height,
-                        self.width,
-                    ),
-                    None,
-                ),
-                Direction::Down => (
-                    LightBeam::try_new(
-                        lb.col as isize - 1,
-                        lb.row as isize,
-                        Direction::Left,
-                        self.height,
-                        self.width,
-                    ),
-                    None,
-                ),
-                Direction::Left => (
-                    LightBeam::try_new(
-                        lb.col as isize,
-                        lb.row as isize + 1,
-                        Direction::Down,
-                        self.height,
-                        self.width,
-                    ),
-                    None,
-                ),
-                Direction::Right => (
-                    LightBeam::try_new(
-                        lb.col as isize,
-                        lb.row as isize - 1,
-                        Direction::Up,
-                        self.height,
-                        self.width,
-                    ),
-                    None,
-                ),
-            },
-            '\\' => match lb.direction {
-                Direction::Up => (
-                    LightBeam::try_new(
-                        lb.col as isize - 1,
-                        lb.row as isize,
-                        Direction::Left,
-                        self.height,
-                        self.width,
-                    ),
-                    None,
-                ),
-                Direction::Down => (
-                    LightBeam::try_new(
-                        lb.col as isize + 1,
-                        lb.row as isize,
-                        Direction::Right,
-                        self.height,
-                        self.width,
-                    ),
-                    None,
-                ),
-                Direction::Left => (
-                    LightBeam::try_new(
-                        lb.col as isize,
-                        lb.row as isize - 1,
-                        Direction::Up,
-                        self.height,
-                        self.width,
-                    ),
-                    None,
-                ),
-                Direction::Right => (
-                    LightBeam::try_new(
-                        lb.col as isize,
-                        lb.row as isize + 1,
-                        Direction::Down,
-                        self.height,
-                        self.width,
-                    ),
-                    None,
-                ),
-            },
-            _ => {
-                panic!("Invalid character");
-            }
+    fn energize(
+        &self,
+        energized: &mut Energized,
+        lb: LightBeam,
+    ) -> (Option<LightBeam>, Option<LightBeam>) {
+        if !energized.visited.insert((lb.position, lb.direction)) {
+            return (None, None);
         }
+        energized.tiles.insert(lb.position);
+
+        let next = |direction: Direction| {
+            lb.position
+                .checked_step(direction, &self.layout)
+                .map(|position| LightBeam {
+                    position,
+                    direction,
+                })
+        };
+        let (first, second) = Contraption::deflect(self.layout[lb.position], lb.direction);
+
+        (next(first), second.and_then(next))
     }
 
-    fn score(&mut self, lb: LightBeam) -> usize {
+    /// How many tiles a beam entering at `lb` energizes.
+    fn score(&self, lb: LightBeam) -> usize {
+        let mut energized = Energized::default();
         let mut stack = vec![lb];
+        stack.retain(|lb| self.layout.contains(lb.position));
 
         while let Some(lb) = stack.pop() {
-            // println!("Start {:?}", lb);
-            let result = self.energize(lb);
-            // println!("\tEnd {:?},{:?}", result.0, result.1);
+            trace!("Start {:?}", lb);
+            let result = self.energize(&mut energized, lb);
+            trace!("\tEnd {:?},{:?}", result.0, result.1);
             if let Some(new_lb) = result.0 {
                 stack.push(new_lb);
             }
@@ -301,75 +83,54 @@ impl Contraption {
             }
         }
 
-        let score = self.energized.len();
-        self.energized = HashSet::new();
-        self.visited = HashSet::new();
-
-        score
+        energized.tiles.len()
     }
 }
 
-impl From<&str> for Contraption {
-    fn from(s: &str) -> Contraption {
-        let mut width = 0;
-        let mut height = 0;
-        let mut layout = vec![];
-
-        for line in s.lines() {
-            let line = line.trim();
-            width = line.len();
-            height += 1;
-            layout.push(line.chars().collect());
-        }
+impl FromStr for Contraption {
+    type Err = ParseError;
 
-        Contraption {
-            layout,
-            width,
-            height,
-            energized: HashSet::new(),
-            visited: HashSet::new(),
-        }
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Contraption {
+            layout: Grid::parse(s, |c| {
+                matches!(c, '.' | '-' | '|' | '/' | '\\').then_some(c)
+            })?,
+        })
     }
 }
 
-pub fn part_1(input: &str) -> usize {
-    let mut contraption = Contraption::from(input);
-
+pub fn part_1(contraption: &Contraption) -> usize {
     contraption.score(LightBeam {
-        col: 0,
-        row: 0,
+        position: Point::ORIGIN,
         direction: Direction::Right,
     })
 }
 
-pub fn part_2(input: &str) -> usize {
-    let mut contraption = Contraption::from(input);
+pub fn part_2(contraption: &Contraption) -> usize {
+    let width = contraption.layout.width() as i64;
+    let height = contraption.layout.height() as i64;
     let mut max_score = 0;
 
-    for row in 0..contraption.height {
+    for row in 0..height {
         let score = contraption.score(LightBeam {
-            row,
-            col: 0,
+            position: Point::new(0, row),
             direction: Direction::Right,
         });
         max_score = cmp::max(score, max_score);
         let score = contraption.score(LightBeam {
-            row,
-            col: contraption.width - 1,
+            position: Point::new(width - 1, row),
             direction: Direction::Left,
         });
         max_score = cmp::max(score, max_score);
     }
-    for col in 0..contraption.width {
+    for col in 0..width {
         let score = contraption.score(LightBeam {
-            row: 0,
-            col,
+            position: Point::new(col, 0),
             direction: Direction::Down,
         });
         max_score = cmp::max(score, max_score);
         let score = contraption.score(LightBeam {
-            row: contraption.height - 1,
-            col,
+            position: Point::new(col, height - 1),
             direction: Direction::Up,
         });
         max_score = cmp::max(score, max_score);
@@ -378,39 +139,48 @@ pub fn part_2(input: &str) -> usize {
     max_score
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Contraption;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = r".|...\....
-        |.-.\.....
-        .....|-...
-        ........|.
-        ..........
-        .........\
-        ..../.\\..
-        .-.-/..|..
-        .|....-|.\
-        ..//.|....";
+        let input: Contraption = example(2023, 16, 1).parse().unwrap();
 
-        assert_eq!(part_1(input), 46);
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 16, 1, 1));
     }
 
     #[test]
     fn part_2_test() {
-        let input = r".|...\....
-        |.-.\.....
-        .....|-...
-        ........|.
-        ..........
-        .........\
-        ..../.\\..
-        .-.-/..|..
-        .|....-|.\
-        ..//.|....";
+        let input: Contraption = example(2023, 16, 1).parse().unwrap();
 
-        assert_eq!(part_2(input), 51);
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 16, 1, 2));
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            ".|..\n.#..".parse::<Contraption>().unwrap_err(),
+            ParseError::new("a grid cell", "#").at(2, 2)
+        );
     }
 }
@@ -1,220 +1,119 @@
-use super::direction::DirectionHeading;
-use std::collections::HashMap;
-use std::cmp;
-
-type CityMemo = HashMap<Point, (DirectionHeading, u32)>;
-type Point = (isize, isize);
+use crate::error::ParseError;
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::search;
+use crate::solution::Solution;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+struct Crucible {
+    position: Point,
+    direction: Direction,
+    /// Blocks moved in a straight line so far, 0 before the first move.
+    steps: u32,
+}
 
-struct City {
-    blocks: Vec<Vec<u32>>,
-    height: usize,
-    width: usize,
+/// The heat lost entering each city block.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct City {
+    blocks: Grid<u32>,
 }
 
 impl City {
-    fn minimize_heatloss(&self) -> u32 {
-        let mut memo: CityMemo = HashMap::new();
-        let start: Point = (0, 0);
-        let _end: Point = (self.width as isize - 1, self.height as isize - 1);
-
-        memo.insert(start, (DirectionHeading::Down(0), 0));
-        self.minimize_heatloss_helper((0, 1), DirectionHeading::Down(1), &mut memo);
-        self.minimize_heatloss_helper((1, 0), DirectionHeading::Right(1), &mut memo);
-        // self.minimize_heatloss_helper(end, DirectionHeading::Left(2), &mut memo);
-
-        cmp::min(memo.get(&(0, 1)).unwrap().1, memo.get(&(1, 0)).unwrap().1)
-    }
-
-    fn update_if_lower(
-        &self,
-        position: Point,
-        heading: DirectionHeading,
-        value: Option<(DirectionHeading, u32)>,
-        memo: &mut CityMemo,
-    ) {
-        if let Some(value) = value {
-            println!(
-                "Current {:?}, heading: {:?}, value: {:?}",
-                position, heading, value
-            );
-            let current = self.blocks[position.1 as usize][position.0 as usize];
-
-            if let Some(prior_value) = memo.get(&position) {
-                if prior_value.1 > value.1 + current {
-                    println!(
-                        "\tOverwriting {:?} with {:?}",
-                        position,
-                        (heading, value.1 + current)
-                    );
-                    memo.insert(position, (heading, value.1 + current));
-                }
-            } else {
-                println!(
-                    "\tInserting {:?} with {:?}",
-                    position,
-                    (heading, value.1 + current)
-                );
-                memo.insert(position, (heading, value.1 + current));
-            }
-        }
+    /// The least heat lost getting from the top left to the bottom right block with a
+    /// crucible that moves at least `min` and at most `max` blocks before it turns or stops,
+    /// if it can get there at all.
+    fn minimize_heatloss(&self, min: u32, max: u32) -> Option<u32> {
+        let end = Point::new(
+            self.blocks.width() as i64 - 1,
+            self.blocks.height() as i64 - 1,
+        );
+        let start = Crucible {
+            position: Point::ORIGIN,
+            direction: Direction::Right,
+            steps: 0,
+        };
+
+        search::astar(
+            start,
+            |crucible| self.moves(crucible, min, max),
+            |crucible| crucible.position.manhattan(end) as u32,
+            |crucible| crucible.position == end && crucible.steps >= min,
+        )
+        .map(|(_, heat)| heat)
     }
 
-    fn minimize_heatloss_helper(
-        &self,
-        position: Point,
-        heading: DirectionHeading,
-        memo: &mut CityMemo,
-    ) -> Option<(DirectionHeading, u32)> {
-        if let Some(value) = memo.get(&position) {
-            return Some(*value);
+    fn moves(&self, crucible: &Crucible, min: u32, max: u32) -> Vec<(Crucible, u32)> {
+        let mut directions = vec![];
+        if crucible.steps < max {
+            directions.push(crucible.direction);
         }
-        if position.0 == self.width as isize - 1 && position.1 == self.height as isize - 1 {
-            let current = self.blocks[position.1 as usize][position.0 as usize];
-            println!("Base case {:?}, {:?}", position, current);
-            memo.insert(position, (heading, current));
-            return Some(*memo.get(&position).unwrap());
+        if crucible.steps == 0 || crucible.steps >= min {
+            directions.push(crucible.direction.turn_left());
+            directions.push(crucible.direction.turn_right());
         }
-        if position.0 < 0
-            || position.0 > self.width as isize - 1
-            || position.1 < 0
-            || position.1 > self.height as isize - 1
-        {
-            return None;
-        }
-
-        println!("Traversing {:?}, heading {:?}", position, heading);
-        match heading {
-            DirectionHeading::Up(distance) => {
-                if distance < 3 {
-                    let value = self.minimize_heatloss_helper(
-                        (position.0, position.1 - 1),
-                        DirectionHeading::Up(distance + 1),
-                        memo,
-                    );
-                    self.update_if_lower(position, heading, value, memo);
-                }
-                let value = self.minimize_heatloss_helper(
-                    (position.0 - 1, position.1),
-                    DirectionHeading::Left(1),
-                    memo,
-                );
-                self.update_if_lower(position, heading, value, memo);
 
-                let value = self.minimize_heatloss_helper(
-                    (position.0 + 1, position.1),
-                    DirectionHeading::Right(1),
-                    memo,
-                );
-                self.update_if_lower(position, heading, value, memo);
-            }
-            DirectionHeading::Down(distance) => {
-                if distance < 3 {
-                    let value = self.minimize_heatloss_helper(
-                        (position.0, position.1 + 1),
-                        DirectionHeading::Down(distance + 1),
-                        memo,
-                    );
-                    self.update_if_lower(position, heading, value, memo);
-                }
-                let value = self.minimize_heatloss_helper(
-                    (position.0 - 1, position.1),
-                    DirectionHeading::Left(1),
-                    memo,
-                );
-                self.update_if_lower(position, heading, value, memo);
+        directions
+            .into_iter()
+            .filter_map(|direction| {
+                let position = crucible.position.checked_step(direction, &self.blocks)?;
+                let steps = if direction == crucible.direction {
+                    crucible.steps + 1
+                } else {
+                    1
+                };
+                let next = Crucible {
+                    position,
+                    direction,
+                    steps,
+                };
+                Some((next, self.blocks[position]))
+            })
+            .collect()
+    }
+}
 
-                let value = self.minimize_heatloss_helper(
-                    (position.0 + 1, position.1),
-                    DirectionHeading::Right(1),
-                    memo,
-                );
-                self.update_if_lower(position, heading, value, memo);
-            }
-            DirectionHeading::Left(distance) => {
-                if distance < 3 {
-                    let value = self.minimize_heatloss_helper(
-                        (position.0 - 1, position.1),
-                        DirectionHeading::Left(distance + 1),
-                        memo,
-                    );
-                    self.update_if_lower(position, heading, value, memo);
-                }
-                let value = self.minimize_heatloss_helper(
-                    (position.0, position.1 - 1),
-                    DirectionHeading::Up(1),
-                    memo,
-                );
-                self.update_if_lower(position, heading, value, memo);
+impl FromStr for City {
+    type Err = ParseError;
 
-                let value = self.minimize_heatloss_helper(
-                    (position.0, position.1 + 1),
-                    DirectionHeading::Down(1),
-                    memo,
-                );
-                self.update_if_lower(position, heading, value, memo);
-            }
-            DirectionHeading::Right(distance) => {
-                if distance < 3 {
-                    let value = self.minimize_heatloss_helper(
-                        (position.0 + 1, position.1),
-                        DirectionHeading::Right(distance + 1),
-                        memo,
-                    );
-                    self.update_if_lower(position, heading, value, memo);
-                }
-                let value = self.minimize_heatloss_helper(
-                    (position.0, position.1 - 1),
-                    DirectionHeading::Up(1),
-                    memo,
-                );
-                self.update_if_lower(position, heading, value, memo);
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(City {
+            blocks: Grid::parse(s, |c| c.to_digit(10))?,
+        })
+    }
+}
 
-                let value = self.minimize_heatloss_helper(
-                    (position.0, position.1 + 1),
-                    DirectionHeading::Down(1),
-                    memo,
-                );
-                self.update_if_lower(position, heading, value, memo);
-            }
-        }
+pub fn part_1(city: &City) -> u32 {
+    city.minimize_heatloss(1, 3).unwrap_or(0)
+}
 
-        Some(*memo.get(&position).unwrap())
-    }
+pub fn part_2(city: &City) -> u32 {
+    city.minimize_heatloss(4, 10).unwrap_or(0)
 }
 
-impl From<&str> for City {
-    fn from(s: &str) -> City {
-        let mut width = 0;
-        let mut height = 0;
-        let mut blocks = vec![];
+pub struct Solver;
 
-        for line in s.lines() {
-            let line = line.trim();
-            width = line.len();
-            height += 1;
-            blocks.push(line.chars().map(|c| c.to_digit(10).unwrap()).collect());
-        }
+impl Solution for Solver {
+    type Input = City;
 
-        City {
-            blocks,
-            width,
-            height,
-        }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
     }
-}
 
-pub fn part_1(input: &str) -> u32 {
-    let city = City::from(input);
-    city.minimize_heatloss()
-}
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
 
-pub fn part_2(_input: &str) -> usize {
-    0
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_small_test() {
@@ -222,7 +121,7 @@ mod tests {
         155
         111";
 
-        assert_eq!(part_1(input), 4);
+        assert_eq!(part_1(&input.parse().unwrap()), 4);
     }
 
     #[test]
@@ -231,7 +130,7 @@ mod tests {
         1551
         9911";
 
-        assert_eq!(part_1(input), 5);
+        assert_eq!(part_1(&input.parse().unwrap()), 5);
     }
 
     #[test]
@@ -240,32 +139,44 @@ mod tests {
         15551
         99911";
 
-        assert_eq!(part_1(input), 10);
+        assert_eq!(part_1(&input.parse().unwrap()), 10);
     }
 
     #[test]
     fn part_1_test() {
-        let input = "2413432311323
-        3215453535623
-        3255245654254
-        3446585845452
-        4546657867536
-        1438598798454
-        4457876987766
-        3637877979653
-        4654967986887
-        4564679986453
-        1224686865563
-        2546548887735
-        4322674655533";
+        let input: City = example(2023, 17, 1).parse().unwrap();
 
-        assert_eq!(part_1(input), 102);
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 17, 1, 1));
     }
 
     #[test]
     fn part_2_test() {
-        let input = "";
+        let input: City = example(2023, 17, 1).parse().unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 17, 1, 2));
+    }
 
-        assert_eq!(part_2(input), 71503);
+    #[test]
+    fn part_2_ultra_test() {
+        let input: City = example(2023, 17, 2).parse().unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 17, 2, 2));
+    }
+
+    #[test]
+    fn unreachable_test() {
+        // One block is never far enough for an ultra crucible to stop after.
+        let city: City = "15".parse().unwrap();
+
+        assert_eq!(city.minimize_heatloss(1, 3), Some(5));
+        assert_eq!(city.minimize_heatloss(4, 10), None);
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            "241\n3x1".parse::<City>().unwrap_err(),
+            ParseError::new("a grid cell", "x").at(2, 2)
+        );
     }
 }
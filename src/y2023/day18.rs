@@ -1,26 +1,171 @@
-pub fn part_1(_input: &str) -> usize {
-    0
+use crate::error::{parse_field, ParseError};
+use crate::geometry::{Direction, Point};
+use crate::polygon::Polygon;
+use crate::solution::Solution;
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DigStep {
+    direction: Direction,
+    distance: i64,
+}
+
+/// The dig plan read both ways: the steps as written, and the real steps hidden in
+/// each line's colour.
+#[derive(Debug, PartialEq, Clone)]
+pub struct DigPlan {
+    steps: Vec<DigStep>,
+    colour_steps: Vec<DigStep>,
+}
+
+/// Decodes a colour such as `70c710`: five hex digits of distance, then the direction
+/// as 0 to 3 for right, down, left and up.
+fn decode_colour(line: &str, hex: &str) -> Result<DigStep, ParseError> {
+    let (distance, direction) = hex.split_at(5);
+    let distance = i64::from_str_radix(distance, 16)
+        .map_err(|_| ParseError::new("a hex distance", distance).within(line, distance))?;
+    let direction = match direction {
+        "0" => Direction::Right,
+        "1" => Direction::Down,
+        "2" => Direction::Left,
+        "3" => Direction::Up,
+        _ => return Err(ParseError::new("a direction 0-3", direction).within(line, direction)),
+    };
+
+    Ok(DigStep {
+        direction,
+        distance,
+    })
+}
+
+fn parse_line(line: &str) -> Result<(DigStep, DigStep), ParseError> {
+    let mut fields = line.split_whitespace();
+    let (Some(direction), Some(distance), Some(colour), None) =
+        (fields.next(), fields.next(), fields.next(), fields.next())
+    else {
+        return Err(ParseError::new("a direction, distance and colour", line));
+    };
+
+    let step = DigStep {
+        direction: parse_field(line, direction, "a direction")?,
+        distance: parse_field(line, distance, "a distance")?,
+    };
+    let hex = colour
+        .strip_prefix("(#")
+        .and_then(|c| c.strip_suffix(')'))
+        .filter(|hex| hex.len() == 6 && hex.is_ascii())
+        .ok_or_else(|| ParseError::new("a colour like (#70c710)", colour).within(line, colour))?;
+
+    Ok((step, decode_colour(line, hex)?))
+}
+
+pub fn parse(input: &str) -> Result<DigPlan, ParseError> {
+    let mut plan = DigPlan {
+        steps: vec![],
+        colour_steps: vec![],
+    };
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (step, colour_step) = parse_line(line).map_err(|e| e.on_line(index + 1))?;
+        plan.steps.push(step);
+        plan.colour_steps.push(colour_step);
+    }
+
+    Ok(plan)
+}
+
+/// The lagoon holds the trench, one cube wide around the polygon through the cube
+/// centres, plus everything inside it.
+fn lagoon_size(steps: &[DigStep]) -> i64 {
+    Polygon::from_moves(
+        Point::ORIGIN,
+        steps.iter().map(|step| (step.direction, step.distance)),
+    )
+    .enclosed_points()
+}
+
+pub fn part_1(plan: &DigPlan) -> i64 {
+    lagoon_size(&plan.steps)
+}
+
+pub fn part_2(plan: &DigPlan) -> i64 {
+    lagoon_size(&plan.colour_steps)
 }
 
-pub fn part_2(_input: &str) -> usize {
-    0
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = DigPlan;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = "";
+        let input = parse(&example(2023, 18, 1)).unwrap();
 
-        assert_eq!(part_1(input), 288);
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 18, 1, 1));
     }
 
     #[test]
     fn part_2_test() {
-        let input = "";
+        let input = parse(&example(2023, 18, 1)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 18, 1, 2));
+    }
+
+    #[test]
+    fn parse_test() {
+        let plan = parse("R 6 (#70c710)\nD 5 (#0dc571)").unwrap();
 
-        assert_eq!(part_2(input), 71503);
+        assert_eq!(
+            plan.steps,
+            vec![
+                DigStep {
+                    direction: Direction::Right,
+                    distance: 6
+                },
+                DigStep {
+                    direction: Direction::Down,
+                    distance: 5
+                }
+            ]
+        );
+        assert_eq!(
+            plan.colour_steps,
+            vec![
+                DigStep {
+                    direction: Direction::Right,
+                    distance: 461937
+                },
+                DigStep {
+                    direction: Direction::Down,
+                    distance: 56407
+                }
+            ]
+        );
+        assert_eq!(
+            parse("R 6 (#70c710)\nD 5 (#0dc574)").unwrap_err(),
+            ParseError::new("a direction 0-3", "4").at(2, 12)
+        );
     }
 }
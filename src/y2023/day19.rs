@@ -1,26 +1,443 @@
-pub fn part_1(_input: &str) -> usize {
-    0
+use crate::error::{parse_field, ParseError};
+use crate::interval::Interval;
+use crate::solution::Solution;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+pub const START: &str = "in";
+
+/// The four ratings a part has, in the order the input lists them.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Category {
+    X,
+    M,
+    A,
+    S,
+}
+
+impl Category {
+    fn index(&self) -> usize {
+        match self {
+            Category::X => 0,
+            Category::M => 1,
+            Category::A => 2,
+            Category::S => 3,
+        }
+    }
+}
+
+impl TryFrom<char> for Category {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'x' => Ok(Category::X),
+            'm' => Ok(Category::M),
+            'a' => Ok(Category::A),
+            's' => Ok(Category::S),
+            _ => Err(ParseError::new("one of x, m, a or s", &c.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Target {
+    Accept,
+    Reject,
+    Workflow(String),
+}
+
+impl From<&str> for Target {
+    fn from(s: &str) -> Self {
+        match s {
+            "A" => Target::Accept,
+            "R" => Target::Reject,
+            name => Target::Workflow(name.to_string()),
+        }
+    }
+}
+
+/// A test such as `a<2006`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Condition {
+    pub category: Category,
+    pub less_than: bool,
+    pub value: u64,
+}
+
+impl Condition {
+    pub fn matches(&self, part: &Part) -> bool {
+        let rating = part.ratings[self.category.index()];
+        if self.less_than {
+            rating < self.value
+        } else {
+            rating > self.value
+        }
+    }
+
+    /// Splits `ratings` into the ratings that pass this condition and those that don't.
+    pub fn split(&self, ratings: &RatingBox) -> (Option<RatingBox>, Option<RatingBox>) {
+        let index = self.category.index();
+        let (below, above) = if self.less_than {
+            ratings.ranges[index].split(self.value)
+        } else {
+            ratings.ranges[index].split(self.value + 1)
+        };
+        let (passed, failed) = if self.less_than {
+            (below, above)
+        } else {
+            (above, below)
+        };
+
+        let with = |range: Interval<u64>| {
+            let mut ranges = ratings.ranges;
+            ranges[index] = range;
+            RatingBox { ranges }
+        };
+        (passed.map(with), failed.map(with))
+    }
+}
+
+/// Sends a part to `target` if it meets the condition, or always without one.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Rule {
+    pub condition: Option<Condition>,
+    pub target: Target,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Workflow {
+    pub name: String,
+    pub rules: Vec<Rule>,
+}
+
+impl Workflow {
+    /// Where the first matching rule sends `part`.
+    pub fn route(&self, part: &Part) -> &Target {
+        self.rules
+            .iter()
+            .find(|rule| rule.condition.is_none_or(|c| c.matches(part)))
+            .map_or(&Target::Reject, |rule| &rule.target)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Part {
+    pub ratings: [u64; 4],
+}
+
+/// Every part with each rating within the matching range, indexed like [`Category`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RatingBox {
+    pub ranges: [Interval<u64>; 4],
+}
+
+impl RatingBox {
+    pub fn combinations(&self) -> u64 {
+        self.ranges.iter().map(Interval::len).product()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct System {
+    pub workflows: HashMap<String, Workflow>,
+    pub parts: Vec<Part>,
+}
+
+impl System {
+    pub fn accepts(&self, part: &Part) -> bool {
+        let mut name = START;
+        loop {
+            match self.workflows[name].route(part) {
+                Target::Accept => return true,
+                Target::Reject => return false,
+                Target::Workflow(next) => name = next,
+            }
+        }
+    }
+
+    /// How many parts within `ratings` are accepted, following every workflow a slice of
+    /// the box could be sent to.
+    pub fn count_accepted(&self, ratings: RatingBox) -> u64 {
+        let mut pending = vec![(START, ratings)];
+        let mut accepted = 0;
+
+        while let Some((name, ratings)) = pending.pop() {
+            let mut remaining = Some(ratings);
+            for rule in &self.workflows[name].rules {
+                let Some(ratings) = remaining else {
+                    break;
+                };
+                let (passed, failed) = match rule.condition {
+                    Some(condition) => condition.split(&ratings),
+                    None => (Some(ratings), None),
+                };
+                remaining = failed;
+
+                match (&rule.target, passed) {
+                    (Target::Accept, Some(passed)) => accepted += passed.combinations(),
+                    (Target::Workflow(next), Some(passed)) => pending.push((next, passed)),
+                    _ => {}
+                }
+            }
+        }
+
+        accepted
+    }
+}
+
+fn parse_condition(line: &str, text: &str) -> Result<Condition, ParseError> {
+    let error = || ParseError::new("a condition like a<2006", text).within(line, text);
+    let mut chars = text.chars();
+    let category =
+        Category::try_from(chars.next().ok_or_else(error)?).map_err(|e| e.within(line, text))?;
+    let less_than = match chars.next() {
+        Some('<') => true,
+        Some('>') => false,
+        _ => return Err(error()),
+    };
+
+    Ok(Condition {
+        category,
+        less_than,
+        value: parse_field(line, &text[2..], "a rating")?,
+    })
+}
+
+/// A workflow, along with the names of the workflows it sends parts to as slices of
+/// `line`, so they can be checked once every workflow is known.
+fn parse_workflow(line: &str) -> Result<(Workflow, Vec<&str>), ParseError> {
+    let error = || ParseError::new("a workflow like px{a<2006:qkq,rfg}", line);
+    let (name, rules) = line
+        .strip_suffix('}')
+        .and_then(|l| l.split_once('{'))
+        .ok_or_else(error)?;
+
+    let mut targets = vec![];
+    let rules = rules
+        .split(',')
+        .map(|rule| {
+            let (condition, target) = match rule.split_once(':') {
+                Some((condition, target)) => (Some(parse_condition(line, condition)?), target),
+                None => (None, rule),
+            };
+            let rule = Rule {
+                condition,
+                target: Target::from(target),
+            };
+            if let Target::Workflow(_) = rule.target {
+                targets.push(target);
+            }
+            Ok(rule)
+        })
+        .collect::<Result<Vec<Rule>, ParseError>>()?;
+
+    let workflow = Workflow {
+        name: name.to_string(),
+        rules,
+    };
+    Ok((workflow, targets))
+}
+
+fn parse_part(line: &str) -> Result<Part, ParseError> {
+    let error = || ParseError::new("a part like {x=787,m=2655,a=1222,s=2876}", line);
+    let fields = line
+        .strip_prefix('{')
+        .and_then(|l| l.strip_suffix('}'))
+        .ok_or_else(error)?;
+
+    let mut ratings = [0; 4];
+    let mut seen = [false; 4];
+    for field in fields.split(',') {
+        let (category, rating) = field.split_once('=').ok_or_else(error)?;
+        let mut chars = category.chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            return Err(ParseError::new("one of x, m, a or s", category).within(line, category));
+        };
+        let index = Category::try_from(c)
+            .map_err(|e| e.within(line, category))?
+            .index();
+        if seen[index] {
+            return Err(
+                ParseError::new("a category not rated yet", category).within(line, category)
+            );
+        }
+        ratings[index] = parse_field(line, rating, "a rating")?;
+        seen[index] = true;
+    }
+    if seen.contains(&false) {
+        return Err(error());
+    }
+
+    Ok(Part { ratings })
+}
+
+pub fn parse(input: &str) -> Result<System, ParseError> {
+    let mut workflows: HashMap<String, Workflow> = HashMap::new();
+    let mut references = vec![];
+    let mut parts = vec![];
+    let mut in_parts = false;
+    // The line after the last workflow, where a missing `in` is reported.
+    let mut workflows_end = 1;
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            in_parts = !workflows.is_empty();
+            continue;
+        }
+
+        if in_parts {
+            parts.push(parse_part(line).map_err(|e| e.on_line(index + 1))?);
+        } else {
+            let (workflow, targets) = parse_workflow(line).map_err(|e| e.on_line(index + 1))?;
+            workflows.insert(workflow.name.clone(), workflow);
+            references.extend(targets.into_iter().map(|target| (index + 1, line, target)));
+            workflows_end = index + 2;
+        }
+    }
+
+    if !workflows.contains_key(START) {
+        return Err(ParseError::new("a workflow named in", "").at(workflows_end, 1));
+    }
+    for (number, line, target) in references {
+        if !workflows.contains_key(target) {
+            return Err(ParseError::new("a known workflow", target)
+                .on_line(number)
+                .within(line, target));
+        }
+    }
+
+    Ok(System { workflows, parts })
 }
 
-pub fn part_2(_input: &str) -> usize {
-    0
+pub fn part_1(system: &System) -> u64 {
+    system
+        .parts
+        .iter()
+        .filter(|part| system.accepts(part))
+        .map(|part| part.ratings.iter().sum::<u64>())
+        .sum()
+}
+
+pub fn part_2(system: &System) -> u64 {
+    system.count_accepted(RatingBox {
+        ranges: [Interval::new(1, 4001); 4],
+    })
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = System;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = "";
+        let input = parse(&example(2023, 19, 1)).unwrap();
 
-        assert_eq!(part_1(input), 288);
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 19, 1, 1));
     }
 
     #[test]
     fn part_2_test() {
-        let input = "";
+        let input = parse(&example(2023, 19, 1)).unwrap();
 
-        assert_eq!(part_2(input), 71503);
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 19, 1, 2));
+    }
+
+    #[test]
+    fn workflow_test() {
+        let (workflow, targets) = parse_workflow("px{a<2006:qkq,m>2090:A,rfg}").unwrap();
+        assert_eq!(targets, vec!["qkq", "rfg"]);
+        let part = |x, m, a, s| Part {
+            ratings: [x, m, a, s],
+        };
+
+        assert_eq!(
+            workflow.rules[0],
+            Rule {
+                condition: Some(Condition {
+                    category: Category::A,
+                    less_than: true,
+                    value: 2006
+                }),
+                target: Target::Workflow(String::from("qkq"))
+            }
+        );
+        assert_eq!(
+            workflow.route(&part(1, 1, 2005, 1)),
+            &Target::Workflow(String::from("qkq"))
+        );
+        assert_eq!(workflow.route(&part(1, 2091, 2006, 1)), &Target::Accept);
+        assert_eq!(
+            workflow.route(&part(1, 2090, 2006, 1)),
+            &Target::Workflow(String::from("rfg"))
+        );
+    }
+
+    #[test]
+    fn condition_split_test() {
+        let ratings = RatingBox {
+            ranges: [Interval::new(1, 4001); 4],
+        };
+        let condition = Condition {
+            category: Category::M,
+            less_than: false,
+            value: 2090,
+        };
+        let (passed, failed) = condition.split(&ratings);
+
+        assert_eq!(passed.unwrap().ranges[1], Interval::new(2091, 4001));
+        assert_eq!(failed.unwrap().ranges[1], Interval::new(1, 2091));
+        assert_eq!(
+            passed.unwrap().combinations() + failed.unwrap().combinations(),
+            ratings.combinations()
+        );
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse("in{a<2006:A,R}\n\n{x=787,m=2655,q=1222,s=2876}").unwrap_err(),
+            ParseError::new("one of x, m, a or s", "q").at(3, 15)
+        );
+        assert_eq!(
+            parse("in{a<2006:A,R}\n\n{x=787,m=2655,x=1222,s=2876}").unwrap_err(),
+            ParseError::new("a category not rated yet", "x").at(3, 15)
+        );
+        assert_eq!(
+            parse("in{a<2006:A,R}\n\n{x=787,m=2655,a=1222}").unwrap_err(),
+            ParseError::new(
+                "a part like {x=787,m=2655,a=1222,s=2876}",
+                "{x=787,m=2655,a=1222}"
+            )
+            .on_line(3)
+        );
+        assert_eq!(
+            parse("in{s>1:A,R}\npx{a<2006:qkq,R}").unwrap_err(),
+            ParseError::new("a known workflow", "qkq").at(2, 11)
+        );
+        assert_eq!(
+            parse("px{a<2006:A,R}\nqq{R}\n\n{x=787,m=2655,a=1222,s=2876}").unwrap_err(),
+            ParseError::new("a workflow named in", "").at(3, 1)
+        );
     }
 }
@@ -1,9 +1,11 @@
-use once_cell::sync::Lazy;
-use regex::Regex;
+use crate::error::{parse_field, ParseError};
+use crate::solution::Solution;
 use std::cmp;
+use std::fmt::Display;
+use std::str::FromStr;
 
 #[derive(Debug, PartialEq, Copy, Clone)]
-struct Game {
+pub struct Game {
     id: i32,
     blue: i32,
     red: i32,
@@ -11,38 +13,45 @@ struct Game {
 }
 
 impl Game {
-    pub fn build(line: &str) -> Game {
-        let mut pieces = line.split(":");
-        let game_split = pieces.next().expect("No game found");
-        let rounds_split = pieces.next().expect("No rounds found");
-
-        static ID_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"Game (?P<id>\d+)").unwrap());
-        let caps = ID_REGEX.captures(game_split).unwrap();
-        let id = &caps["id"]
-            .parse::<i32>()
-            .expect("Game id should be numeric");
+    /// Parses a game like `Game 1: 3 blue, 4 red; 1 red`, placing errors within `line`.
+    fn parse(line: &str) -> Result<Game, ParseError> {
+        let trimmed = line.trim();
+        let (game_split, rounds_split) = trimmed.split_once(':').ok_or_else(|| {
+            ParseError::new("a game like Game 1: 3 blue", trimmed).within(line, trimmed)
+        })?;
+        let id = game_split
+            .strip_prefix("Game ")
+            .ok_or_else(|| ParseError::new("Game and its id", game_split).within(line, game_split))
+            .and_then(|id| parse_field(line, id.trim(), "a game id"))?;
 
         let mut red = 0;
         let mut green = 0;
         let mut blue = 0;
-        static ROUNDS_REGEX: Lazy<Regex> =
-            Lazy::new(|| Regex::new(r"(?:(?P<count>\d+) (?P<color>blue|green|red),?)").unwrap());
-        // let caps = rounds_regex.captures(rounds_split).unwrap();
-        for c in ROUNDS_REGEX.captures_iter(rounds_split) {
-            let count = &c["count"].parse::<i32>().expect("Missing count");
-            match &c["color"] {
-                "red" => red = cmp::max(red, *count),
-                "blue" => blue = cmp::max(blue, *count),
-                _ => green = cmp::max(green, *count),
+        if !rounds_split.trim().is_empty() {
+            for cubes in rounds_split.split([';', ',']).map(str::trim) {
+                let Some((count, color)) = cubes.split_once(' ') else {
+                    return Err(
+                        ParseError::new("a count and color like 3 blue", cubes).within(line, cubes)
+                    );
+                };
+                let count: i32 = parse_field(line, count, "a cube count")?;
+                match color {
+                    "red" => red = cmp::max(red, count),
+                    "blue" => blue = cmp::max(blue, count),
+                    "green" => green = cmp::max(green, count),
+                    _ => {
+                        return Err(ParseError::new("red, green or blue", color).within(line, color))
+                    }
+                }
             }
         }
 
-        Game {
-            id: *id,
+        Ok(Game {
+            id,
             blue,
             green,
             red,
-        }
+        })
     }
 
     pub fn is_valid(self, red: i32, green: i32, blue: i32) -> bool {
@@ -50,67 +59,112 @@ impl Game {
     }
 }
 
-pub fn part_1(input: &Vec<String>) -> i32 {
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Game::parse(s)
+    }
+}
+
+/// One game per non-empty line.
+pub fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| Game::parse(line).map_err(|e| e.on_line(index + 1)))
+        .collect()
+}
+
+pub fn part_1(input: &[Game]) -> i32 {
     let red = 12;
     let green = 13;
     let blue = 14;
 
     input
         .iter()
-        .map(|line| Game::build(line))
         .filter(|game| game.is_valid(red, green, blue))
         .map(|game| game.id)
         .sum()
 }
 
-pub fn part_2(input: &Vec<String>) -> i32 {
+pub fn part_2(input: &[Game]) -> i32 {
     input
         .iter()
-        .map(|line| Game::build(line))
         .map(|game| game.red * game.blue * game.green)
         .sum()
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-        Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-        Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-        Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-        Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_1(&lines), 8)
+        let input = parse(&example(2023, 2, 1)).unwrap();
+
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 2, 1, 1));
     }
 
     #[test]
     fn parse_game() {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green:";
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
 
         assert_eq!(
-            Game::build(input),
-            Game {
+            input.parse(),
+            Ok(Game {
                 id: 1,
                 blue: 6,
                 red: 4,
                 green: 2
-            }
+            })
         )
     }
 
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse("Game 1: 3 blue\nGame x: 1 red").unwrap_err(),
+            ParseError::new("a game id", "x").at(2, 6)
+        );
+        assert_eq!(
+            parse("Game 1: 3 blue; 2 purple").unwrap_err(),
+            ParseError::new("red, green or blue", "purple").at(1, 19)
+        );
+        assert_eq!(
+            parse("Game 1: 3 blue;; 2 red").unwrap_err(),
+            ParseError::new("a count and color like 3 blue", "").at(1, 16)
+        );
+        assert_eq!(
+            parse("  Game 1 3 blue").unwrap_err(),
+            ParseError::new("a game like Game 1: 3 blue", "Game 1 3 blue").at(1, 3)
+        );
+    }
+
     #[test]
     fn part_2_test() {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-        Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-        Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-        Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-        Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_2(&lines), 2286)
+        let input = parse(&example(2023, 2, 1)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 2, 1, 2));
     }
 }
@@ -1,26 +1,377 @@
-pub fn part_1(_input: &str) -> usize {
-    0
+use crate::error::ParseError;
+use crate::math::crt;
+use crate::solution::Solution;
+use crate::{debug, trace};
+use std::collections::VecDeque;
+use std::fmt::Display;
+
+pub const BROADCASTER: &str = "broadcaster";
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Kind {
+    Broadcaster,
+    /// `%`, toggles on a low pulse and sends its new state.
+    FlipFlop,
+    /// `&`, sends low only once the last pulse from every input was high.
+    Conjunction,
+    /// Only named as a destination, like `output` or `rx`.
+    Sink,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Module {
+    pub name: String,
+    pub kind: Kind,
+    pub outputs: Vec<usize>,
+    pub inputs: Vec<usize>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Pulse {
+    pub from: usize,
+    pub to: usize,
+    pub high: bool,
+}
+
+/// The modules, indexed by position, with the broadcaster's index kept for button presses.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Circuit {
+    pub modules: Vec<Module>,
+    broadcaster: usize,
+}
+
+impl Circuit {
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.modules.iter().position(|module| module.name == name)
+    }
+
+    /// The circuit in Graphviz's dot language, flip-flops as boxes and conjunctions as
+    /// diamonds, e.g. for `dot -Tsvg`.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph circuit {\n");
+        for module in &self.modules {
+            let shape = match module.kind {
+                Kind::Broadcaster => "doublecircle",
+                Kind::FlipFlop => "box",
+                Kind::Conjunction => "diamond",
+                Kind::Sink => "circle",
+            };
+            out.push_str(&format!("  {} [shape={shape}];\n", module.name));
+        }
+        for module in &self.modules {
+            for output in &module.outputs {
+                out.push_str(&format!(
+                    "  {} -> {};\n",
+                    module.name, self.modules[*output].name
+                ));
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
 }
 
-pub fn part_2(_input: &str) -> usize {
-    0
+/// The state of every flip-flop and conjunction memory between button presses.
+pub struct Simulator<'a> {
+    circuit: &'a Circuit,
+    on: Vec<bool>,
+    /// For each module, the last pulse received from each of its inputs, in input order.
+    memory: Vec<Vec<bool>>,
+    pub presses: u64,
+    pub low: u64,
+    pub high: u64,
+}
+
+impl<'a> Simulator<'a> {
+    pub fn new(circuit: &'a Circuit) -> Simulator<'a> {
+        Simulator {
+            circuit,
+            on: vec![false; circuit.modules.len()],
+            memory: circuit
+                .modules
+                .iter()
+                .map(|module| vec![false; module.inputs.len()])
+                .collect(),
+            presses: 0,
+            low: 0,
+            high: 0,
+        }
+    }
+
+    /// Sends a low pulse to the broadcaster and delivers every pulse that follows in the
+    /// order sent, showing each to `observe` as it is delivered.
+    pub fn press(&mut self, mut observe: impl FnMut(&Pulse)) {
+        self.presses += 1;
+        let mut queue = VecDeque::from([Pulse {
+            from: self.circuit.broadcaster,
+            to: self.circuit.broadcaster,
+            high: false,
+        }]);
+
+        while let Some(pulse) = queue.pop_front() {
+            observe(&pulse);
+            if pulse.high {
+                self.high += 1;
+            } else {
+                self.low += 1;
+            }
+
+            let module = &self.circuit.modules[pulse.to];
+            let send = match module.kind {
+                Kind::Broadcaster => Some(pulse.high),
+                Kind::FlipFlop if !pulse.high => {
+                    self.on[pulse.to] = !self.on[pulse.to];
+                    Some(self.on[pulse.to])
+                }
+                Kind::FlipFlop => None,
+                Kind::Conjunction => {
+                    let memory = &mut self.memory[pulse.to];
+                    if let Some(slot) = module.inputs.iter().position(|i| *i == pulse.from) {
+                        memory[slot] = pulse.high;
+                    }
+                    Some(!memory.iter().all(|high| *high))
+                }
+                Kind::Sink => None,
+            };
+
+            if let Some(high) = send {
+                queue.extend(module.outputs.iter().map(|to| Pulse {
+                    from: pulse.to,
+                    to: *to,
+                    high,
+                }));
+            }
+        }
+    }
+}
+
+fn parse_kind(name: &str) -> (Kind, &str) {
+    if let Some(name) = name.strip_prefix('%') {
+        (Kind::FlipFlop, name)
+    } else if let Some(name) = name.strip_prefix('&') {
+        (Kind::Conjunction, name)
+    } else if name == BROADCASTER {
+        (Kind::Broadcaster, name)
+    } else {
+        (Kind::Sink, name)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Circuit, ParseError> {
+    let mut modules: Vec<Module> = vec![];
+    let mut outputs: Vec<Vec<&str>> = vec![];
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (name, destinations) = line
+            .split_once(" -> ")
+            .ok_or_else(|| ParseError::new("a module like %a -> b, c", line).on_line(index + 1))?;
+        let (kind, name) = parse_kind(name);
+        if kind == Kind::Sink {
+            return Err(ParseError::new("%, & or broadcaster", name).at(index + 1, 1));
+        }
+
+        modules.push(Module {
+            name: name.to_string(),
+            kind,
+            outputs: vec![],
+            inputs: vec![],
+        });
+        outputs.push(destinations.split(',').map(str::trim).collect());
+    }
+
+    let broadcaster = modules
+        .iter()
+        .position(|module| module.kind == Kind::Broadcaster)
+        .ok_or_else(|| ParseError::new("a broadcaster module", ""))?;
+
+    for (from, destinations) in outputs.into_iter().enumerate() {
+        for destination in destinations {
+            let to = match modules.iter().position(|m| m.name == destination) {
+                Some(to) => to,
+                None => {
+                    modules.push(Module {
+                        name: destination.to_string(),
+                        kind: Kind::Sink,
+                        outputs: vec![],
+                        inputs: vec![],
+                    });
+                    modules.len() - 1
+                }
+            };
+            modules[from].outputs.push(to);
+            modules[to].inputs.push(from);
+        }
+    }
+
+    Ok(Circuit {
+        modules,
+        broadcaster,
+    })
+}
+
+/// The first press on which `rx` gets a low pulse, assuming, as the puzzle input is
+/// built, that it is fed by a single conjunction whose inputs each go high on a cycle of
+/// their own. Watches for two high pulses from each input to learn its cycle, then finds
+/// the first press on which they line up. `None` if there is no such conjunction or the
+/// cycles don't show within `limit` presses.
+pub fn first_low_to(circuit: &Circuit, target: &str, limit: u64) -> Option<u64> {
+    let target = circuit.index(target)?;
+    let [feeder] = circuit.modules[target].inputs[..] else {
+        return None;
+    };
+    if circuit.modules[feeder].kind != Kind::Conjunction {
+        return None;
+    }
+
+    let inputs = &circuit.modules[feeder].inputs;
+    let mut highs: Vec<Vec<u64>> = vec![vec![]; inputs.len()];
+    let mut simulator = Simulator::new(circuit);
+    while highs.iter().any(|presses| presses.len() < 2) {
+        if simulator.presses >= limit {
+            return None;
+        }
+        let press = simulator.presses + 1;
+        simulator.press(|pulse| {
+            if pulse.high && pulse.to == feeder {
+                if let Some(slot) = inputs.iter().position(|i| *i == pulse.from) {
+                    if highs[slot].last() != Some(&press) {
+                        highs[slot].push(press);
+                    }
+                }
+            }
+        });
+    }
+    trace!("Presses sending a high pulse to the feeder {:?}", highs);
+
+    let congruences: Vec<(i64, i64)> = highs
+        .iter()
+        .map(|presses| {
+            let period = (presses[1] - presses[0]) as i64;
+            (presses[0] as i64, period)
+        })
+        .collect();
+    let (residue, modulus) = crt(&congruences)?;
+    let first = highs.iter().map(|presses| presses[0]).max()? as i64;
+
+    // The smallest press at or after every input's first high that fits every cycle.
+    let behind = (first - residue).max(0);
+    let press = residue + (behind + modulus - 1) / modulus * modulus;
+    Some(press as u64)
+}
+
+pub fn part_1(circuit: &Circuit) -> u64 {
+    let mut simulator = Simulator::new(circuit);
+    for _ in 0..1000 {
+        simulator.press(|_| {});
+    }
+
+    simulator.low * simulator.high
+}
+
+pub fn part_2(circuit: &Circuit) -> u64 {
+    debug!("Circuit {}", circuit.to_dot());
+
+    first_low_to(circuit, "rx", 1_000_000).unwrap_or_else(|| {
+        debug!("No low pulse reaches rx");
+        0
+    })
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Circuit;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = "";
+        let input = parse(&example(2023, 20, 1)).unwrap();
 
-        assert_eq!(part_1(input), 288);
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 20, 1, 1));
     }
 
     #[test]
-    fn part_2_test() {
-        let input = "";
+    fn part_1_second_example_test() {
+        let input = parse(&example(2023, 20, 2)).unwrap();
 
-        assert_eq!(part_2(input), 71503);
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 20, 2, 1));
+    }
+
+    #[test]
+    fn simulator_test() {
+        let circuit = parse(&example(2023, 20, 2)).unwrap();
+        let mut simulator = Simulator::new(&circuit);
+        let mut pulses = vec![];
+        simulator.press(|pulse| pulses.push(*pulse));
+
+        // The first press from the puzzle description, button pulse included.
+        assert_eq!(pulses.len(), 8);
+        assert_eq!((simulator.low, simulator.high), (4, 4));
+        assert_eq!(
+            pulses
+                .last()
+                .map(|p| (circuit.modules[p.to].name.as_str(), p.high)),
+            Some(("output", false))
+        );
+    }
+
+    #[test]
+    fn first_low_to_test() {
+        // Two flip-flop counters feeding `&hub`, `fx` sending it a high pulse every 4th
+        // press and `fy` every 8th.
+        let input = "broadcaster -> x1, y1
+        %x1 -> x2
+        %x2 -> fx
+        &fx -> hub
+        %y1 -> y2
+        %y2 -> y3
+        %y3 -> fy
+        &fy -> hub
+        &hub -> rx";
+        let circuit = parse(input).unwrap();
+
+        assert_eq!(first_low_to(&circuit, "rx", 100), Some(8));
+        assert_eq!(first_low_to(&circuit, "output", 100), None);
+    }
+
+    #[test]
+    fn to_dot_test() {
+        let circuit = parse("broadcaster -> a\n%a -> inv, output\n&inv -> a").unwrap();
+
+        assert_eq!(
+            circuit.to_dot(),
+            "digraph circuit {
+  broadcaster [shape=doublecircle];
+  a [shape=box];
+  inv [shape=diamond];
+  output [shape=circle];
+  broadcaster -> a;
+  a -> inv;
+  a -> output;
+  inv -> a;
+}
+"
+        );
     }
 }
@@ -0,0 +1,142 @@
+use crate::error::ParseError;
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::solution::Solution;
+use crate::trace;
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Display;
+
+/// The garden map, `true` for rocks, and where the elf starts.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Garden {
+    rocks: Grid<bool>,
+    start: Point,
+}
+
+impl Garden {
+    /// Whether `point` is a rock, with the map repeating forever in every direction when
+    /// `infinite`, and everything outside it a rock otherwise.
+    fn is_rock(&self, point: Point, infinite: bool) -> bool {
+        let (width, height) = (self.rocks.width() as i64, self.rocks.height() as i64);
+        if infinite {
+            let wrapped = Point::new(point.x.rem_euclid(width), point.y.rem_euclid(height));
+            return self.rocks[wrapped];
+        }
+        !self.rocks.contains(point) || self.rocks[point]
+    }
+
+    /// How many plots the elf could end on after exactly `steps` steps. Any plot reached
+    /// in fewer steps of the same parity counts, since the elf can step back and forth.
+    pub fn reachable(&self, steps: usize, infinite: bool) -> usize {
+        let mut seen: HashSet<Point> = HashSet::from([self.start]);
+        let mut queue = VecDeque::from([(self.start, 0)]);
+        let mut count = 0;
+
+        while let Some((point, distance)) = queue.pop_front() {
+            if distance % 2 == steps % 2 {
+                count += 1;
+            }
+            if distance == steps {
+                continue;
+            }
+            for next in point.neighbours() {
+                if !self.is_rock(next, infinite) && seen.insert(next) {
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+
+        count
+    }
+}
+
+pub fn parse(input: &str) -> Result<Garden, ParseError> {
+    let tiles = Grid::parse(input, |c| matches!(c, '.' | '#' | 'S').then_some(c))?;
+    let start = tiles.find(|c| *c == 'S').ok_or_else(|| {
+        ParseError::new("a starting plot 'S'", input.lines().next().unwrap_or(""))
+    })?;
+
+    Ok(Garden {
+        rocks: tiles.map(|c| *c == '#'),
+        start: Point::from(start),
+    })
+}
+
+/// The value at `x` of the quadratic through `(0, y0)`, `(1, y1)` and `(2, y2)`.
+pub fn extrapolate([y0, y1, y2]: [i64; 3], x: i64) -> i64 {
+    let first = y1 - y0;
+    let second = y2 - 2 * y1 + y0;
+    y0 + x * first + x * (x - 1) / 2 * second
+}
+
+/// Plots reachable in `steps` on the infinite map. Real inputs are square with a clear
+/// row and column through the start, so the reachable area grows as a quadratic in the
+/// number of whole maps crossed: sample it at three map widths and extrapolate.
+pub fn reachable_far(garden: &Garden, steps: usize) -> i64 {
+    let width = garden.rocks.width();
+    let offset = steps % width;
+    let samples = [0, 1, 2].map(|k| garden.reachable(offset + k * width, true) as i64);
+    trace!(
+        "Reachable after {offset} + k * {width} steps: {:?}",
+        samples
+    );
+
+    extrapolate(samples, (steps / width) as i64)
+}
+
+pub fn part_1(garden: &Garden) -> usize {
+    garden.reachable(64, false)
+}
+
+pub fn part_2(garden: &Garden) -> i64 {
+    reachable_far(garden, 26501365)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Garden;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::example;
+
+    #[test]
+    fn part_1_test() {
+        let garden = parse(&example(2023, 21, 1)).unwrap();
+
+        assert_eq!(garden.reachable(6, false), 16);
+    }
+
+    #[test]
+    fn infinite_test() {
+        let garden = parse(&example(2023, 21, 1)).unwrap();
+
+        assert_eq!(garden.reachable(6, true), 16);
+        assert_eq!(garden.reachable(10, true), 50);
+        assert_eq!(garden.reachable(50, true), 1594);
+        assert_eq!(garden.reachable(100, true), 6536);
+    }
+
+    #[test]
+    fn extrapolate_test() {
+        let f = |x: i64| 3 * x * x - 2 * x + 7;
+
+        assert_eq!(extrapolate([f(0), f(1), f(2)], 10), f(10));
+        assert_eq!(extrapolate([f(0), f(1), f(2)], 202300), f(202300));
+    }
+}
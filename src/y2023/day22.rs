@@ -0,0 +1,198 @@
+use crate::error::{parse_field, ParseError};
+use crate::solution::Solution;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Display;
+
+/// A brick between two corner cubes, both included.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Brick {
+    pub start: [i64; 3],
+    pub end: [i64; 3],
+}
+
+impl Brick {
+    fn bottom(&self) -> i64 {
+        self.start[2].min(self.end[2])
+    }
+
+    fn height(&self) -> i64 {
+        (self.end[2] - self.start[2]).abs() + 1
+    }
+
+    /// The `(x, y)` columns the brick covers.
+    fn footprint(&self) -> impl Iterator<Item = (i64, i64)> {
+        let xs = self.start[0].min(self.end[0])..=self.start[0].max(self.end[0]);
+        let ys = self.start[1].min(self.end[1])..=self.start[1].max(self.end[1]);
+        xs.flat_map(move |x| ys.clone().map(move |y| (x, y)))
+    }
+}
+
+/// Which settled bricks rest on which, by index into the bricks sorted by height.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Supports {
+    pub supports: Vec<Vec<usize>>,
+    pub supported_by: Vec<Vec<usize>>,
+}
+
+/// Drops every brick as far as it will go, lowest first, noting what each lands on.
+pub fn settle(bricks: &[Brick]) -> Supports {
+    let mut order: Vec<&Brick> = bricks.iter().collect();
+    order.sort_by_key(|brick| brick.bottom());
+
+    // The height of the top cube in each column, and the brick it belongs to.
+    let mut tops: HashMap<(i64, i64), (i64, usize)> = HashMap::new();
+    let mut supports = vec![vec![]; bricks.len()];
+    let mut supported_by: Vec<Vec<usize>> = vec![vec![]; bricks.len()];
+
+    for (index, brick) in order.into_iter().enumerate() {
+        let below: Vec<(i64, usize)> = brick
+            .footprint()
+            .filter_map(|column| tops.get(&column).copied())
+            .collect();
+        let floor = below.iter().map(|(top, _)| *top).max().unwrap_or(0);
+
+        for (top, support) in below {
+            if top == floor && !supported_by[index].contains(&support) {
+                supported_by[index].push(support);
+                supports[support].push(index);
+            }
+        }
+        for column in brick.footprint() {
+            tops.insert(column, (floor + brick.height(), index));
+        }
+    }
+
+    Supports {
+        supports,
+        supported_by,
+    }
+}
+
+/// How many other bricks would fall if `brick` were taken away.
+pub fn chain_reaction(settled: &Supports, brick: usize) -> usize {
+    let mut fallen = vec![false; settled.supports.len()];
+    fallen[brick] = true;
+    let mut queue = VecDeque::from([brick]);
+    let mut count = 0;
+
+    while let Some(brick) = queue.pop_front() {
+        for &above in &settled.supports[brick] {
+            if !fallen[above] && settled.supported_by[above].iter().all(|b| fallen[*b]) {
+                fallen[above] = true;
+                count += 1;
+                queue.push_back(above);
+            }
+        }
+    }
+
+    count
+}
+
+fn parse_corner(line: &str, corner: &str) -> Result<[i64; 3], ParseError> {
+    let mut coordinates = corner.split(',');
+    let mut next = || {
+        let part = coordinates
+            .next()
+            .ok_or_else(|| ParseError::new("x,y,z", corner).within(line, corner))?;
+        parse_field(line, part, "a coordinate")
+    };
+
+    Ok([next()?, next()?, next()?])
+}
+
+pub fn parse(input: &str) -> Result<Vec<Brick>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| {
+            let (start, end) = line
+                .split_once('~')
+                .ok_or_else(|| ParseError::new("a brick like 1,0,1~1,2,1", line))
+                .and_then(|(start, end)| Ok((parse_corner(line, start)?, parse_corner(line, end)?)))
+                .map_err(|e| e.on_line(index + 1))?;
+            Ok(Brick { start, end })
+        })
+        .collect()
+}
+
+/// Bricks that can go without anything falling: those that never hold something up alone.
+pub fn part_1(bricks: &[Brick]) -> usize {
+    let settled = settle(bricks);
+
+    settled
+        .supports
+        .iter()
+        .filter(|above| {
+            above
+                .iter()
+                .all(|brick| settled.supported_by[*brick].len() > 1)
+        })
+        .count()
+}
+
+pub fn part_2(bricks: &[Brick]) -> usize {
+    let settled = settle(bricks);
+
+    (0..bricks.len())
+        .map(|brick| chain_reaction(&settled, brick))
+        .sum()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Brick>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::{example, example_answer};
+
+    #[test]
+    fn part_1_test() {
+        let input = parse(&example(2023, 22, 1)).unwrap();
+
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 22, 1, 1));
+    }
+
+    #[test]
+    fn part_2_test() {
+        let input = parse(&example(2023, 22, 1)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 22, 1, 2));
+    }
+
+    #[test]
+    fn settle_test() {
+        let settled = settle(&parse(&example(2023, 22, 1)).unwrap());
+
+        // A holds up B and C, which both hold up D and E.
+        assert_eq!(settled.supports[0], vec![1, 2]);
+        assert_eq!(settled.supported_by[3], vec![1, 2]);
+        assert_eq!(chain_reaction(&settled, 0), 6);
+        assert_eq!(chain_reaction(&settled, 5), 1);
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse("1,0,1~1,2,1\n0,0,2~2,x,2").unwrap_err(),
+            ParseError::new("a coordinate", "x").at(2, 9)
+        );
+    }
+}
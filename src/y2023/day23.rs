@@ -0,0 +1,251 @@
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::solution::Solution;
+use crate::trace;
+use std::fmt::Display;
+
+/// The hiking map, with the gaps in the top and bottom walls the hike runs between.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Trails {
+    tiles: Grid<char>,
+    start: (usize, usize),
+    end: (usize, usize),
+}
+
+/// The trails with every corridor collapsed into one edge between the junctions at its
+/// ends, weighted by its length.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TrailGraph {
+    pub junctions: Vec<(usize, usize)>,
+    pub edges: Vec<Vec<(usize, usize)>>,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Trails {
+    fn is_open(&self, position: (usize, usize)) -> bool {
+        self.tiles[position] != '#'
+    }
+
+    /// The tiles a hiker can step to next. On a slope that is only downhill, unless
+    /// the slopes aren't `slippery`.
+    fn exits(&self, position: (usize, usize), slippery: bool) -> Vec<(usize, usize)> {
+        let downhill = match self.tiles[position] {
+            '^' => Some((0, -1)),
+            '>' => Some((1, 0)),
+            'v' => Some((0, 1)),
+            '<' => Some((-1, 0)),
+            _ => None,
+        };
+
+        match downhill {
+            Some(delta) if slippery => self
+                .tiles
+                .step(position, delta)
+                .filter(|next| self.is_open(*next))
+                .into_iter()
+                .collect(),
+            _ => self
+                .tiles
+                .neighbours(position)
+                .filter(|next| self.is_open(*next))
+                .collect(),
+        }
+    }
+
+    /// Collapses the corridors between the start, the end and every tile where paths
+    /// meet, dropping corridors that dead-end or that a slope won't let us walk.
+    pub fn compress(&self, slippery: bool) -> TrailGraph {
+        let mut junctions = vec![self.start, self.end];
+        junctions.extend(self.tiles.positions().filter(|position| {
+            self.is_open(*position)
+                && self
+                    .tiles
+                    .neighbours(*position)
+                    .filter(|next| self.is_open(*next))
+                    .count()
+                    > 2
+        }));
+
+        let edges = junctions
+            .iter()
+            .map(|from| {
+                self.exits(*from, slippery)
+                    .into_iter()
+                    .filter_map(|first| {
+                        let (mut previous, mut current, mut length) = (*from, first, 1);
+                        loop {
+                            if let Some(to) = junctions.iter().position(|j| *j == current) {
+                                return Some((to, length));
+                            }
+                            let next = self
+                                .exits(current, slippery)
+                                .into_iter()
+                                .find(|next| *next != previous)?;
+                            (previous, current, length) = (current, next, length + 1);
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        TrailGraph {
+            junctions,
+            edges,
+            start: 0,
+            end: 1,
+        }
+    }
+}
+
+impl TrailGraph {
+    /// The longest hike from start to end that never visits a junction twice.
+    pub fn longest_hike(&self) -> Option<usize> {
+        // Once at the only junction leading to the end the hike has to finish, or it
+        // could never get there.
+        let last = self
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|(_, edges)| edges.iter().any(|(to, _)| *to == self.end))
+            .map(|(from, _)| from)
+            .collect::<Vec<usize>>();
+        let last = match last[..] {
+            [last] => Some(last),
+            _ => None,
+        };
+
+        let mut visited = vec![false; self.junctions.len()];
+        self.longest_from(self.start, last, &mut visited)
+    }
+
+    fn longest_from(
+        &self,
+        from: usize,
+        last: Option<usize>,
+        visited: &mut [bool],
+    ) -> Option<usize> {
+        if from == self.end {
+            return Some(0);
+        }
+        if Some(from) == last {
+            return self.edges[from]
+                .iter()
+                .find(|(to, _)| *to == self.end)
+                .map(|(_, length)| *length);
+        }
+
+        visited[from] = true;
+        let longest = self.edges[from]
+            .iter()
+            .filter_map(|(to, length)| {
+                if visited[*to] {
+                    return None;
+                }
+                self.longest_from(*to, last, visited)
+                    .map(|rest| rest + length)
+            })
+            .max();
+        visited[from] = false;
+
+        longest
+    }
+}
+
+pub fn parse(input: &str) -> Result<Trails, ParseError> {
+    let tiles = Grid::parse(input, |c| {
+        matches!(c, '#' | '.' | '^' | '>' | 'v' | '<').then_some(c)
+    })?;
+    let gap = |y: usize| {
+        (0..tiles.width())
+            .map(|x| (x, y))
+            .find(|position| tiles[*position] == '.')
+            .ok_or_else(|| {
+                ParseError::new(
+                    "a gap in the wall",
+                    &tiles.row(y).iter().collect::<String>(),
+                )
+                .on_line(y + 1)
+            })
+    };
+
+    Ok(Trails {
+        start: gap(0)?,
+        end: gap(tiles.height().saturating_sub(1))?,
+        tiles,
+    })
+}
+
+pub fn part_1(trails: &Trails) -> usize {
+    let graph = trails.compress(true);
+    trace!("Slippery trails {:?}", graph);
+
+    graph.longest_hike().unwrap_or(0)
+}
+
+pub fn part_2(trails: &Trails) -> usize {
+    let graph = trails.compress(false);
+    trace!("Dry trails {:?}", graph);
+
+    graph.longest_hike().unwrap_or(0)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Trails;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::{example, example_answer};
+
+    #[test]
+    fn part_1_test() {
+        let input = parse(&example(2023, 23, 1)).unwrap();
+
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 23, 1, 1));
+    }
+
+    #[test]
+    fn part_2_test() {
+        let input = parse(&example(2023, 23, 1)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 23, 1, 2));
+    }
+
+    #[test]
+    fn compress_test() {
+        let trails = parse(&example(2023, 23, 1)).unwrap();
+        let graph = trails.compress(false);
+
+        // The start, the end and 7 places where paths meet.
+        assert_eq!(graph.junctions.len(), 9);
+        assert_eq!(graph.edges[graph.start].len(), 1);
+        // Every dry corridor can be walked both ways.
+        let edges: usize = graph.edges.iter().map(Vec::len).sum();
+        assert_eq!(edges % 2, 0);
+        assert!(
+            trails
+                .compress(true)
+                .edges
+                .iter()
+                .map(Vec::len)
+                .sum::<usize>()
+                < edges
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use crate::error::{parse_field, ParseError};
+use crate::solution::Solution;
+use std::fmt::Display;
+use std::ops::{Add, Mul, Sub};
+
+/// A 3D vector kept in `i128`, wide enough for the cross and dot products of puzzle
+/// positions in the hundreds of trillions.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct Vec3 {
+    pub x: i128,
+    pub y: i128,
+    pub z: i128,
+}
+
+impl Vec3 {
+    pub const fn new(x: i128, y: i128, z: i128) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+
+    pub fn dot(&self, other: Vec3) -> i128 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(&self, other: Vec3) -> Vec3 {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// Divides every coordinate by `divisor`, if it divides them all exactly.
+    fn exact_div(&self, divisor: i128) -> Option<Vec3> {
+        if divisor == 0 || self.x % divisor != 0 || self.y % divisor != 0 || self.z % divisor != 0 {
+            return None;
+        }
+        Some(Vec3::new(
+            self.x / divisor,
+            self.y / divisor,
+            self.z / divisor,
+        ))
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, rhs: Vec3) -> Vec3 {
+        Vec3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, rhs: Vec3) -> Vec3 {
+        Vec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Mul<i128> for Vec3 {
+    type Output = Vec3;
+
+    fn mul(self, rhs: i128) -> Vec3 {
+        Vec3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Hailstone {
+    pub position: Vec3,
+    pub velocity: Vec3,
+}
+
+impl Hailstone {
+    /// Whether the paths of `self` and `other` cross, ignoring `z`, ahead of both
+    /// hailstones and within `low..=high` on both axes. Worked in exact integers by
+    /// comparing numerators against the shared denominator.
+    pub fn paths_cross_within(&self, other: &Hailstone, low: i128, high: i128) -> bool {
+        let (p, v) = (self.position, self.velocity);
+        let (q, w) = (other.position, other.velocity);
+        let mut denominator = v.x * w.y - v.y * w.x;
+        if denominator == 0 {
+            return false;
+        }
+        // p + t * v == q + s * w, with t and s scaled by the denominator.
+        let d = q - p;
+        let mut t = d.x * w.y - d.y * w.x;
+        let mut s = d.x * v.y - d.y * v.x;
+        if denominator < 0 {
+            (denominator, t, s) = (-denominator, -t, -s);
+        }
+        if t < 0 || s < 0 {
+            return false;
+        }
+
+        let x = p.x * denominator + v.x * t;
+        let y = p.y * denominator + v.y * t;
+        let range = low * denominator..=high * denominator;
+        range.contains(&x) && range.contains(&y)
+    }
+}
+
+/// Counts the pairs of hailstones whose paths cross within the test area.
+pub fn crossings_within(hailstones: &[Hailstone], low: i128, high: i128) -> usize {
+    hailstones
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| hailstones[i + 1..].iter().map(move |b| (a, b)))
+        .filter(|(a, b)| a.paths_cross_within(b, low, high))
+        .count()
+}
+
+/// The time `hailstone` meets the plane through the origin spanned by the path of
+/// `other`, both relative to the frame where the origin stands still.
+fn plane_crossing(hailstone: &Hailstone, other: &Hailstone) -> Option<i128> {
+    let normal = other.position.cross(other.velocity);
+    let speed = hailstone.velocity.dot(normal);
+    let distance = -hailstone.position.dot(normal);
+    (speed != 0 && distance % speed == 0).then(|| distance / speed)
+}
+
+/// The rock's starting position and velocity, if a rock thrown in a straight line at
+/// whole times can hit every hailstone.
+///
+/// Seen from the first hailstone it stands still at the origin, so the rock's path
+/// runs through the origin and meets each other hailstone somewhere on the plane
+/// spanned by that hailstone's path and the origin. Crossing two hailstones with each
+/// other's planes gives both collision times, and from those the rock's path. All of
+/// it is exact integer arithmetic.
+pub fn throw_rock(hailstones: &[Hailstone]) -> Option<(Vec3, Vec3)> {
+    let (first, rest) = hailstones.split_first()?;
+    let relative: Vec<Hailstone> = rest
+        .iter()
+        .map(|h| Hailstone {
+            position: h.position - first.position,
+            velocity: h.velocity - first.velocity,
+        })
+        .collect();
+
+    for (i, a) in relative.iter().enumerate() {
+        for (j, b) in relative.iter().enumerate().skip(i + 1) {
+            let (Some(t_a), Some(t_b)) = (plane_crossing(a, b), plane_crossing(b, a)) else {
+                continue;
+            };
+            if t_a == t_b {
+                continue;
+            }
+            let hit_a = rest[i].position + rest[i].velocity * t_a;
+            let hit_b = rest[j].position + rest[j].velocity * t_b;
+            let Some(velocity) = (hit_b - hit_a).exact_div(t_b - t_a) else {
+                continue;
+            };
+            let position = hit_a - velocity * t_a;
+
+            if hailstones.iter().all(|h| hits(position, velocity, h)) {
+                return Some((position, velocity));
+            }
+        }
+    }
+
+    None
+}
+
+/// Whether a rock from `position` moving at `velocity` meets `hailstone` at a whole,
+/// non-negative time.
+fn hits(position: Vec3, velocity: Vec3, hailstone: &Hailstone) -> bool {
+    let offset = hailstone.position - position;
+    let closing = velocity - hailstone.velocity;
+    if offset.cross(closing) != Vec3::default() {
+        return false;
+    }
+    match [
+        (offset.x, closing.x),
+        (offset.y, closing.y),
+        (offset.z, closing.z),
+    ]
+    .into_iter()
+    .find(|(_, c)| *c != 0)
+    {
+        Some((o, c)) => o % c == 0 && o / c >= 0,
+        None => offset == Vec3::default(),
+    }
+}
+
+fn parse_vec3(line: &str, part: &str) -> Result<Vec3, ParseError> {
+    let mut coordinates = part.split(',').map(str::trim);
+    let mut next = || {
+        let coordinate = coordinates
+            .next()
+            .filter(|c| !c.is_empty())
+            .ok_or_else(|| ParseError::new("x, y, z", part).within(line, part))?;
+        parse_field(line, coordinate, "a number")
+    };
+
+    Ok(Vec3::new(next()?, next()?, next()?))
+}
+
+pub fn parse(input: &str) -> Result<Vec<Hailstone>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| {
+            line.split_once('@')
+                .ok_or_else(|| ParseError::new("a hailstone like 19, 13, 30 @ -2, 1, -2", line))
+                .and_then(|(position, velocity)| {
+                    Ok(Hailstone {
+                        position: parse_vec3(line, position)?,
+                        velocity: parse_vec3(line, velocity)?,
+                    })
+                })
+                .map_err(|e| e.on_line(index + 1))
+        })
+        .collect()
+}
+
+pub fn part_1(hailstones: &[Hailstone]) -> usize {
+    crossings_within(hailstones, 200_000_000_000_000, 400_000_000_000_000)
+}
+
+pub fn part_2(hailstones: &[Hailstone]) -> i128 {
+    throw_rock(hailstones).map_or(0, |(position, _)| position.x + position.y + position.z)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Hailstone>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::{example, example_answer};
+
+    #[test]
+    fn part_1_test() {
+        let input = parse(&example(2023, 24, 1)).unwrap();
+
+        // The example uses a test area of 7 to 27 on both axes.
+        assert_eq!(crossings_within(&input, 7, 27), 2);
+    }
+
+    #[test]
+    fn part_2_test() {
+        let input = parse(&example(2023, 24, 1)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 24, 1, 2));
+        assert_eq!(
+            throw_rock(&input),
+            Some((Vec3::new(24, 13, 10), Vec3::new(-3, 1, 2)))
+        );
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(
+            parse("20, 19, 15 @  1, -5, -3").unwrap(),
+            vec![Hailstone {
+                position: Vec3::new(20, 19, 15),
+                velocity: Vec3::new(1, -5, -3),
+            }]
+        );
+        assert_eq!(
+            parse("20, 19, 15 @  1, -5").unwrap_err(),
+            ParseError::new("x, y, z", "  1, -5").at(1, 13)
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use crate::error::ParseError;
+use crate::solution::Solution;
+use crate::trace;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+
+/// The wiring diagram as an undirected graph, components numbered in order of appearance.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Wiring {
+    pub names: Vec<String>,
+    pub neighbours: Vec<Vec<usize>>,
+}
+
+impl Wiring {
+    /// Splits the components in two by cutting at most `wires` wires, returning the
+    /// size of each group. Sends unit flows from the first component to each of the
+    /// others in turn: once one can't take more than `wires`, the components still
+    /// reachable through spare capacity form one side of the cut.
+    pub fn min_cut(&self, wires: usize) -> Option<(usize, usize)> {
+        (1..self.names.len()).find_map(|sink| {
+            let mut flow: HashSet<(usize, usize)> = HashSet::new();
+            for _ in 0..wires {
+                self.augment(0, sink, &mut flow)?;
+            }
+            if self.augment(0, sink, &mut flow).is_some() {
+                return None;
+            }
+
+            let side = self.residual_reach(0, &flow).len();
+            trace!(
+                "Cut {} wires between {} and {}",
+                wires,
+                self.names[0],
+                self.names[sink]
+            );
+            Some((side, self.names.len() - side))
+        })
+    }
+
+    /// Whether wire `from` to `to` has room for another unit of flow in that direction.
+    fn has_capacity(flow: &HashSet<(usize, usize)>, from: usize, to: usize) -> bool {
+        !flow.contains(&(from, to))
+    }
+
+    /// Pushes one unit of flow along a shortest path with spare capacity, if any.
+    fn augment(
+        &self,
+        source: usize,
+        sink: usize,
+        flow: &mut HashSet<(usize, usize)>,
+    ) -> Option<()> {
+        let mut previous: HashMap<usize, usize> = HashMap::from([(source, source)]);
+        let mut queue = VecDeque::from([source]);
+
+        while let Some(node) = queue.pop_front() {
+            if node == sink {
+                break;
+            }
+            for &next in &self.neighbours[node] {
+                if Self::has_capacity(flow, node, next) && !previous.contains_key(&next) {
+                    previous.insert(next, node);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let mut node = sink;
+        previous.get(&sink)?;
+        while node != source {
+            let from = previous[&node];
+            // Flow back along a used wire cancels it rather than using it twice.
+            if !flow.remove(&(node, from)) {
+                flow.insert((from, node));
+            }
+            node = from;
+        }
+        Some(())
+    }
+
+    fn residual_reach(&self, source: usize, flow: &HashSet<(usize, usize)>) -> HashSet<usize> {
+        let mut seen = HashSet::from([source]);
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            for &next in &self.neighbours[node] {
+                if Self::has_capacity(flow, node, next) && seen.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        seen
+    }
+}
+
+pub fn parse(input: &str) -> Result<Wiring, ParseError> {
+    let mut wiring = Wiring {
+        names: vec![],
+        neighbours: vec![],
+    };
+    let mut ids: HashMap<String, usize> = HashMap::new();
+    let mut id = |wiring: &mut Wiring, name: &str| {
+        *ids.entry(name.to_string()).or_insert_with(|| {
+            wiring.names.push(name.to_string());
+            wiring.neighbours.push(vec![]);
+            wiring.names.len() - 1
+        })
+    };
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Some((name, others)) = line.split_once(':').filter(|(name, _)| !name.is_empty()) else {
+            return Err(ParseError::new("a component like jqt: rhn xhk", line).on_line(index + 1));
+        };
+
+        let from = id(&mut wiring, name);
+        for other in others.split_whitespace() {
+            let to = id(&mut wiring, other);
+            wiring.neighbours[from].push(to);
+            wiring.neighbours[to].push(from);
+        }
+    }
+
+    Ok(wiring)
+}
+
+pub fn part_1(wiring: &Wiring) -> usize {
+    wiring.min_cut(3).map_or(0, |(a, b)| a * b)
+}
+
+pub fn part_2(_wiring: &Wiring) -> usize {
+    0
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Wiring;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::{example, example_answer};
+
+    #[test]
+    fn part_1_test() {
+        let input = parse(&example(2023, 25, 1)).unwrap();
+
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 25, 1, 1));
+    }
+
+    #[test]
+    fn min_cut_test() {
+        let wiring = parse(&example(2023, 25, 1)).unwrap();
+
+        assert_eq!(wiring.names.len(), 15);
+        assert_eq!(wiring.min_cut(2), None);
+        let (a, b) = wiring.min_cut(3).unwrap();
+        assert_eq!((a.min(b), a.max(b)), (6, 9));
+    }
+}
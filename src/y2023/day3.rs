@@ -1,3 +1,8 @@
+use crate::error::ParseError;
+use crate::solution::Solution;
+use crate::trace;
+use std::fmt::Display;
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 struct PartNumber {
     x1: i32,
@@ -20,20 +25,37 @@ struct Symbol {
     value: char,
 }
 
-fn parse_numbers(line: &String, y: usize) -> Vec<PartNumber> {
+/// The part numbers and symbols of each non-empty line of the engine schematic.
+#[derive(Debug, PartialEq)]
+pub struct Schematic {
+    part_numbers: Vec<Vec<PartNumber>>,
+    symbols: Vec<Vec<Symbol>>,
+}
+
+fn parse_numbers(line: &str, y: usize) -> Result<Vec<PartNumber>, ParseError> {
     let mut part_numbers: Vec<PartNumber> = vec![];
     let mut value: u32 = 0;
     let mut start_index: i32 = 0;
+    let mut start_offset = 0;
     let mut is_building: bool = false;
 
-    for (idx, char) in line.chars().enumerate() {
-        match char {
-            '0'..='9' => {
+    for (idx, (offset, char)) in line.char_indices().enumerate() {
+        match char.to_digit(10) {
+            Some(digit) => {
                 if !is_building {
                     start_index = idx as i32;
+                    start_offset = offset;
                 }
                 is_building = true;
-                value = value * 10 + char.to_digit(10).expect("This should've been a digit");
+                value = value
+                    .checked_mul(10)
+                    .and_then(|value| value.checked_add(digit))
+                    .ok_or_else(|| {
+                        let run = &line[start_offset..];
+                        let run =
+                            &run[..run.find(|c: char| !c.is_ascii_digit()).unwrap_or(run.len())];
+                        ParseError::new("a part number that fits in 32 bits", run).within(line, run)
+                    })?;
             }
             _ => {
                 if is_building {
@@ -53,17 +75,17 @@ fn parse_numbers(line: &String, y: usize) -> Vec<PartNumber> {
     if is_building {
         part_numbers.push(PartNumber {
             x1: start_index,
-            x2: (line.len() as i32) - 1,
+            x2: (line.chars().count() as i32) - 1,
             y,
             value,
             valid: false,
         });
     }
 
-    part_numbers
+    Ok(part_numbers)
 }
 
-fn parse_symbols(line: &String, y: usize) -> Vec<Symbol> {
+fn parse_symbols(line: &str, y: usize) -> Vec<Symbol> {
     line.chars()
         .enumerate()
         .filter_map(|(idx, char)| match char {
@@ -78,53 +100,72 @@ fn parse_symbols(line: &String, y: usize) -> Vec<Symbol> {
         .collect()
 }
 
-fn validate_row(row: &Vec<PartNumber>, symbol: &Symbol, _y: usize) -> Vec<PartNumber> {
+fn validate_row(row: &[PartNumber], symbol: &Symbol, y: usize) -> Vec<PartNumber> {
     let valid: Vec<PartNumber> = row
         .iter()
         .map(|part_number| {
             if validate_part_number(part_number, symbol) {
-                let mut pn = part_number.clone();
+                let mut pn = *part_number;
                 pn.validate();
-                // println!("updated pn {:?}", pn);
+                trace!("updated pn {:?}", pn);
                 return pn;
             }
-            part_number.clone()
+            *part_number
         })
         .collect();
 
-    // println!("Valid part numbers {:?} for line {}", valid, y);
+    trace!("Valid part numbers {:?} for line {}", valid, y);
 
     valid
 }
 
 fn validate_part_number(part_number: &PartNumber, symbol: &Symbol) -> bool {
     let is_valid = part_number.x1 - 1 <= symbol.x && symbol.x <= part_number.x2 + 1;
-    // println!("is_valid {} {:?} {:?}", is_valid, part_number, symbol);
+    trace!("is_valid {} {:?} {:?}", is_valid, part_number, symbol);
     is_valid
 }
 
-pub fn part_1(input: &Vec<String>) -> i32 {
-    let mut part_numbers: Vec<Vec<PartNumber>> = input
-        .iter()
-        .enumerate()
-        .map(|(y, line)| parse_numbers(line, y))
-        .collect();
-    let symbols: Vec<Vec<Symbol>> = input
-        .iter()
-        .enumerate()
-        .map(|(y, line)| parse_symbols(line, y))
-        .collect();
+/// Reads the schematic row by row, skipping blank lines. Lines are trimmed so
+/// indented test input lines up the same as a real input.
+pub fn parse(input: &str) -> Result<Schematic, ParseError> {
+    let mut schematic = Schematic {
+        part_numbers: vec![],
+        symbols: vec![],
+    };
+
+    for (index, line) in input.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let y = schematic.part_numbers.len();
+        let indent = line[..line.len() - line.trim_start().len()].chars().count();
+        let numbers = parse_numbers(trimmed, y).map_err(|e| {
+            let column = e.column + indent;
+            e.at(index + 1, column)
+        })?;
+        schematic.part_numbers.push(numbers);
+        schematic.symbols.push(parse_symbols(trimmed, y));
+    }
+
+    Ok(schematic)
+}
+
+pub fn part_1(input: &Schematic) -> i32 {
+    let mut part_numbers = input.part_numbers.clone();
+    let symbols = &input.symbols;
 
     for (y, line) in symbols.iter().enumerate() {
         for symbol in line {
             if y > 0 {
-                // println!("Testing {:?} symbol above {}", symbol, y - 1);
+                trace!("Testing {:?} symbol above {}", symbol, y - 1);
                 part_numbers[y - 1] = validate_row(&part_numbers[y - 1], symbol, y);
             }
-            // println!("Testing {:?} symbol at {}", symbol, y);
+            trace!("Testing {:?} symbol at {}", symbol, y);
             part_numbers[y] = validate_row(&part_numbers[y], symbol, y);
-            if y + 1 < input.len() {
-                // println!("Testing {:?} symbol below {}", symbol, y + 1);
+            if y + 1 < part_numbers.len() {
+                trace!("Testing {:?} symbol below {}", symbol, y + 1);
                 part_numbers[y + 1] = validate_row(&part_numbers[y + 1], symbol, y);
             }
         }
@@ -139,11 +180,6 @@ pub fn part_1(input: &Vec<String>) -> i32 {
                 .map(|pn| pn.value as i32)
                 .collect()
         })
-        .enumerate()
-        .map(|(_idx, pns)| {
-            // println!("Valid at line {}: {:?}", idx + 1, pns);
-            pns
-        })
         .collect();
 
     valid_numbers.iter().fold(0, |acc, part_numbers| {
@@ -155,17 +191,9 @@ pub fn part_1(input: &Vec<String>) -> i32 {
     })
 }
 
-pub fn part_2(input: &Vec<String>) -> i32 {
-    let part_numbers: Vec<Vec<PartNumber>> = input
-        .iter()
-        .enumerate()
-        .map(|(y, line)| parse_numbers(line, y))
-        .collect();
-    let symbols: Vec<Vec<Symbol>> = input
-        .iter()
-        .enumerate()
-        .map(|(y, line)| parse_symbols(line, y))
-        .collect();
+pub fn part_2(input: &Schematic) -> i32 {
+    let part_numbers = &input.part_numbers;
+    let symbols = &input.symbols;
 
     let mut gear_ratios = 0;
     for (y, line) in symbols.iter().enumerate() {
@@ -177,35 +205,36 @@ pub fn part_2(input: &Vec<String>) -> i32 {
             let mut possible_parts: Vec<PartNumber> = vec![];
 
             if y > 0 {
-                // println!("Testing {:?} symbol above {}", symbol, y - 1);
+                trace!("Testing {:?} symbol above {}", symbol, y - 1);
                 let valid: Vec<_> = part_numbers[y - 1]
                     .iter()
                     .filter(|pn| validate_part_number(pn, symbol))
-                    .map(|pn| *pn)
+                    .copied()
                     .collect();
                 possible_parts.extend(valid);
             }
-            // println!("Testing {:?} symbol at {}", symbol, y);
+            trace!("Testing {:?} symbol at {}", symbol, y);
             let valid: Vec<_> = part_numbers[y]
                 .iter()
                 .filter(|pn| validate_part_number(pn, symbol))
-                .map(|pn| *pn)
+                .copied()
                 .collect();
             possible_parts.extend(valid);
 
-            if y + 1 < input.len() {
+            if y + 1 < part_numbers.len() {
                 let valid: Vec<_> = part_numbers[y + 1]
                     .iter()
                     .filter(|pn| validate_part_number(pn, symbol))
-                    .map(|pn| *pn)
+                    .copied()
                     .collect();
                 possible_parts.extend(valid);
             }
 
-            // println!(
-            //     "Possible parts for symbol {:?}: {:?}",
-            //     symbol, possible_parts
-            // );
+            trace!(
+                "Possible parts for symbol {:?}: {:?}",
+                symbol,
+                possible_parts
+            );
             if possible_parts.len() == 2 {
                 gear_ratios += possible_parts[0].value * possible_parts[1].value
             }
@@ -215,25 +244,34 @@ pub fn part_2(input: &Vec<String>) -> i32 {
     gear_ratios as i32
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Schematic;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = "467..114..
-        ...*......
-        ..35..633.
-        ......#...
-        617*......
-        .....+.58.
-        ..592.....
-        ......755.
-        ...$.*....
-        .664.598..";
-
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_1(&lines), 4361)
+        let input = parse(&example(2023, 3, 1)).unwrap();
+
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 3, 1, 1));
     }
 
     #[test]
@@ -242,8 +280,7 @@ mod tests {
         ..1...
         ..#...";
 
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_1(&lines), 1)
+        assert_eq!(part_1(&parse(input).unwrap()), 1)
     }
 
     #[test]
@@ -251,8 +288,7 @@ mod tests {
         let input = "#...
         1...";
 
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_1(&lines), 1)
+        assert_eq!(part_1(&parse(input).unwrap()), 1)
     }
 
     #[test]
@@ -260,8 +296,7 @@ mod tests {
         let input = "1...
         #...";
 
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_1(&lines), 1)
+        assert_eq!(part_1(&parse(input).unwrap()), 1)
     }
 
     #[test]
@@ -269,8 +304,7 @@ mod tests {
         let input = "...1
         ...#";
 
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_1(&lines), 1)
+        assert_eq!(part_1(&parse(input).unwrap()), 1)
     }
 
     #[test]
@@ -279,7 +313,7 @@ mod tests {
 
         assert_eq!(
             parse_numbers(&input, 1),
-            vec![
+            Ok(vec![
                 PartNumber {
                     x1: 0,
                     x2: 2,
@@ -308,10 +342,18 @@ mod tests {
                     value: 9,
                     valid: false,
                 }
-            ]
+            ])
         )
     }
 
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse("..1\n  .99999999999.").unwrap_err(),
+            ParseError::new("a part number that fits in 32 bits", "99999999999").at(2, 4)
+        );
+    }
+
     #[test]
     fn parse_symbols_test() {
         let input = String::from("123...456..90$");
@@ -387,18 +429,8 @@ mod tests {
 
     #[test]
     fn part_2_test() {
-        let input = r#"467..114..
-            ...*......
-            ..35..633.
-            ......#...
-            617*......
-            .....+.58.
-            ..592.....
-            ......755.
-            ...$.*....
-            .664.598.."#;
-
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_2(&lines), 467835)
+        let input = parse(&example(2023, 3, 1)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 3, 1, 2));
     }
 }
@@ -1,5 +1,9 @@
+use crate::error::{parse_field, ParseError};
+use crate::solution::Solution;
 use std::cmp;
 use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::str::FromStr;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct ScratchCard {
@@ -10,53 +14,42 @@ pub struct ScratchCard {
 }
 
 impl ScratchCard {
-    fn new(line: &str) -> Self {
-        let mut pieces = line.split(":");
-        let id: u32 = pieces
-            .next()
-            .unwrap()
-            .split(" ")
-            .last()
-            .unwrap()
-            .parse()
-            .expect("Should have id");
-        let pieces = pieces
-            .next()
-            .unwrap()
-            .split("|")
-            .map(|str| str.trim())
-            .collect::<Vec<&str>>();
-        let raw_winning_numbers = pieces[0];
-        let raw_numbers = pieces[1];
+    /// Parses a card like `Card 1: 41 48 | 83 86`, placing errors within `line`.
+    fn parse(line: &str) -> Result<Self, ParseError> {
+        let trimmed = line.trim();
+        let (card, pieces) = trimmed.split_once(':').ok_or_else(|| {
+            ParseError::new("a card like Card 1: 41 48 | 83 86", trimmed).within(line, trimmed)
+        })?;
+        let id: u32 = card
+            .strip_prefix("Card")
+            .ok_or_else(|| ParseError::new("Card and its number", card).within(line, card))
+            .and_then(|id| parse_field(line, id.trim(), "a card number"))?;
+        let (raw_winning_numbers, raw_numbers) = pieces.split_once('|').ok_or_else(|| {
+            let pieces = pieces.trim();
+            ParseError::new("winning numbers | numbers", pieces).within(line, pieces)
+        })?;
 
         let mut winning_numbers = HashSet::new();
         let mut numbers = HashSet::new();
         let mut matched = 0;
 
-        for raw_number in raw_winning_numbers.trim().split(" ") {
-            let res = match raw_number.trim().parse::<i32>() {
-                Ok(number) => number,
-                Err(_) => continue,
-            };
-            winning_numbers.insert(res);
+        for raw_number in raw_winning_numbers.split_whitespace() {
+            winning_numbers.insert(parse_field(line, raw_number, "a winning number")?);
         }
-        for raw_number in raw_numbers.trim().split(" ") {
-            let res = match raw_number.trim().parse::<i32>() {
-                Ok(number) => number,
-                Err(_) => continue,
-            };
+        for raw_number in raw_numbers.split_whitespace() {
+            let res = parse_field(line, raw_number, "a number")?;
             numbers.insert(res);
             if winning_numbers.contains(&res) {
                 matched += 1;
             }
         }
 
-        ScratchCard {
+        Ok(ScratchCard {
             winning_numbers,
             numbers,
             matched,
             id,
-        }
+        })
     }
 
     fn score(self) -> u32 {
@@ -64,7 +57,7 @@ impl ScratchCard {
             return 0;
         }
 
-        u32::pow(2, self.matched - 1)
+        2u32.saturating_pow(self.matched - 1)
     }
 
     fn winnings(self, max: u32) -> Vec<u32> {
@@ -73,34 +66,48 @@ impl ScratchCard {
     }
 }
 
-pub fn part_1(input: &Vec<String>) -> u32 {
-    input
-        .iter()
-        .map(|line| ScratchCard::new(line).score())
-        .sum()
+impl FromStr for ScratchCard {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ScratchCard::parse(s)
+    }
+}
+
+/// One card per non-empty line, numbered from 1 in order.
+pub fn parse(input: &str) -> Result<Vec<ScratchCard>, ParseError> {
+    let mut scratch_cards: Vec<ScratchCard> = vec![];
+
+    for (index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let card = ScratchCard::parse(line).map_err(|e| e.on_line(index + 1))?;
+        if card.id as usize != scratch_cards.len() + 1 {
+            let trimmed = line.trim();
+            return Err(ParseError::new("the next card in order", trimmed)
+                .on_line(index + 1)
+                .within(line, trimmed));
+        }
+        scratch_cards.push(card);
+    }
+
+    Ok(scratch_cards)
+}
+
+pub fn part_1(input: &[ScratchCard]) -> u32 {
+    input.iter().map(|sc| sc.clone().score()).sum()
 }
 
-pub fn part_2(input: &Vec<String>) -> u32 {
-    let scratch_cards: Vec<_> = input.iter().map(|line| ScratchCard::new(line)).collect();
+pub fn part_2(scratch_cards: &[ScratchCard]) -> u32 {
     let scrath_card_ids: Vec<_> = scratch_cards.iter().map(|sc| sc.id).collect();
-    let mut scratch_winnings: HashMap<u32, Vec<u32>> = HashMap::new();
-    scratch_cards.iter().for_each(|sc| {
-        scratch_winnings.insert(
-            sc.id,
-            sc.clone()
-                .winnings(u32::try_from(input.len()).unwrap() - 1 + 999),
-        );
-    });
 
     let mut memo: HashMap<u32, u32> = HashMap::new();
-    let max = scratch_cards.last().expect("It exists").id;
+    let max = scratch_cards.last().map_or(0, |sc| sc.id);
 
     for sc in scratch_cards.iter().rev() {
         let winnings = sc.clone().winnings(max);
-        let score: u32 = winnings
-            .iter()
-            .map(|x| memo.get(x).expect("hmm missing"))
-            .sum();
+        let score: u32 = winnings.iter().map(|x| memo.get(x).unwrap_or(&0)).sum();
         memo.insert(sc.id, score + 1);
     }
 
@@ -110,21 +117,34 @@ pub fn part_2(input: &Vec<String>) -> u32 {
         .sum()
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<ScratchCard>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-        Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-        Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-        Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-        Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-        Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_1(&lines), 13);
+        let input = parse(&example(2023, 4, 1)).unwrap();
+
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 4, 1, 1));
         let v: Vec<u32> = vec![];
         assert_eq!(v.iter().sum::<u32>(), 0);
     }
@@ -134,40 +154,74 @@ mod tests {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
 
         assert_eq!(
-            ScratchCard::new(input),
-            ScratchCard {
+            input.parse(),
+            Ok(ScratchCard {
                 winning_numbers: HashSet::from_iter(vec![41, 48, 83, 86, 17]),
                 numbers: HashSet::from_iter(vec![83, 86, 6, 31, 17, 9, 48, 53]),
                 matched: 4,
                 id: 1,
-            }
+            })
         )
     }
 
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse("Card 1: 41 48 | 83 x6").unwrap_err(),
+            ParseError::new("a number", "x6").at(1, 20)
+        );
+        assert_eq!(
+            parse("Card 1: 41 48 | 83\nCard 3: 41 | 1").unwrap_err(),
+            ParseError::new("the next card in order", "Card 3: 41 | 1").on_line(2)
+        );
+        assert_eq!(
+            parse("Card 1: 41 48 83").unwrap_err(),
+            ParseError::new("winning numbers | numbers", "41 48 83").at(1, 9)
+        );
+    }
+
     #[test]
     fn scratch_card_score() {
         assert_eq!(
-            ScratchCard::new("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").score(),
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"
+                .parse::<ScratchCard>()
+                .unwrap()
+                .score(),
             8
         );
         assert_eq!(
-            ScratchCard::new("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19").score(),
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19"
+                .parse::<ScratchCard>()
+                .unwrap()
+                .score(),
             2
         );
         assert_eq!(
-            ScratchCard::new("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1").score(),
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1"
+                .parse::<ScratchCard>()
+                .unwrap()
+                .score(),
             2
         );
         assert_eq!(
-            ScratchCard::new("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83").score(),
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83"
+                .parse::<ScratchCard>()
+                .unwrap()
+                .score(),
             1
         );
         assert_eq!(
-            ScratchCard::new("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36").score(),
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36"
+                .parse::<ScratchCard>()
+                .unwrap()
+                .score(),
             0
         );
         assert_eq!(
-            ScratchCard::new("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11").score(),
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
+                .parse::<ScratchCard>()
+                .unwrap()
+                .score(),
             0
         );
     }
@@ -175,25 +229,25 @@ mod tests {
     #[test]
     fn winnings() {
         assert_eq!(
-            ScratchCard::new("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").winnings(10),
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"
+                .parse::<ScratchCard>()
+                .unwrap()
+                .winnings(10),
             vec![2, 3, 4, 5]
         );
         assert_eq!(
-            ScratchCard::new("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").winnings(3),
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"
+                .parse::<ScratchCard>()
+                .unwrap()
+                .winnings(3),
             vec![2, 3]
         );
     }
 
     #[test]
     fn part_2_test() {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-        Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-        Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-        Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-        Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-        Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_2(&lines), 30)
+        let input = parse(&example(2023, 4, 1)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 4, 1, 2));
     }
 }
@@ -1,53 +1,9 @@
-use std::cmp;
-use std::sync::mpsc;
-use std::thread;
-use std::time::Instant;
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Range {
-    start: u64,
-    end: u64,
-    size: u64,
-}
-
-impl Range {
-    fn new(start: u64, end: u64) -> Range {
-        Range {
-            start,
-            end,
-            size: end - start,
-        }
-    }
-
-    fn _split(self, index: u64) -> (Range, Range) {
-        (
-            Range {
-                start: self.start,
-                end: index,
-                size: index - self.start,
-            },
-            Range {
-                start: index + 1,
-                end: self.end,
-                size: self.end - (index + 1),
-            },
-        )
-    }
-}
-
-impl Iterator for Range {
-    type Item = u64;
-
-    fn next(&mut self) -> Option<u64> {
-        if self.start == self.end {
-            None
-        } else {
-            let result = Some(self.start);
-            self.start += 1;
-            result
-        }
-    }
-}
+use crate::error::{parse_field, ParseError};
+use crate::interval::{Interval, IntervalSet};
+use crate::solution::{or_dash, Solution};
+use crate::trace;
+use std::fmt::Display;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Map {
@@ -65,6 +21,18 @@ impl Map {
         let delta = number - self.from;
         Some(self.to + delta)
     }
+
+    fn source(self) -> Interval<u64> {
+        Interval::new(self.from, self.from + self.length)
+    }
+
+    /// Converts an interval lying inside `source()`.
+    fn shift(self, interval: Interval<u64>) -> Interval<u64> {
+        Interval::new(
+            interval.start - self.from + self.to,
+            interval.end - self.from + self.to,
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -81,15 +49,16 @@ impl Mapper {
         }
     }
 
-    fn add_map(&mut self, line: &str) -> &mut Self {
-        let mut pieces = line.split(" ").map(|n| n.parse::<u64>().unwrap());
-        let map = Map {
-            to: pieces.next().unwrap_or(0),
-            from: pieces.next().unwrap_or(0),
-            length: pieces.next().unwrap_or(0),
+    fn add_map(&mut self, line: &str) -> Result<&mut Self, ParseError> {
+        let numbers = line
+            .split_whitespace()
+            .map(|n| parse_field(line, n, "a number"))
+            .collect::<Result<Vec<u64>, _>>()?;
+        let [to, from, length] = numbers[..] else {
+            return Err(ParseError::new("three numbers", line));
         };
-        self.maps.push(map);
-        self
+        self.maps.push(Map { from, to, length });
+        Ok(self)
     }
 
     fn convert(&self, seed: u64) -> u64 {
@@ -98,8 +67,8 @@ impl Mapper {
             .iter()
             .filter_map(|map| map.convert(seed))
             .collect();
-        if result.len() == 0 {
-            // println!("{} seed {} not converted", self._name, seed);
+        if result.is_empty() {
+            trace!("{} seed {} not converted", self._name, seed);
             return seed;
         } else if result.len() > 1 {
             panic!(
@@ -107,188 +76,152 @@ impl Mapper {
                 seed, self
             );
         }
-        // println!("{} seed {} converted to {}", self._name, seed, result[0]);
+        trace!("{} seed {} converted to {}", self._name, seed, result[0]);
         result[0]
     }
+
+    /// Converts every seed in `seeds` at once, one interval at a time.
+    fn convert_set(&self, seeds: &IntervalSet<u64>) -> IntervalSet<u64> {
+        let mut unmapped = seeds.clone();
+        let mut mapped = IntervalSet::new();
+        for map in &self.maps {
+            let source = IntervalSet::from(map.source());
+            for interval in unmapped.intersection(&source).iter() {
+                mapped.insert(map.shift(*interval));
+            }
+            unmapped = unmapped.difference(&source);
+        }
+        mapped.union(&unmapped)
+    }
+}
+
+/// The seeds line followed by blank-line separated maps, each under a `... map:` header.
+#[derive(Debug, Clone)]
+pub struct Almanac {
+    seeds: Vec<u64>,
+    mappers: Vec<Mapper>,
 }
 
-pub fn build_mappers(mut iter: std::slice::Iter<'_, String>) -> Vec<Mapper> {
-    let mut mappers: Vec<Option<Mapper>> = vec![];
+/// Builds the mappers from numbered lines, reporting errors on the input line they
+/// came from.
+pub fn build_mappers<'a>(
+    lines: impl Iterator<Item = (usize, &'a str)>,
+) -> Result<Vec<Mapper>, ParseError> {
+    let mut mappers: Vec<Mapper> = vec![];
     let mut mapper: Option<Mapper> = None;
-    loop {
-        if let Some(line) = iter.next() {
-            if line == "" {
-                // println!("mapper {:?}", mapper);
-                mappers.push(mapper);
-                mapper = None;
-                continue;
-            }
+    for (number, line) in lines {
+        if line.is_empty() {
+            mappers.extend(mapper.take());
+            continue;
+        }
 
-            if let Some(ref mut m) = mapper {
-                m.add_map(line);
-            } else {
-                mapper = Some(Mapper::new(line))
-            }
+        if let Some(ref mut m) = mapper {
+            m.add_map(line).map_err(|e| e.on_line(number))?;
+        } else if line.ends_with("map:") {
+            mapper = Some(Mapper::new(line))
         } else {
-            mappers.push(mapper);
-            break;
+            return Err(ParseError::new("a map header", line).on_line(number));
         }
     }
+    mappers.extend(mapper);
 
-    mappers.into_iter().flatten().collect()
+    Ok(mappers)
 }
 
-pub fn get_location(seed: u64, mappers: &Vec<Mapper>) -> u64 {
+impl FromStr for Almanac {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().map(str::trim).enumerate();
+        let first = lines.next().map_or("", |(_, line)| line);
+        let Some(numbers) = first.strip_prefix("seeds:") else {
+            return Err(ParseError::new("a list of seeds", first));
+        };
+        let fields: Vec<&str> = numbers.split_whitespace().collect();
+        let seeds = fields
+            .iter()
+            .map(|n| parse_field(first, n, "a seed number"))
+            .collect::<Result<_, _>>()?;
+        // Part 2 reads the seeds as start and length pairs.
+        if let [unpaired] = *fields.chunks_exact(2).remainder() {
+            return Err(
+                ParseError::new("a range length after the seed", unpaired).within(first, unpaired)
+            );
+        }
+        let mappers = build_mappers(lines.map(|(index, line)| (index + 1, line)))?;
+
+        Ok(Almanac { seeds, mappers })
+    }
+}
+
+pub fn get_location(seed: u64, mappers: &[Mapper]) -> u64 {
     mappers.iter().fold(seed, |acc, val| val.convert(acc))
 }
 
-pub fn get_locations(seeds: Vec<u64>, mappers: Vec<Mapper>) -> Vec<u64> {
+pub fn get_locations(seeds: &[u64], mappers: &[Mapper]) -> Vec<u64> {
     seeds
         .iter()
-        .map(|seed| get_location(*seed, &mappers))
+        .map(|seed| get_location(*seed, mappers))
         .collect()
 }
 
-pub fn smallest(locations: Vec<u64>) -> u64 {
-    locations
-        .iter()
-        .fold(locations[0], |acc, val| cmp::min(acc, *val))
-}
+/// The lowest location, or None without any seeds.
+pub fn part_1(almanac: &Almanac) -> Option<u64> {
+    let locations = get_locations(&almanac.seeds, &almanac.mappers);
+    trace!("locations {:?}", locations);
+    let smallest = locations.into_iter().min();
 
-pub fn part_1(input: &Vec<String>) -> u64 {
-    let mut iter = input.iter();
-    let seeds: Vec<_> = iter
-        .next()
-        .unwrap()
-        .split(":")
-        .last()
-        .unwrap()
-        .trim()
-        .split(" ")
-        .map(|n| n.parse::<u64>().unwrap_or(0))
-        .collect();
-
-    // println!("Seeds {:?}", seeds);
-    iter.next();
-    let mappers = build_mappers(iter);
-
-    let locations = get_locations(seeds, mappers);
-    // println!("locations {:?}", locations);
-    let smallest = smallest(locations);
-
-    // println!("smallest location {}", smallest);
+    trace!("smallest location {:?}", smallest);
     smallest
 }
 
-pub fn build_ranges(seed_ranges: Vec<u64>) -> Vec<Range> {
-    let mut ranges: Vec<Range> = vec![];
-    for i in (0..seed_ranges.len()).step_by(2) {
-        ranges.push(Range::new(
-            seed_ranges[i],
-            seed_ranges[i] + seed_ranges[i + 1],
-        ));
-    }
-    ranges
+/// Reads `seed_ranges` as start and length pairs, ignoring an unpaired last number.
+pub fn build_ranges(seed_ranges: &[u64]) -> Vec<Interval<u64>> {
+    seed_ranges
+        .chunks_exact(2)
+        .map(|pair| Interval::new(pair[0], pair[0].saturating_add(pair[1])))
+        .collect()
 }
 
-pub fn part_2(input: &Vec<String>) -> u64 {
-    let mut iter = input.iter();
-    let seed_ranges: Vec<_> = iter
-        .next()
-        .unwrap()
-        .split(":")
-        .last()
-        .unwrap()
-        .trim()
-        .split(" ")
-        .map(|n| n.parse::<u64>().unwrap_or(0))
-        .collect();
-
-    let ranges: Vec<Range> = build_ranges(seed_ranges);
-    println!("Ranges {:?}", ranges);
-    let mappers = build_mappers(iter);
-
-    let mut smallest_location: u64 = u64::MAX;
-    let total = ranges.iter().fold(0, |acc, val| acc + val.size);
-    let now = Instant::now();
-    println!("Starting computation of {} seeds", total);
-    let mut count: u64 = 0;
-
-    let (tx, rx) = mpsc::channel();
-    for range in ranges {
-        let tx = tx.clone();
-        let mappers = mappers.clone();
-
-        thread::spawn(move || {
-            for seed in range {
-                let location = get_location(seed, &mappers);
-                tx.send(location).unwrap();
-            }
-            drop(tx)
-        });
+/// The lowest location of any seed in the ranges, or None without any seeds.
+pub fn part_2(almanac: &Almanac) -> Option<u64> {
+    let seeds: IntervalSet<u64> = build_ranges(&almanac.seeds).into_iter().collect();
+
+    almanac
+        .mappers
+        .iter()
+        .fold(seeds, |acc, mapper| mapper.convert_set(&acc))
+        .min()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Almanac;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
     }
-    drop(tx);
-
-    for location in rx {
-        if count % 100000 == 0 {
-            println!(
-                "Elapsed time: {:?}, count: {}/{}, percentage: {:05.2}%, smallest_location: {}",
-                now.elapsed(),
-                count,
-                total,
-                count / total,
-                smallest_location
-            );
-        }
-        smallest_location = cmp::min(smallest_location, location);
-        count += 1;
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        or_dash(part_1(input))
     }
 
-    smallest_location
+    fn part_2(input: &Self::Input) -> impl Display {
+        or_dash(part_2(input))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = "seeds: 79 14 55 13
-
-        seed-to-soil map:
-        52 50 48
-        50 98 2
-        
-        soil-to-fertilizer map:
-        37 52 2
-        39 0 15
-        0 15 37
-        
-        fertilizer-to-water map:
-        0 11 42
-        49 53 8
-        42 0 7
-        57 7 4
-        
-        water-to-light map:
-        88 18 7
-        18 25 70
-        
-        light-to-temperature map:
-        45 77 23
-        81 45 19
-        68 64 13
-        
-        temperature-to-humidity map:
-        0 69 1
-        1 0 69
-        
-        humidity-to-location map:
-        60 56 37
-        56 93 4";
+        let input: Almanac = example(2023, 5, 1).parse().unwrap();
 
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_1(&lines), 35);
+        assert_eq!(or_dash(part_1(&input)), example_answer(2023, 5, 1, 1));
     }
 
     #[test]
@@ -299,8 +232,8 @@ mod tests {
         let mut lines = input.lines().map(|l| l.trim());
 
         let mut mapper = Mapper::new(lines.next().unwrap());
-        mapper.add_map(lines.next().unwrap());
-        mapper.add_map(lines.next().unwrap());
+        mapper.add_map(lines.next().unwrap()).unwrap();
+        mapper.add_map(lines.next().unwrap()).unwrap();
 
         assert_eq!(mapper.clone().convert(98), 50);
         assert_eq!(mapper.clone().convert(99), 51);
@@ -309,6 +242,24 @@ mod tests {
         assert_eq!(mapper.clone().convert(50), 52);
     }
 
+    #[test]
+    fn convert_set_test() {
+        let mut mapper = Mapper::new("seed-to-soil map:");
+        mapper.add_map("50 98 2").unwrap();
+        mapper.add_map("52 50 48").unwrap();
+
+        let seeds: IntervalSet<u64> = [Interval::new(45, 55), Interval::new(97, 101)]
+            .into_iter()
+            .collect();
+        let converted = mapper.convert_set(&seeds);
+
+        assert_eq!(
+            converted.iter().copied().collect::<Vec<_>>(),
+            vec![Interval::new(45, 57), Interval::new(99, 101)]
+        );
+        assert_eq!(converted.len(), seeds.len());
+    }
+
     #[test]
     fn part_1_test2() {
         let input = "seeds: 222541566 218404460 670428364 432472902 2728902838 12147727 3962570697 52031641 2849288350 113747257 3648852659 73423293 4036058422 190602154 1931540843 584314999 3344622241 180428346 1301166628 310966761
@@ -554,73 +505,59 @@ mod tests {
         2087721389 2835070776 93923407
         1382789085 946284271 103970355";
 
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_1(&lines), 379811651)
+        assert_eq!(part_1(&input.parse().unwrap()), Some(379811651))
     }
 
     #[test]
     fn build_ranges_test() {
         let input: Vec<u64> = vec![0, 5, 10, 10, 20, 20];
 
+        assert_eq!(build_ranges(&input[..5]), build_ranges(&input[..4]));
         assert_eq!(
-            build_ranges(input),
+            build_ranges(&input),
             vec![
-                Range {
-                    start: 0,
-                    end: 5,
-                    size: 5,
-                },
-                Range {
-                    start: 10,
-                    end: 20,
-                    size: 10,
-                },
-                Range {
-                    start: 20,
-                    end: 40,
-                    size: 20
-                }
+                Interval::new(0, 5),
+                Interval::new(10, 20),
+                Interval::new(20, 40)
             ]
         );
     }
 
     #[test]
     fn part_2_test() {
-        let input = "seeds: 79 14 55 13
+        let input: Almanac = example(2023, 5, 1).parse().unwrap();
 
-        seed-to-soil map:
-        52 50 48
-        50 98 2
-        
-        soil-to-fertilizer map:
-        37 52 2
-        39 0 15
-        0 15 37
-        
-        fertilizer-to-water map:
-        0 11 42
-        49 53 8
-        42 0 7
-        57 7 4
-        
-        water-to-light map:
-        88 18 7
-        18 25 70
-        
-        light-to-temperature map:
-        45 77 23
-        81 45 19
-        68 64 13
-        
-        temperature-to-humidity map:
-        0 69 1
-        1 0 69
-        
-        humidity-to-location map:
-        60 56 37
-        56 93 4";
+        assert_eq!(or_dash(part_2(&input)), example_answer(2023, 5, 1, 2));
+    }
 
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_2(&lines), 46);
+    #[test]
+    fn no_seeds_test() {
+        let input: Almanac = "seeds:\n\nseed-to-soil map:\n50 98 2".parse().unwrap();
+
+        assert_eq!(part_1(&input), None);
+        assert_eq!(part_2(&input), None);
+        assert_eq!(Solver::part_1(&input).to_string(), "-");
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            "seeds: 79 1x".parse::<Almanac>().unwrap_err(),
+            ParseError::new("a seed number", "1x").at(1, 11)
+        );
+        assert_eq!(
+            "seeds: 79 14 55".parse::<Almanac>().unwrap_err(),
+            ParseError::new("a range length after the seed", "55").at(1, 14)
+        );
+        assert_eq!(
+            "seeds: 79 14\n\nseed-to-soil map:\n52 50"
+                .parse::<Almanac>()
+                .unwrap_err(),
+            ParseError::new("three numbers", "52 50").at(4, 1)
+        );
+        assert_eq!(
+            "seeds: 79 14\n\n52 50 48".parse::<Almanac>().unwrap_err(),
+            ParseError::new("a map header", "52 50 48").at(3, 1)
+        );
     }
 }
@@ -1,3 +1,6 @@
+use crate::error::{parse_field, ParseError};
+use crate::solution::Solution;
+use std::fmt::Display;
 use std::iter::zip;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -9,7 +12,10 @@ struct Race {
 
 impl Race {
     fn new(time: u64, distance: u64) -> Race {
-        let winning_numbers: Vec<u64> = (0..time).filter(|t| t * (time - t) > distance).collect();
+        // A product too big for u64 beats any record.
+        let winning_numbers: Vec<u64> = (0..time)
+            .filter(|t| t.checked_mul(time - t).is_none_or(|d| d > distance))
+            .collect();
         Race {
             time,
             distance,
@@ -18,77 +24,106 @@ impl Race {
     }
 }
 
-pub fn part_1(input: &Vec<String>) -> usize {
-    let mut iter = input.iter();
-    let times: Vec<_> = iter
-        .next()
-        .unwrap()
-        .split(":")
-        .last()
-        .unwrap()
-        .trim()
-        .split_whitespace()
-        .map(|n| n.parse::<u64>().unwrap_or(0))
-        .collect();
-    let distances: Vec<_> = iter
-        .next()
-        .unwrap()
-        .split(":")
-        .last()
-        .unwrap()
-        .trim()
-        .split_whitespace()
-        .map(|n| n.parse::<u64>().unwrap_or(0))
-        .collect();
-
-    let races = zip(times, distances).map(|(time, distance)| Race::new(time, distance));
-
-    races.fold(1, |acc, race| acc * race.winning_numbers.len())
+/// The race times and record distances, read both as separate races and, ignoring the
+/// spaces, as one long race.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Sheet {
+    races: Vec<(u64, u64)>,
+    race: (u64, u64),
 }
 
-pub fn part_2(input: &Vec<String>) -> usize {
-    let mut iter = input.iter();
-    let time = iter
-        .next()
-        .unwrap()
-        .split(":")
-        .last()
-        .unwrap()
-        .trim()
+/// Reads the numbers after `label`, both one by one and run together.
+fn parse_row(line: &str, label: &'static str) -> Result<(Vec<u64>, u64), ParseError> {
+    let trimmed = line.trim();
+    let numbers = trimmed
+        .strip_prefix(label)
+        .ok_or_else(|| ParseError::new(label, trimmed).within(line, trimmed))?
+        .trim();
+    let separate = numbers
         .split_whitespace()
-        .collect::<Vec<_>>()
-        .join("")
-        .parse::<u64>()
-        .unwrap_or(0);
-    let distance = iter
-        .next()
-        .unwrap()
-        .split(":")
-        .last()
-        .unwrap()
-        .trim()
+        .map(|n| parse_field(line, n, "a number"))
+        .collect::<Result<_, _>>()?;
+    let joined = numbers
         .split_whitespace()
         .collect::<Vec<_>>()
         .join("")
         .parse::<u64>()
-        .unwrap_or(0);
+        .map_err(|_| {
+            ParseError::new("numbers that fit in 64 bits run together", numbers)
+                .within(line, numbers)
+        })?;
+
+    Ok((separate, joined))
+}
 
+/// A `Time:` line followed by a `Distance:` line with as many numbers.
+pub fn parse(input: &str) -> Result<Sheet, ParseError> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let mut row = |label| match lines.next() {
+        Some((index, line)) => parse_row(line, label)
+            .map(|row| (index + 1, row))
+            .map_err(|e| e.on_line(index + 1)),
+        None => Err(ParseError::new(label, "").on_line(input.lines().count() + 1)),
+    };
+    let (_, (times, time)) = row("Time:")?;
+    let (line, (distances, distance)) = row("Distance:")?;
+    if times.len() != distances.len() {
+        return Err(ParseError::new("as many distances as times", "").on_line(line));
+    }
+
+    Ok(Sheet {
+        races: zip(times, distances).collect(),
+        race: (time, distance),
+    })
+}
+
+pub fn part_1(input: &Sheet) -> usize {
+    let races = input
+        .races
+        .iter()
+        .map(|&(time, distance)| Race::new(time, distance));
+
+    races.fold(1, |acc, race| acc * race.winning_numbers.len())
+}
+
+pub fn part_2(input: &Sheet) -> usize {
+    let (time, distance) = input.race;
     let race = Race::new(time, distance);
 
     race.winning_numbers.len()
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Sheet;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = "Time:      7  15   30
-        Distance:  9  40  200";
+        let input = parse(&example(2023, 6, 1)).unwrap();
 
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_1(&lines), 288);
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 6, 1, 1));
     }
 
     #[test]
@@ -107,10 +142,32 @@ mod tests {
 
     #[test]
     fn part_2_test() {
-        let input = "Time:      7  15   30
-        Distance:  9  40  200";
+        let input = parse(&example(2023, 6, 1)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 6, 1, 2));
+    }
 
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_2(&lines), 71503);
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse("Time: 7 x\nDistance: 9 40").unwrap_err(),
+            ParseError::new("a number", "x").at(1, 9)
+        );
+        assert_eq!(
+            parse("Time: 7 15\n\nDistance: 9").unwrap_err(),
+            ParseError::new("as many distances as times", "").on_line(3)
+        );
+        assert_eq!(
+            parse("Time: 7 15").unwrap_err(),
+            ParseError::new("Distance:", "").on_line(2)
+        );
+        assert_eq!(
+            parse("Time: 99999999999 99999999999\nDistance: 9 40").unwrap_err(),
+            ParseError::new(
+                "numbers that fit in 64 bits run together",
+                "99999999999 99999999999"
+            )
+            .at(1, 7)
+        );
     }
 }
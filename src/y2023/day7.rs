@@ -1,4 +1,7 @@
-use std::{cmp::Ordering, collections::HashMap};
+use crate::error::{parse_field, ParseError};
+use crate::solution::Solution;
+use crate::trace;
+use std::{cmp::Ordering, collections::HashMap, fmt::Display, str::FromStr};
 
 #[derive(Debug, PartialEq, PartialOrd, Eq, Ord)]
 enum Card {
@@ -47,6 +50,28 @@ enum HandType {
     FiveOfAKind,
 }
 
+/// Splits a line like `32T3K 765` into its 5 cards and the bid, placing errors
+/// within `line`.
+fn parse_line(line: &str) -> Result<(&str, u32), ParseError> {
+    let trimmed = line.trim();
+    let (cards, bet) = trimmed.split_once(' ').ok_or_else(|| {
+        ParseError::new("a hand and a bid like 32T3K 765", trimmed).within(line, trimmed)
+    })?;
+    if let Some((offset, c)) = cards
+        .char_indices()
+        .find(|(_, c)| !"123456789TJQKA".contains(*c))
+    {
+        let card = &cards[offset..offset + c.len_utf8()];
+        return Err(ParseError::new("a card", card).within(line, card));
+    }
+    if cards.len() != 5 {
+        return Err(ParseError::new("a hand of 5 cards", cards).within(line, cards));
+    }
+    let bet = parse_field(line, bet.trim(), "a bid")?;
+
+    Ok((cards, bet))
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct Hand {
     hand: Vec<Card>,
@@ -55,11 +80,7 @@ pub struct Hand {
 }
 
 impl Hand {
-    fn new(line: &str) -> Hand {
-        let mut parts = line.split(" ");
-        let hand_str = parts.next().unwrap();
-        let bet = parts.next().unwrap().parse().unwrap();
-
+    fn new(hand_str: &str, bet: u32) -> Hand {
         let mut hand: Vec<Card> = vec![];
         let mut char_count = HashMap::new();
         for char in hand_str.chars() {
@@ -83,7 +104,7 @@ impl Hand {
             let count: &mut u32 = char_count.entry(char).or_insert(0);
             *count += 1;
         }
-        let max = char_count.values().max_by(|a, b| a.cmp(b)).unwrap();
+        let max = char_count.values().max_by(|a, b| a.cmp(b)).unwrap_or(&0);
 
         let hand_type = match max {
             5 => HandType::FiveOfAKind,
@@ -113,6 +134,15 @@ impl Hand {
     }
 }
 
+impl FromStr for Hand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (hand_str, bet) = parse_line(s)?;
+        Ok(Hand::new(hand_str, bet))
+    }
+}
+
 impl PartialOrd for Hand {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -141,11 +171,7 @@ pub struct Hand2 {
 }
 
 impl Hand2 {
-    fn new(line: &str) -> Hand2 {
-        let mut parts = line.split(" ");
-        let hand_str = parts.next().unwrap();
-        let bet = parts.next().unwrap().parse().unwrap();
-
+    fn new(hand_str: &str, bet: u32) -> Hand2 {
         let mut hand: Vec<Card2> = vec![];
         let mut char_count = HashMap::new();
         for char in hand_str.chars() {
@@ -171,7 +197,7 @@ impl Hand2 {
         }
 
         let joker_count = match char_count.get(&'J') {
-            Some(count) => count.clone(),
+            Some(count) => *count,
             None => 0,
         };
         char_count.remove(&'J');
@@ -206,6 +232,15 @@ impl Hand2 {
     }
 }
 
+impl FromStr for Hand2 {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (hand_str, bet) = parse_line(s)?;
+        Ok(Hand2::new(hand_str, bet))
+    }
+}
+
 impl PartialOrd for Hand2 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -226,10 +261,36 @@ impl Ord for Hand2 {
     }
 }
 
-pub fn part_1(input: &Vec<String>) -> u32 {
-    let mut hands: Vec<_> = input.iter().map(|line| Hand::new(line)).collect();
+/// Every hand, read once with J as a jack and once with J as a joker.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Hands {
+    hands: Vec<Hand>,
+    joker_hands: Vec<Hand2>,
+}
+
+/// One hand and bid per non-empty line.
+pub fn parse(input: &str) -> Result<Hands, ParseError> {
+    let mut hands = Hands {
+        hands: vec![],
+        joker_hands: vec![],
+    };
+
+    for (index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (hand_str, bet) = parse_line(line).map_err(|e| e.on_line(index + 1))?;
+        hands.hands.push(Hand::new(hand_str, bet));
+        hands.joker_hands.push(Hand2::new(hand_str, bet));
+    }
+
+    Ok(hands)
+}
+
+pub fn part_1(input: &Hands) -> u32 {
+    let mut hands: Vec<_> = input.hands.iter().collect();
     hands.sort();
-    //println!("Hands in order {:?}", hands);
+    trace!("Hands in order {:?}", hands);
 
     hands
         .iter()
@@ -237,10 +298,10 @@ pub fn part_1(input: &Vec<String>) -> u32 {
         .fold(0, |acc, (idx, hand)| acc + (1 + idx as u32) * hand.bet)
 }
 
-pub fn part_2(input: &Vec<String>) -> u32 {
-    let mut hands: Vec<_> = input.iter().map(|line| Hand2::new(line)).collect();
+pub fn part_2(input: &Hands) -> u32 {
+    let mut hands: Vec<_> = input.joker_hands.iter().collect();
     hands.sort();
-    //println!("Hands in order {:?}", hands);
+    trace!("Hands in order {:?}", hands);
 
     hands
         .iter()
@@ -248,87 +309,124 @@ pub fn part_2(input: &Vec<String>) -> u32 {
         .fold(0, |acc, (idx, hand)| acc + (1 + idx as u32) * hand.bet)
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Hands;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
+
+    fn hand(line: &str) -> Hand {
+        line.parse().unwrap()
+    }
+
+    fn hand2(line: &str) -> Hand2 {
+        line.parse().unwrap()
+    }
 
     #[test]
     fn part_1_test() {
-        let input = "32T3K 765
-        T55J5 684
-        KK677 28
-        KTJJT 220
-        QQQJA 483";
-
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_1(&lines), 6440);
+        let input = parse(&example(2023, 7, 1)).unwrap();
+
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 7, 1, 1));
     }
 
     #[test]
     fn test_hands() {
         assert_eq!(
-            Hand::new("AAAAA 1"),
+            hand("AAAAA 1"),
             Hand {
                 hand: vec![Card::A, Card::A, Card::A, Card::A, Card::A],
                 hand_type: HandType::FiveOfAKind,
                 bet: 1
             }
         );
-        assert_eq!(Hand::new("AAAA1 1").hand_type, HandType::FourOfAKind,);
-        assert_eq!(Hand::new("AAA11 1").hand_type, HandType::FullHouse);
-        assert_eq!(Hand::new("AAA12 1").hand_type, HandType::ThreeOfKind);
-        assert_eq!(Hand::new("AA112 1").hand_type, HandType::TwoPair);
-        assert_eq!(Hand::new("AA123 1").hand_type, HandType::OnePair);
-        assert_eq!(Hand::new("12345 1").hand_type, HandType::HighCard);
+        assert_eq!(hand("AAAA1 1").hand_type, HandType::FourOfAKind,);
+        assert_eq!(hand("AAA11 1").hand_type, HandType::FullHouse);
+        assert_eq!(hand("AAA12 1").hand_type, HandType::ThreeOfKind);
+        assert_eq!(hand("AA112 1").hand_type, HandType::TwoPair);
+        assert_eq!(hand("AA123 1").hand_type, HandType::OnePair);
+        assert_eq!(hand("12345 1").hand_type, HandType::HighCard);
     }
 
     #[test]
     fn compare_hands() {
-        assert!(Hand::new("AAAA1 1") < Hand::new("AAAAA 1"));
-        assert!(Hand::new("1AAAA 1") < Hand::new("AAAA1 1"));
+        assert!(hand("AAAA1 1") < hand("AAAAA 1"));
+        assert!(hand("1AAAA 1") < hand("AAAA1 1"));
     }
 
     #[test]
     fn test_hands_2() {
-        assert_eq!(Hand2::new("AAAAA 1").hand_type, HandType::FiveOfAKind);
-        assert_eq!(Hand2::new("AAAAJ 1").hand_type, HandType::FiveOfAKind);
-        assert_eq!(Hand2::new("AAAJJ 1").hand_type, HandType::FiveOfAKind);
-        assert_eq!(Hand2::new("AAJJJ 1").hand_type, HandType::FiveOfAKind);
-        assert_eq!(Hand2::new("AJJJJ 1").hand_type, HandType::FiveOfAKind);
-        assert_eq!(Hand2::new("JJJJJ 1").hand_type, HandType::FiveOfAKind);
+        assert_eq!(hand2("AAAAA 1").hand_type, HandType::FiveOfAKind);
+        assert_eq!(hand2("AAAAJ 1").hand_type, HandType::FiveOfAKind);
+        assert_eq!(hand2("AAAJJ 1").hand_type, HandType::FiveOfAKind);
+        assert_eq!(hand2("AAJJJ 1").hand_type, HandType::FiveOfAKind);
+        assert_eq!(hand2("AJJJJ 1").hand_type, HandType::FiveOfAKind);
+        assert_eq!(hand2("JJJJJ 1").hand_type, HandType::FiveOfAKind);
 
-        assert_eq!(Hand2::new("AAAJ1 1").hand_type, HandType::FourOfAKind);
-        assert_eq!(Hand2::new("AAJJ1 1").hand_type, HandType::FourOfAKind);
-        assert_eq!(Hand2::new("AJJJ1 1").hand_type, HandType::FourOfAKind);
+        assert_eq!(hand2("AAAJ1 1").hand_type, HandType::FourOfAKind);
+        assert_eq!(hand2("AAJJ1 1").hand_type, HandType::FourOfAKind);
+        assert_eq!(hand2("AJJJ1 1").hand_type, HandType::FourOfAKind);
 
-        assert_eq!(Hand2::new("AAA11 1").hand_type, HandType::FullHouse);
-        assert_eq!(Hand2::new("AAJ11 1").hand_type, HandType::FullHouse);
+        assert_eq!(hand2("AAA11 1").hand_type, HandType::FullHouse);
+        assert_eq!(hand2("AAJ11 1").hand_type, HandType::FullHouse);
 
-        assert_eq!(Hand2::new("AAA12 1").hand_type, HandType::ThreeOfKind);
-        assert_eq!(Hand2::new("AJJ12 1").hand_type, HandType::ThreeOfKind);
-        assert_eq!(Hand2::new("AAJ12 1").hand_type, HandType::ThreeOfKind);
+        assert_eq!(hand2("AAA12 1").hand_type, HandType::ThreeOfKind);
+        assert_eq!(hand2("AJJ12 1").hand_type, HandType::ThreeOfKind);
+        assert_eq!(hand2("AAJ12 1").hand_type, HandType::ThreeOfKind);
 
-        assert_eq!(Hand2::new("AA112 1").hand_type, HandType::TwoPair);
-        assert_eq!(Hand2::new("AJ123 1").hand_type, HandType::OnePair);
+        assert_eq!(hand2("AA112 1").hand_type, HandType::TwoPair);
+        assert_eq!(hand2("AJ123 1").hand_type, HandType::OnePair);
 
-        assert_eq!(Hand2::new("12345 1").hand_type, HandType::HighCard);
+        assert_eq!(hand2("12345 1").hand_type, HandType::HighCard);
     }
 
     #[test]
     fn compare_hands_2() {
-        assert!(Hand2::new("JAAAA 1") < Hand2::new("11111 1"));
-        assert!(Hand2::new("JJJAA 1") < Hand2::new("11111 1"));
+        assert!(hand2("JAAAA 1") < hand2("11111 1"));
+        assert!(hand2("JJJAA 1") < hand2("11111 1"));
     }
 
     #[test]
     fn part_2_test() {
-        let input = "32T3K 765
-        T55J5 684
-        KK677 28
-        KTJJT 220
-        QQQJA 483";
-
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_2(&lines), 5905);
+        let input = parse(&example(2023, 7, 1)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 7, 1, 2));
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse("32T3K 765\nT55X5 684").unwrap_err(),
+            ParseError::new("a card", "X").at(2, 4)
+        );
+        assert_eq!(
+            parse("32T3 765").unwrap_err(),
+            ParseError::new("a hand of 5 cards", "32T3").at(1, 1)
+        );
+        assert_eq!(
+            parse("32T3K -1").unwrap_err(),
+            ParseError::new("a bid", "-1").at(1, 7)
+        );
+        assert_eq!(
+            "32T3K".parse::<Hand2>().unwrap_err(),
+            ParseError::new("a hand and a bid like 32T3K 765", "32T3K")
+        );
     }
 }
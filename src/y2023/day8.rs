@@ -1,66 +1,128 @@
+use crate::cycle::{self, Cycle};
+use crate::error::ParseError;
+use crate::math;
+use crate::solution::{or_dash, Solution};
+use crate::trace;
 use std::collections::HashMap;
-use std::slice::Iter;
+use std::fmt::Display;
 
 #[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Clone)]
-struct Node<'n, 'l, 'r> {
-    left: &'l str,
-    right: &'r str,
-    name: &'n str,
+struct Node {
+    left: String,
+    right: String,
+    name: String,
     ends_in_a: bool,
     ends_in_z: bool,
-    visited: bool,
-    visited_step: Option<u32>,
 }
 
-fn build_map(iter: Iter<'_, String>) -> HashMap<String, Node> {
-    let mut map: HashMap<String, Node> = HashMap::new();
-    iter.for_each(|line| {
-        let name = &line[..3];
-        let left = &line[7..10];
-        let right = &line[12..15];
+/// The left/right instructions and the nodes they walk, keyed by name.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Network {
+    moves: String,
+    map: HashMap<String, Node>,
+}
+
+/// Splits a node like `AAA = (BBB, CCC)` into its name, left and right.
+fn parse_node(line: &str) -> Result<(&str, &str, &str), ParseError> {
+    let trimmed = line.trim();
+    let error = || ParseError::new("a node like AAA = (BBB, CCC)", trimmed).within(line, trimmed);
+    let (name, targets) = trimmed.split_once(" = ").ok_or_else(error)?;
+    let (left, right) = targets
+        .strip_prefix('(')
+        .and_then(|targets| targets.strip_suffix(')'))
+        .and_then(|targets| targets.split_once(", "))
+        .ok_or_else(error)?;
+    for part in [name, left, right] {
+        if part.len() != 3 || !part.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(ParseError::new("a node name like AAA", part).within(line, part));
+        }
+    }
+
+    trace!("name {}, left {}, right {}", name, left, right);
+    Ok((name, left, right))
+}
 
-        // println!("name {}, left {}, right {}", name, left, right);
+/// The instructions on the first line, then one node per line. Every node a node
+/// leads to must be listed too.
+pub fn parse(input: &str) -> Result<Network, ParseError> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let Some((index, line)) = lines.next() else {
+        return Err(ParseError::new("the left/right instructions", ""));
+    };
+    let moves = line.trim();
+    if let Some((offset, c)) = moves.char_indices().find(|(_, c)| !matches!(c, 'L' | 'R')) {
+        let movement = &moves[offset..offset + c.len_utf8()];
+        return Err(ParseError::new("L or R", movement)
+            .on_line(index + 1)
+            .within(line, movement));
+    }
+
+    let mut nodes = vec![];
+    for (index, line) in lines {
+        let (name, left, right) = parse_node(line).map_err(|e| e.on_line(index + 1))?;
+        if nodes.iter().any(|(_, _, other, _, _)| *other == name) {
+            return Err(ParseError::new("a node not listed before", name)
+                .on_line(index + 1)
+                .within(line, name));
+        }
+        nodes.push((index, line, name, left, right));
+    }
+
+    let mut map: HashMap<String, Node> = HashMap::new();
+    for &(index, line, name, left, right) in &nodes {
+        for target in [left, right] {
+            if !nodes.iter().any(|(_, _, other, _, _)| *other == target) {
+                return Err(ParseError::new("a listed node", target)
+                    .on_line(index + 1)
+                    .within(line, target));
+            }
+        }
         map.insert(
             name.to_string(),
             Node {
-                left,
-                right,
-                name,
+                left: left.to_string(),
+                right: right.to_string(),
+                name: name.to_string(),
                 ends_in_a: ends_in(name, 'A'),
                 ends_in_z: ends_in(name, 'Z'),
-                visited: false,
-                visited_step: None,
             },
         );
-    });
+    }
 
-    map
+    Ok(Network {
+        moves: moves.to_string(),
+        map,
+    })
 }
 
-fn find_ending_with_a<'a>(map: &'a HashMap<String, Node<'a, 'a, 'a>>) -> Vec<&'a Node<'a, 'a, 'a>> {
+fn find_ending_with_a(map: &HashMap<String, Node>) -> Vec<&Node> {
     map.iter()
         .filter(|(_, node)| node.ends_in_a)
         .map(|(_, node)| node)
         .collect()
 }
 
-pub fn part_1(input: &Vec<String>) -> u32 {
-    let mut iter = input.iter();
-    let moves = (iter.next().unwrap()).clone();
-
-    iter.next();
-
-    let map = build_map(iter);
+/// The steps from `AAA` to `ZZZ`, if there is such a walk.
+pub fn part_1(input: &Network) -> Option<u32> {
+    let Network { moves, map } = input;
     let mut current = "AAA";
     let mut step: u32 = 0;
+    // Past this many steps some node and instruction must have repeated.
+    let limit = map.len() * moves.len();
 
     'outer: loop {
         for movement in moves.chars() {
             if current == "ZZZ" {
                 break 'outer;
             }
+            if step as usize > limit {
+                return None;
+            }
 
-            let node = map.get(current).unwrap();
+            let node = map.get(current)?;
             current = match movement {
                 'L' => &node.left,
                 _ => &node.right,
@@ -69,186 +131,152 @@ pub fn part_1(input: &Vec<String>) -> u32 {
         }
     }
 
-    step
+    Some(step)
 }
 
 fn ends_in(str: &str, ch: char) -> bool {
     str.chars().nth(2) == Some(ch)
 }
 
-fn all_ends_in_z(vec: &Vec<&Node>) -> bool {
-    vec.iter().fold(true, |acc, val| acc && val.ends_in_z)
-}
-
-fn loop_size<'a>(
-    node: &Node,
-    moves: &str,
-    map: &HashMap<String, Node<'a, 'a, 'a>>,
-) -> (u32, u32, Vec<&'a str>) {
-    let mut map = map.clone();
-    let mut current = map.get(node.name).unwrap().clone();
-    let mut step = 0;
-    let mut vec: Vec<&str> = vec![];
-
-    'outer: loop {
-        for movement in moves.chars() {
-            if current.visited {
-                break 'outer;
-            }
-
-            vec.push(current.name);
-            current.visited = true;
-            current.visited_step = Some(step);
-
-            map.insert(current.name.to_string(), current.clone());
-
-            current = match movement {
-                'L' => map.get(&current.left.to_string()).unwrap().clone(),
-                _ => map.get(&current.right.to_string()).unwrap().clone(),
-            };
-
-            step += 1;
-        }
-    }
+/// The cycle a ghost starting at `node` falls into, its state being the node and the
+/// position in `moves`, and the steps before the first repeat that land on a `..Z` node.
+fn loop_size(node: &Node, moves: &str, map: &HashMap<String, Node>) -> (Cycle, Vec<usize>) {
+    let step = |&(name, index): &(&str, usize)| -> (&str, usize) {
+        let node = &map[name];
+        let next = match moves.as_bytes()[index] {
+            b'L' => &node.left,
+            _ => &node.right,
+        };
+        (next, (index + 1) % moves.len())
+    };
+
+    let start = (&*node.name, 0);
+    let cycle = cycle::find_cycle(start, step);
+    let ends_in_z = std::iter::successors(Some(start), |state| Some(step(state)))
+        .take(cycle.prefix + cycle.period)
+        .enumerate()
+        .filter(|(_, (name, _))| map[*name].ends_in_z)
+        .map(|(step, _)| step)
+        .collect();
 
-    (
-        current.visited_step.unwrap(),
-        step - current.visited_step.unwrap(),
-        vec,
-    )
+    (cycle, ends_in_z)
 }
 
-pub fn greatest_common_divisor(a: u64, b: u64) -> u64 {
-    if b == 0 {
-        return a;
-    }
-    greatest_common_divisor(b, a % b)
-}
+/// The first step with every ghost on a `..Z` node, or None if they never all are.
+pub fn part_2(input: &Network) -> Option<u64> {
+    let Network { moves, map } = input;
+    let loops: Vec<_> = find_ending_with_a(map)
+        .iter()
+        .map(|node| loop_size(node, moves, map))
+        .collect();
 
-pub fn lowest_common_multiple(a: u64, b: u64) -> u64 {
-    if a > b {
-        return (a / greatest_common_divisor(a, b)) * b;
+    // Until every ghost is inside its loop, check each step directly.
+    let settled = loops
+        .iter()
+        .map(|(cycle, _)| cycle.prefix)
+        .max()
+        .unwrap_or(0);
+    let at_z = |step: usize| {
+        loops
+            .iter()
+            .all(|(cycle, ends_in_z)| ends_in_z.contains(&cycle.index(step)))
+    };
+    if let Some(step) = (0..settled).find(|step| at_z(*step)) {
+        return Some(step as u64);
     }
-    (b / greatest_common_divisor(a, b)) * a
-}
 
-pub fn part_2(input: &Vec<String>) -> u64 {
-    let mut iter = input.iter();
-    let moves = (iter.next().unwrap()).clone();
-    iter.next();
-
-    let map = build_map(iter);
-    let mut current = find_ending_with_a(&map);
+    // Afterwards each ghost is on a `..Z` node every `period` steps from each of them.
+    let mut congruences = vec![(0, 1)];
+    for (cycle, ends_in_z) in &loops {
+        congruences = congruences
+            .iter()
+            .flat_map(|&congruence| {
+                ends_in_z
+                    .iter()
+                    .filter(|step| **step >= cycle.prefix)
+                    .filter_map(move |&step| {
+                        math::crt(&[congruence, (step as i64, cycle.period as i64)])
+                    })
+            })
+            .collect();
+    }
 
-    let _loop_sizes: Vec<_> = current
+    congruences
         .iter()
-        .map(|node| loop_size(node, &moves, &map))
-        .collect();
-    // println!("starting positions: {:?}", current);
-    // println!("loop_sizes positions: {:?}", loop_sizes);
-
-    let mut step: u64 = 0;
-    let mut already_visited: Vec<(usize, u64)> = vec![];
-
-    // let mut   found_first = false;
-    'outer: loop {
-        let movement = moves.as_bytes()[step as usize % moves.len()];
+        .map(|&(residue, modulus)| {
+            let below = (settled as i64 - residue).max(0);
+            (residue + (below + modulus - 1) / modulus * modulus) as u64
+        })
+        .min()
+}
 
-        if all_ends_in_z(&current) {
-            break 'outer;
-        }
+pub struct Solver;
 
-        let _last: Vec<_> = current.clone().iter().map(|node| node.name).collect();
-        let mut next: Vec<&Node> = vec![];
-
-        for (idx, node) in current.clone().into_iter().enumerate() {
-            if ends_in(current[idx].name, 'Z') {
-                // println!(
-                //     "Node {} name {} ends in Z, step {}, cloned {:?}, found_first {}",
-                //     idx, current[idx].name, step, last, found_first
-                // );
-                already_visited.push((idx, step));
-                // found_first = true;
-            }
-            let node = match movement {
-                b'L' => map.get(&node.left.to_string()),
-                _ => map.get(&node.right.to_string()),
-            };
+impl Solution for Solver {
+    type Input = Network;
 
-            if let Some(node) = node {
-                next.push(node);
-            }
-        }
-        current = next;
-        if already_visited.len() == 6 {
-            return already_visited
-                .iter()
-                .fold(1, |acc, (_, step)| lowest_common_multiple(acc, *step));
-        }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
 
-        step += 1;
+    fn part_1(input: &Self::Input) -> impl Display {
+        or_dash(part_1(input))
     }
 
-    step
+    fn part_2(input: &Self::Input) -> impl Display {
+        or_dash(part_2(input))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = "RL
-
-        AAA = (BBB, CCC)
-        BBB = (DDD, EEE)
-        CCC = (ZZZ, GGG)
-        DDD = (DDD, DDD)
-        EEE = (EEE, EEE)
-        GGG = (GGG, GGG)
-        ZZZ = (ZZZ, ZZZ)";
+        let input = parse(&example(2023, 8, 1)).unwrap();
 
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_1(&lines), 2);
+        assert_eq!(or_dash(part_1(&input)), example_answer(2023, 8, 1, 1));
     }
 
     #[test]
     fn part_1_test_2() {
-        let input = "LLR
-
-        AAA = (BBB, BBB)
-        BBB = (AAA, ZZZ)
-        ZZZ = (ZZZ, ZZZ)";
+        let input = parse(&example(2023, 8, 2)).unwrap();
 
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_1(&lines), 6);
+        assert_eq!(or_dash(part_1(&input)), example_answer(2023, 8, 2, 1));
     }
 
     #[test]
     fn find_ending_with_a_test() {
-        let input: Vec<_> = "AAA = (BBB, CCC)
+        let input = "L
+
+        AAA = (BBB, CCC)
         BBA = (DDD, EEE)
         CCA = (ZZZ, GGG)
         DDD = (DDD, DDD)
         EEE = (EEE, EEE)
         GGG = (GGG, GGG)
-        ZZZ = (ZZZ, ZZZ)"
-            .lines()
-            .map(|line| line.trim().to_string())
-            .collect();
-        let iter = input.iter();
+        ZZZ = (ZZZ, ZZZ)
+        BBB = (BBB, BBB)
+        CCC = (CCC, CCC)";
 
-        let map = build_map(iter);
+        let map = parse(input).unwrap().map;
 
-        assert_eq!(
-            find_ending_with_a(&map).sort(),
-            vec!["AAA", "BBA", "CCA"].sort()
-        );
+        let mut names: Vec<_> = find_ending_with_a(&map)
+            .iter()
+            .map(|node| &*node.name)
+            .collect();
+        names.sort();
+
+        assert_eq!(names, vec!["AAA", "BBA", "CCA"]);
     }
 
     #[test]
     fn loop_size_test() {
-        let input = "11A = (11B, XXX)
+        let input = "LR
+
+        11A = (11B, XXX)
         12A = (12B, XXX)
         12B = (XXX, 11A)
         11B = (XXX, 11Z)
@@ -259,33 +287,89 @@ mod tests {
         22Z = (22B, 22B)
         XXX = (XXX, XXX)";
 
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        let iter = lines.iter();
-        let map = build_map(iter);
+        let map = parse(input).unwrap().map;
         let node1 = map.get("11A").unwrap();
         let node2 = map.get("22A").unwrap();
         let node3 = map.get("12A").unwrap();
         let moves = "LR";
 
-        assert_eq!(loop_size(node3, moves, &map), (3 as u32, 2 as u32, vec![]),);
-        assert_eq!(loop_size(node1, moves, &map), (1 as u32, 2 as u32, vec![]));
-        assert_eq!(loop_size(node2, moves, &map), (1 as u32, 3 as u32, vec![]));
+        assert_eq!(
+            loop_size(node3, moves, &map),
+            (
+                Cycle {
+                    prefix: 3,
+                    period: 2
+                },
+                vec![4]
+            )
+        );
+        assert_eq!(
+            loop_size(node1, moves, &map),
+            (
+                Cycle {
+                    prefix: 1,
+                    period: 2
+                },
+                vec![2]
+            )
+        );
+        assert_eq!(
+            loop_size(node2, moves, &map),
+            (
+                Cycle {
+                    prefix: 1,
+                    period: 6
+                },
+                vec![3, 6]
+            )
+        );
     }
 
     #[test]
     fn part_2_test() {
-        let input = "LR
+        let input = parse(&example(2023, 8, 3)).unwrap();
 
-        11A = (11B, XXX)
-        11B = (XXX, 11Z)
-        11Z = (11B, XXX)
-        22A = (22B, XXX)
-        22B = (22C, 22C)
-        22C = (22Z, 22Z)
-        22Z = (22B, 22B)
-        XXX = (XXX, XXX)";
+        assert_eq!(or_dash(part_2(&input)), example_answer(2023, 8, 3, 2));
+    }
+
+    #[test]
+    fn part_1_unreachable_test() {
+        let input = parse("L\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        assert_eq!(part_1(&input), None);
+
+        let input = parse("L\n\n11A = (11Z, 11Z)\n11Z = (11A, 11A)").unwrap();
+        assert_eq!(part_1(&input), None);
+    }
+
+    #[test]
+    fn part_2_unreachable_test() {
+        // The first ghost is on 11Z after odd steps only, the second after even ones.
+        let input = parse(
+            "L\n\n11A = (11Z, 11Z)\n11Z = (11A, 11A)\n22A = (22B, 22B)\n22B = (22Z, 22Z)\n22Z = (22B, 22B)",
+        )
+        .unwrap();
+
+        assert_eq!(part_2(&input), None);
+        assert_eq!(Solver::part_2(&input).to_string(), "-");
+    }
 
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_2(&lines), 6);
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse("LRX\n\nAAA = (AAA, AAA)").unwrap_err(),
+            ParseError::new("L or R", "X").at(1, 3)
+        );
+        assert_eq!(
+            parse("LR\n\nAAA = (BBB, AAA)").unwrap_err(),
+            ParseError::new("a listed node", "BBB").at(3, 8)
+        );
+        assert_eq!(
+            parse("LR\n\nAAA = (AAA AAA)").unwrap_err(),
+            ParseError::new("a node like AAA = (BBB, CCC)", "AAA = (AAA AAA)").on_line(3)
+        );
+        assert_eq!(
+            parse("LR\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)").unwrap_err(),
+            ParseError::new("a node not listed before", "AAA").on_line(4)
+        );
     }
 }
@@ -1,3 +1,7 @@
+use crate::error::{parse_field, ParseError};
+use crate::solution::Solution;
+use std::fmt::Display;
+
 fn next_in_sequence_helper(numbers: Vec<i32>) -> i32 {
     let mut deltas: Vec<i32> = vec![];
 
@@ -12,15 +16,6 @@ fn next_in_sequence_helper(numbers: Vec<i32>) -> i32 {
     numbers[numbers.len() - 1] + next_in_sequence_helper(deltas)
 }
 
-fn next_in_sequence(line: &str) -> i32 {
-    let numbers: Vec<i32> = line
-        .split(" ")
-        .map(|raw| raw.parse::<i32>().unwrap_or(0))
-        .collect();
-
-    next_in_sequence_helper(numbers)
-}
-
 fn previous_in_sequence_helper(numbers: Vec<i32>) -> i32 {
     let mut deltas: Vec<i32> = vec![];
 
@@ -35,38 +30,63 @@ fn previous_in_sequence_helper(numbers: Vec<i32>) -> i32 {
     numbers[0] - previous_in_sequence_helper(deltas)
 }
 
-fn previous_in_sequence(line: &str) -> i32 {
-    let numbers: Vec<i32> = line
-        .split(" ")
-        .map(|raw| raw.parse::<i32>().unwrap_or(0))
-        .collect();
-
-    previous_in_sequence_helper(numbers)
+/// One history of readings per non-empty line.
+pub fn parse(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            line.split_whitespace()
+                .map(|raw| parse_field(line, raw, "a reading"))
+                .collect::<Result<Vec<i32>, ParseError>>()
+                .map_err(|e| e.on_line(index + 1))
+        })
+        .collect()
 }
 
-pub fn part_1(input: &Vec<String>) -> i32 {
-    input.iter().map(|line| next_in_sequence(line)).sum()
+pub fn part_1(input: &[Vec<i32>]) -> i32 {
+    input
+        .iter()
+        .map(|numbers| next_in_sequence_helper(numbers.clone()))
+        .sum()
 }
 
-pub fn part_2(input: &Vec<String>) -> i32 {
+pub fn part_2(input: &[Vec<i32>]) -> i32 {
     input
         .iter()
-        .map(|line: &String| previous_in_sequence(line))
+        .map(|numbers| previous_in_sequence_helper(numbers.clone()))
         .sum()
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = "0 3 6 9 12 15
-        1 3 6 10 15 21
-        10 13 16 21 30 45";
+        let input = parse(&example(2023, 9, 1)).unwrap();
 
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_1(&lines), 114);
+        assert_eq!(part_1(&input).to_string(), example_answer(2023, 9, 1, 1));
     }
 
     #[test]
@@ -78,12 +98,9 @@ mod tests {
 
     #[test]
     fn part_2_test() {
-        let input = "0 3 6 9 12 15
-        1 3 6 10 15 21
-        10 13 16 21 30 45";
+        let input = parse(&example(2023, 9, 1)).unwrap();
 
-        let lines: Vec<String> = input.lines().map(|l| l.trim().to_string()).collect();
-        assert_eq!(part_2(&lines), 2);
+        assert_eq!(part_2(&input).to_string(), example_answer(2023, 9, 1, 2));
     }
 
     #[test]
@@ -92,4 +109,12 @@ mod tests {
         assert_eq!(previous_in_sequence_helper(vec![1, 3, 6, 10, 15, 21]), 0);
         assert_eq!(previous_in_sequence_helper(vec![10, 13, 16, 21, 30, 45]), 5);
     }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse("0 3 6\n1 3 x 10").unwrap_err(),
+            ParseError::new("a reading", "x").at(2, 5)
+        );
+    }
 }
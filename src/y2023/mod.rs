@@ -1,3 +1,5 @@
+use crate::solution::Entry;
+
 pub mod day1;
 pub mod day10;
 pub mod day11;
@@ -11,6 +13,11 @@ pub mod day18;
 pub mod day19;
 pub mod day2;
 pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
 pub mod day3;
 pub mod day4;
 pub mod day5;
@@ -19,4 +26,32 @@ pub mod day7;
 pub mod day8;
 pub mod day9;
 
-pub mod direction;
+pub fn solutions() -> Vec<Entry> {
+    vec![
+        Entry::new::<day1::Solver>(2023, 1),
+        Entry::new::<day2::Solver>(2023, 2),
+        Entry::new::<day3::Solver>(2023, 3),
+        Entry::new::<day4::Solver>(2023, 4),
+        Entry::new::<day5::Solver>(2023, 5),
+        Entry::new::<day6::Solver>(2023, 6),
+        Entry::new::<day7::Solver>(2023, 7),
+        Entry::new::<day8::Solver>(2023, 8),
+        Entry::new::<day9::Solver>(2023, 9),
+        Entry::new::<day10::Solver>(2023, 10),
+        Entry::new::<day11::Solver>(2023, 11),
+        Entry::new::<day12::Solver>(2023, 12),
+        Entry::new::<day13::Solver>(2023, 13),
+        Entry::new::<day14::Solver>(2023, 14),
+        Entry::new::<day15::Solver>(2023, 15),
+        Entry::new::<day16::Solver>(2023, 16),
+        Entry::new::<day17::Solver>(2023, 17),
+        Entry::new::<day18::Solver>(2023, 18),
+        Entry::new::<day19::Solver>(2023, 19),
+        Entry::new::<day20::Solver>(2023, 20),
+        Entry::new::<day21::Solver>(2023, 21),
+        Entry::new::<day22::Solver>(2023, 22),
+        Entry::new::<day23::Solver>(2023, 23),
+        Entry::new::<day24::Solver>(2023, 24),
+        Entry::new::<day25::Solver>(2023, 25),
+    ]
+}
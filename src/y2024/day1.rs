@@ -1,19 +1,30 @@
-use std::{collections::HashMap, str::FromStr};
+use crate::error::{parse_field, ParseError};
+use crate::solution::Solution;
+use std::{collections::HashMap, fmt::Display};
 
-pub fn part_1(input: &str) -> i32 {
+/// The left and right location lists, in input order.
+pub type Lists = (Vec<i32>, Vec<i32>);
+
+pub fn parse(input: &str) -> Result<Lists, ParseError> {
     let mut l: Vec<i32> = vec![];
     let mut r: Vec<i32> = vec![];
 
-    for line in input.lines() {
-        let nums: Vec<i32> = line
-            .split_whitespace()
-            .map(i32::from_str)
-            .map(Result::unwrap)
-            .collect();
-        l.push(nums[0]);
-        r.push(nums[1]);
+    for (index, line) in input.lines().enumerate() {
+        let mut fields = line.split_whitespace();
+        let (Some(left), Some(right)) = (fields.next(), fields.next()) else {
+            return Err(ParseError::new("two numbers", line).on_line(index + 1));
+        };
+        l.push(parse_field(line, left, "a number").map_err(|e| e.on_line(index + 1))?);
+        r.push(parse_field(line, right, "a number").map_err(|e| e.on_line(index + 1))?);
     }
 
+    Ok((l, r))
+}
+
+pub fn part_1((l, r): &Lists) -> i32 {
+    let mut l = l.clone();
+    let mut r = r.clone();
+
     l.sort();
     r.sort();
 
@@ -25,51 +36,64 @@ pub fn part_1(input: &str) -> i32 {
     sum
 }
 
-pub fn part_2(input: &str) -> i32 {
-    let mut l: Vec<i32> = vec![];
-    let mut r: HashMap<i32, i32> = HashMap::new();
-
-    for line in input.lines() {
-        let nums: Vec<i32> = line
-            .split_whitespace()
-            .map(i32::from_str)
-            .map(Result::unwrap)
-            .collect();
-        l.push(nums[0]);
-        let count: &mut i32 = r.entry(nums[1]).or_insert(0);
+pub fn part_2((l, r): &Lists) -> i32 {
+    let mut counts: HashMap<i32, i32> = HashMap::new();
+    for val in r {
+        let count: &mut i32 = counts.entry(*val).or_insert(0);
         *count += 1;
     }
 
     l.iter()
-        .map(|val| *r.entry(*val).or_insert(0) * (*val))
+        .map(|val| *counts.entry(*val).or_insert(0) * (*val))
         .sum()
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Lists;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = "3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
-
-        assert_eq!(part_1(input), 11);
+        let input = parse(&example(2024, 1, 1)).unwrap();
+
+        assert_eq!(part_1(&input).to_string(), example_answer(2024, 1, 1, 1));
     }
 
     #[test]
     fn part_2_test() {
-        let input = "3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
-
-        assert_eq!(part_2(input), 31);
+        let input = parse(&example(2024, 1, 1)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2024, 1, 1, 2));
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse("3   4\n4   x3"),
+            Err(ParseError::new("a number", "x3").at(2, 5))
+        );
+        assert_eq!(
+            parse("3   4\n4"),
+            Err(ParseError::new("two numbers", "4").at(2, 1))
+        );
     }
 }
@@ -1,36 +1,34 @@
-pub fn part_1(_input: &str) -> i32 {
+use crate::error::ParseError;
+use crate::solution::{lines, Solution};
+use std::fmt::Display;
+
+/// One entry per line until the day is solved and its input gets a real type.
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    Ok(lines(input))
+}
+
+pub fn part_1(_input: &[String]) -> i32 {
     0
 }
 
-pub fn part_2(_input: &str) -> i32 {
+pub fn part_2(_input: &[String]) -> i32 {
     0
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Solver;
 
-    #[test]
-    fn part_1_test() {
-        let input = "3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
+impl Solution for Solver {
+    type Input = Vec<String>;
 
-        assert_eq!(part_1(input), 0);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
     }
 
-    #[test]
-    fn part_2_test() {
-        let input = "3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
 
-        assert_eq!(part_2(input), 0);
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
     }
 }
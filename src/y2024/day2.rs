@@ -1,4 +1,6 @@
-use std::str::FromStr;
+use crate::error::{parse_field, ParseError};
+use crate::solution::Solution;
+use std::fmt::Display;
 
 #[derive(PartialEq, Clone, Copy)]
 enum Direction {
@@ -10,7 +12,7 @@ pub fn is_safe(nums: &[i32]) -> bool {
     let mut safe = true;
     let mut direction: Option<Direction> = None;
 
-    for i in 0..nums.len() - 1 {
+    for i in 0..nums.len().saturating_sub(1) {
         let a = nums[i];
         let b = nums[i + 1];
         let delta = a - b;
@@ -34,20 +36,29 @@ pub fn is_safe(nums: &[i32]) -> bool {
         }
     }
 
-    return safe;
+    safe
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let mut count = 0;
+/// One report of levels per non-empty line.
+pub fn parse(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            line.split_whitespace()
+                .map(|level| parse_field(line, level, "a level"))
+                .collect::<Result<Vec<i32>, ParseError>>()
+                .map_err(|e| e.on_line(index + 1))
+        })
+        .collect()
+}
 
-    for line in input.lines() {
-        let nums: Vec<i32> = line
-            .split_whitespace()
-            .map(i32::from_str)
-            .map(Result::unwrap)
-            .collect();
+pub fn part_1(reports: &[Vec<i32>]) -> i32 {
+    let mut count = 0;
 
-        if is_safe(&nums) {
+    for nums in reports {
+        if is_safe(nums) {
             count += 1;
         }
     }
@@ -55,17 +66,11 @@ pub fn part_1(input: &str) -> i32 {
     count
 }
 
-pub fn part_2(input: &str) -> i32 {
+pub fn part_2(reports: &[Vec<i32>]) -> i32 {
     let mut count = 0;
 
-    for line in input.lines() {
-        let nums: Vec<i32> = line
-            .split_whitespace()
-            .map(i32::from_str)
-            .map(Result::unwrap)
-            .collect();
-
-        if is_safe(&nums) {
+    for nums in reports {
+        if is_safe(nums) {
             count += 1;
         } else {
             for n in 0..nums.len() {
@@ -89,31 +94,48 @@ pub fn part_2(input: &str) -> i32 {
     count
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
-
-        assert_eq!(part_1(input), 2);
+        let input = parse(&example(2024, 2, 1)).unwrap();
+
+        assert_eq!(part_1(&input).to_string(), example_answer(2024, 2, 1, 1));
     }
 
     #[test]
     fn part_2_test() {
-        let input = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
-
-        assert_eq!(part_2(input), 4);
+        let input = parse(&example(2024, 2, 1)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2024, 2, 1, 2));
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse("7 6 4 2 1\n1 2 7 x 9").unwrap_err(),
+            ParseError::new("a level", "x").at(2, 7)
+        );
     }
 }
@@ -1,10 +1,17 @@
+use crate::error::{parse_field, ParseError};
+use crate::solution::Solution;
+use once_cell::sync::Lazy;
 use regex::Regex;
+use std::fmt::Display;
 
-#[derive(PartialEq, Clone, Copy)]
-enum Op {
+static INSTRUCTION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(mul)\((\d{1,3}),(\d{1,3})\)|(do)\(\)()()|(don't)\(\)()()").unwrap());
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Instruction {
     Do,
     Dont,
-    Mul,
+    Mul(i32, i32),
 }
 
 #[derive(Debug)]
@@ -13,40 +20,45 @@ struct State {
     count: i32,
 }
 
-pub fn part_2(input: &str) -> i32 {
-    let regex = Regex::new(r"(mul)\((\d{1,3}),(\d{1,3})\)|(do)\(\)()()|(don't)\(\)()()").unwrap();
-    let all: Vec<(Op, i32, i32)> = regex
-        .captures_iter(input)
-        .map(|caps| {
+/// Picks the instructions out of the corrupted memory, skipping everything else.
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let mut instructions = vec![];
+
+    for (index, line) in input.lines().enumerate() {
+        for caps in INSTRUCTION_REGEX.captures_iter(line) {
             let (_, [op, a, b]) = caps.extract();
-            match op {
-                "mul" => (
-                    Op::Mul,
-                    a.parse::<i32>().unwrap(),
-                    b.parse::<i32>().unwrap(),
+            instructions.push(match op {
+                "mul" => Instruction::Mul(
+                    parse_field(line, a, "a number").map_err(|e| e.on_line(index + 1))?,
+                    parse_field(line, b, "a number").map_err(|e| e.on_line(index + 1))?,
                 ),
-                "do" => (Op::Do, 0, 0),
-                _ => (Op::Dont, 0, 0),
-            }
-        })
-        .collect();
+                "do" => Instruction::Do,
+                _ => Instruction::Dont,
+            });
+        }
+    }
 
-    all.into_iter()
+    Ok(instructions)
+}
+
+pub fn part_2(instructions: &[Instruction]) -> i32 {
+    instructions
+        .iter()
         .fold(
             State {
                 enabled: true,
                 count: 0,
             },
-            |acc, (op, a, b)| match op {
-                Op::Do => State {
+            |acc, instruction| match instruction {
+                Instruction::Do => State {
                     enabled: true,
                     count: acc.count,
                 },
-                Op::Dont => State {
+                Instruction::Dont => State {
                     enabled: false,
                     count: acc.count,
                 },
-                Op::Mul => {
+                Instruction::Mul(a, b) => {
                     if acc.enabled {
                         State {
                             enabled: true,
@@ -61,33 +73,57 @@ pub fn part_2(input: &str) -> i32 {
         .count
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let regex = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
-    let all: Vec<(i32, i32)> = regex
-        .captures_iter(input)
-        .map(|caps| {
-            let (_, [a, b]) = caps.extract();
-            (a.parse::<i32>().unwrap(), b.parse::<i32>().unwrap())
+pub fn part_1(instructions: &[Instruction]) -> i32 {
+    instructions
+        .iter()
+        .fold(0, |acc, instruction| match instruction {
+            Instruction::Mul(a, b) => acc + a * b,
+            _ => acc,
         })
-        .collect();
-    all.into_iter().fold(0, |acc, (a, b)| acc + a * b)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{example, example_answer};
 
     #[test]
     fn part_1_test() {
-        let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+        let input = parse(&example(2024, 3, 1)).unwrap();
 
-        assert_eq!(part_1(input), 161);
+        assert_eq!(part_1(&input).to_string(), example_answer(2024, 3, 1, 1));
     }
 
     #[test]
     fn part_2_test() {
-        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        let input = parse(&example(2024, 3, 2)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer(2024, 3, 2, 2));
+    }
 
-        assert_eq!(part_2(input), 48);
+    #[test]
+    fn parse_test() {
+        assert_eq!(
+            parse("mul(4*mul(6,9!?(12,34)do()\nmul ( 2 , 4 )don't()mul(123,4)").unwrap(),
+            vec![Instruction::Do, Instruction::Dont, Instruction::Mul(123, 4)]
+        );
     }
 }
@@ -1,3 +1,5 @@
+use crate::solution::Entry;
+
 pub mod day1;
 pub mod day10;
 pub mod day11;
@@ -23,3 +25,33 @@ pub mod day6;
 pub mod day7;
 pub mod day8;
 pub mod day9;
+
+pub fn solutions() -> Vec<Entry> {
+    vec![
+        Entry::new::<day1::Solver>(2024, 1),
+        Entry::new::<day2::Solver>(2024, 2),
+        Entry::new::<day3::Solver>(2024, 3),
+        Entry::new::<day4::Solver>(2024, 4),
+        Entry::new::<day5::Solver>(2024, 5),
+        Entry::new::<day6::Solver>(2024, 6),
+        Entry::new::<day7::Solver>(2024, 7),
+        Entry::new::<day8::Solver>(2024, 8),
+        Entry::new::<day9::Solver>(2024, 9),
+        Entry::new::<day10::Solver>(2024, 10),
+        Entry::new::<day11::Solver>(2024, 11),
+        Entry::new::<day12::Solver>(2024, 12),
+        Entry::new::<day13::Solver>(2024, 13),
+        Entry::new::<day14::Solver>(2024, 14),
+        Entry::new::<day15::Solver>(2024, 15),
+        Entry::new::<day16::Solver>(2024, 16),
+        Entry::new::<day17::Solver>(2024, 17),
+        Entry::new::<day18::Solver>(2024, 18),
+        Entry::new::<day19::Solver>(2024, 19),
+        Entry::new::<day20::Solver>(2024, 20),
+        Entry::new::<day21::Solver>(2024, 21),
+        Entry::new::<day22::Solver>(2024, 22),
+        Entry::new::<day23::Solver>(2024, 23),
+        Entry::new::<day24::Solver>(2024, 24),
+        Entry::new::<day25::Solver>(2024, 25),
+    ]
+}
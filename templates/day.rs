@@ -0,0 +1,56 @@
+use crate::error::ParseError;
+use crate::solution::{lines, Solution};
+use std::fmt::Display;
+
+/// One entry per line until the day is solved and its input gets a real type.
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    Ok(lines(input))
+}
+
+pub fn part_1(_input: &[String]) -> usize {
+    0
+}
+
+pub fn part_2(_input: &[String]) -> usize {
+    0
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::{example, example_answer};
+
+    #[test]
+    #[ignore = "needs the extracted example and a solution, remove once part 1 is solved"]
+    fn part_1_test() {
+        let input = parse(&example({{year}}, {{day}}, 1)).unwrap();
+
+        assert_eq!(part_1(&input).to_string(), example_answer({{year}}, {{day}}, 1, 1));
+    }
+
+    #[test]
+    #[ignore = "needs the extracted example and a solution, remove once part 2 is solved"]
+    fn part_2_test() {
+        let input = parse(&example({{year}}, {{day}}, 1)).unwrap();
+
+        assert_eq!(part_2(&input).to_string(), example_answer({{year}}, {{day}}, 1, 2));
+    }
+}